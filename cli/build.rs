@@ -15,6 +15,7 @@
 use std::path::Path;
 use std::process::Command;
 use std::str;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use cargo_metadata::MetadataCommand;
 
@@ -40,12 +41,26 @@ fn main() -> std::io::Result<()> {
         println!("cargo:rerun-if-changed={JJ_OP_HEADS_PATH}");
     }
     println!("cargo:rerun-if-env-changed=NIX_JJ_GIT_HASH");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
 
-    if let Some(git_hash) = get_git_hash() {
+    let git_hash = get_git_hash();
+    if let Some(git_hash) = &git_hash {
         println!("cargo:rustc-env=JJ_VERSION={}-{}", version, git_hash);
     } else {
         println!("cargo:rustc-env=JJ_VERSION={}", version);
     }
+    println!(
+        "cargo:rustc-env=JJ_BUILD_GIT_HASH={}",
+        git_hash.as_deref().unwrap_or("")
+    );
+    println!(
+        "cargo:rustc-env=JJ_BUILD_TIMESTAMP={}",
+        get_build_timestamp()
+    );
+    println!(
+        "cargo:rustc-env=JJ_BUILD_FEATURES={}",
+        get_features().join(",")
+    );
 
     Ok(())
 }
@@ -82,3 +97,31 @@ fn get_git_hash() -> Option<String> {
 
     None
 }
+
+/// Returns the build time in seconds since the Unix epoch. `SOURCE_DATE_EPOCH`
+/// is respected for reproducible builds.
+fn get_build_timestamp() -> u64 {
+    if let Some(epoch) = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.parse().ok())
+    {
+        return epoch;
+    }
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Returns the sorted names of the enabled Cargo features, excluding the
+/// "default" feature.
+fn get_features() -> Vec<String> {
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| {
+            let name = key.strip_prefix("CARGO_FEATURE_")?;
+            Some(name.to_lowercase().replace('_', "-"))
+        })
+        .filter(|name| name != "default")
+        .collect();
+    features.sort();
+    features
+}
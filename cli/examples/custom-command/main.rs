@@ -40,11 +40,16 @@ fn run_custom_command(
         CustomCommand::Frobnicate(args) => {
             let mut workspace_command = command_helper.workspace_helper(ui)?;
             let commit = workspace_command.resolve_single_rev(&args.revision)?;
+            let description = command_helper
+                .settings()
+                .config()
+                .get_string("frobnicate.description")
+                .unwrap_or_else(|_| "Frobnicated!".to_owned());
             let mut tx = workspace_command.start_transaction();
             let new_commit = tx
                 .mut_repo()
                 .rewrite_commit(command_helper.settings(), &commit)
-                .set_description("Frobnicated!")
+                .set_description(description)
                 .write()?;
             tx.finish(ui, "Frobnicate")?;
             writeln!(
@@ -58,5 +63,28 @@ fn run_custom_command(
 }
 
 fn main() -> std::process::ExitCode {
-    CliRunner::init().add_subcommand(run_custom_command).run()
+    CliRunner::init()
+        .add_subcommand(run_custom_command)
+        .add_help_keyword(
+            "frobnication",
+            "How revisions are frobnicated",
+            "# Frobnication\n\n`jj frobnicate` replaces the description of a revision.\n",
+        )
+        .add_config_schema(
+            r#"{
+                "properties": {
+                    "frobnicate": {
+                        "type": "object",
+                        "properties": {
+                            "description": {
+                                "type": "string",
+                                "description": "Description set by `jj frobnicate`",
+                                "default": "Frobnicated!"
+                            }
+                        }
+                    }
+                }
+            }"#,
+        )
+        .run()
 }
@@ -24,8 +24,8 @@ use std::process::ExitCode;
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::SystemTime;
-use std::{fs, str};
+use std::time::{Duration, Instant, SystemTime};
+use std::{fs, iter, str};
 
 use clap::builder::{
     MapValueParser, NonEmptyStringValueParser, TypedValueParser, ValueParserFactory,
@@ -37,10 +37,12 @@ use itertools::Itertools;
 use jj_lib::backend::{ChangeId, CommitId, MergedTreeId, TreeValue};
 use jj_lib::commit::Commit;
 use jj_lib::fileset::FilesetExpression;
+use jj_lib::fsmonitor::{FsmonitorFactories, FsmonitorSettings};
 use jj_lib::git_backend::GitBackend;
 use jj_lib::gitignore::{GitIgnoreError, GitIgnoreFile};
 use jj_lib::hex_util::to_reverse_hex;
 use jj_lib::id_prefix::IdPrefixContext;
+use jj_lib::local_working_copy::LocalWorkingCopy;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::MergedTreeValue;
 use jj_lib::merged_tree::MergedTree;
@@ -65,7 +67,8 @@ use jj_lib::str_util::StringPattern;
 use jj_lib::transaction::Transaction;
 use jj_lib::view::View;
 use jj_lib::working_copy::{
-    CheckoutStats, LockedWorkingCopy, SnapshotOptions, WorkingCopy, WorkingCopyFactory,
+    CheckoutStats, LockedWorkingCopy, SkippedLargeFile, SnapshotOptions, WorkingCopy,
+    WorkingCopyFactory,
 };
 use jj_lib::workspace::{
     default_working_copy_factories, LockedWorkspace, WorkingCopyFactories, Workspace,
@@ -78,26 +81,28 @@ use tracing_chrome::ChromeLayerBuilder;
 use tracing_subscriber::prelude::*;
 
 use crate::command_error::{
-    cli_error, config_error_with_message, handle_command_result, internal_error,
-    internal_error_with_message, user_error, user_error_with_hint, user_error_with_message,
-    CommandError,
+    cli_error, config_error_with_message, format_file_size_excess, handle_command_result,
+    internal_error, internal_error_with_message, large_file_hint, user_error, user_error_with_hint,
+    user_error_with_message, CommandError,
 };
+use crate::commands::help::{self, HelpKeyword};
 use crate::commit_templater::{CommitTemplateLanguage, CommitTemplateLanguageExtension};
 use crate::config::{
-    new_config_path, AnnotatedValue, CommandNameAndArgs, ConfigNamePathBuf, ConfigSource,
-    LayeredConfigs,
+    merged_config_schema, new_config_path, AnnotatedValue, CommandNameAndArgs, ConfigNamePathBuf,
+    ConfigSource, LayeredConfigs,
 };
 use crate::diff_util::{self, DiffFormat, DiffFormatArgs, DiffRenderer};
 use crate::formatter::{FormatRecorder, Formatter, PlainTextFormatter};
+use crate::generic_templater::GenericTemplateLanguage;
 use crate::git_util::{
     is_colocated_git_workspace, print_failed_git_export, print_git_import_stats,
 };
-use crate::merge_tools::{DiffEditor, MergeEditor, MergeToolConfigError};
+use crate::merge_tools::{DiffEditor, ExternalMergeTool, MergeEditor, MergeToolConfigError};
 use crate::operation_templater::OperationTemplateLanguageExtension;
-use crate::revset_util::RevsetExpressionEvaluator;
+use crate::revset_util::{RevsetCache, RevsetExpressionEvaluator};
 use crate::template_builder::TemplateLanguage;
 use crate::template_parser::TemplateAliasesMap;
-use crate::templater::{PropertyPlaceholder, TemplateRenderer};
+use crate::templater::{PropertyPlaceholder, TemplatePropertyExt as _, TemplateRenderer};
 use crate::ui::{ColorChoice, Ui};
 use crate::{revset_util, template_builder, text_util};
 
@@ -199,9 +204,12 @@ pub struct CommandHelper {
     revset_extensions: Arc<RevsetExtensions>,
     commit_template_extensions: Vec<Arc<dyn CommitTemplateLanguageExtension>>,
     operation_template_extensions: Vec<Arc<dyn OperationTemplateLanguageExtension>>,
+    help_keywords: Vec<HelpKeyword>,
+    config_schema_fragments: Vec<serde_json::Value>,
     maybe_workspace_loader: Result<WorkspaceLoader, CommandError>,
     store_factories: StoreFactories,
     working_copy_factories: WorkingCopyFactories,
+    fsmonitor_factories: Arc<FsmonitorFactories>,
 }
 
 impl CommandHelper {
@@ -217,6 +225,15 @@ impl CommandHelper {
         &self.cwd
     }
 
+    pub(crate) fn help_keywords(&self) -> &[HelpKeyword] {
+        &self.help_keywords
+    }
+
+    /// JSON schema fragments of config options registered by extensions.
+    pub(crate) fn config_schema_fragments(&self) -> &[serde_json::Value] {
+        &self.config_schema_fragments
+    }
+
     pub fn string_args(&self) -> &Vec<String> {
         &self.string_args
     }
@@ -233,6 +250,15 @@ impl CommandHelper {
         &self.settings
     }
 
+    /// Loads the filesystem monitor settings. Monitors registered to the
+    /// `CliRunner` can be selected by the `core.fsmonitor` config.
+    pub fn fsmonitor_settings(&self) -> Result<FsmonitorSettings, config::ConfigError> {
+        FsmonitorSettings::from_config_with_factories(
+            self.settings.config(),
+            &self.fsmonitor_factories,
+        )
+    }
+
     pub fn resolved_config_values(
         &self,
         prefix: &ConfigNamePathBuf,
@@ -314,6 +340,25 @@ impl CommandHelper {
         Ok(factory)
     }
 
+    /// Returns the working-copy factory to use for a new workspace, as
+    /// selected by the `working-copy.backend` config.
+    pub fn get_new_working_copy_factory(&self) -> Result<&dyn WorkingCopyFactory, CommandError> {
+        let name = self
+            .settings
+            .config()
+            .get_string("working-copy.backend")
+            .optional()
+            .map_err(|err| config_error_with_message("Invalid `working-copy.backend`", err))?
+            .unwrap_or_else(|| LocalWorkingCopy::name().to_owned());
+        match self.working_copy_factories.get(&name) {
+            Some(factory) => Ok(factory.as_ref()),
+            None => Err(user_error(format!(
+                "Unknown working-copy backend '{name}'. Registered backends: {}",
+                self.working_copy_factories.keys().sorted().join(", ")
+            ))),
+        }
+    }
+
     #[instrument(skip_all)]
     pub fn load_workspace(&self) -> Result<Workspace, CommandError> {
         let loader = self.workspace_loader()?;
@@ -343,8 +388,12 @@ impl CommandHelper {
                     )?;
                     let base_repo = repo_loader.load_at(&op_heads[0])?;
                     // TODO: It may be helpful to print each operation we're merging here
-                    let mut tx =
-                        start_repo_transaction(&base_repo, &self.settings, &self.string_args);
+                    let mut tx = start_repo_transaction(
+                        &base_repo,
+                        &self.settings,
+                        &self.string_args,
+                        &self.global_args.op_tag,
+                    );
                     for other_op_head in op_heads.into_iter().skip(1) {
                         tx.merge_operation(other_op_head)?;
                         let num_rebased = tx.mut_repo().rebase_descendants(&self.settings)?;
@@ -386,6 +435,7 @@ impl CommandHelper {
 struct ReadonlyUserRepo {
     repo: Arc<ReadonlyRepo>,
     id_prefix_context: OnceCell<IdPrefixContext>,
+    revset_cache: RevsetCache,
 }
 
 impl ReadonlyUserRepo {
@@ -393,6 +443,7 @@ impl ReadonlyUserRepo {
         Self {
             repo,
             id_prefix_context: OnceCell::new(),
+            revset_cache: RevsetCache::default(),
         }
     }
 
@@ -489,6 +540,7 @@ pub struct WorkspaceCommandHelper {
     may_update_working_copy: bool,
     working_copy_shared_with_git: bool,
     path_converter: RepoPathUiConverter,
+    fsmonitor_factories: Arc<FsmonitorFactories>,
 }
 
 impl WorkspaceCommandHelper {
@@ -525,6 +577,7 @@ impl WorkspaceCommandHelper {
             may_update_working_copy,
             working_copy_shared_with_git,
             path_converter,
+            fsmonitor_factories: command.fsmonitor_factories.clone(),
         };
         // Parse commit_summary template (and short-prefixes revset) early to
         // report error before starting mutable operation.
@@ -624,7 +677,7 @@ impl WorkspaceCommandHelper {
             }
         } else {
             // Unlikely, but the HEAD ref got deleted by git?
-            self.finish_transaction(ui, tx, "import git head")?;
+            self.finish_transaction(ui, tx, "import git head", true)?;
         }
         Ok(())
     }
@@ -659,7 +712,7 @@ impl WorkspaceCommandHelper {
                 "Rebased {num_rebased} descendant commits off of commits rewritten from git"
             )?;
         }
-        self.finish_transaction(ui, tx, "import git refs")?;
+        self.finish_transaction(ui, tx, "import git refs", true)?;
         writeln!(
             ui.status(),
             "Done importing changes from the underlying Git repo."
@@ -814,7 +867,12 @@ impl WorkspaceCommandHelper {
         args: &DiffFormatArgs,
     ) -> Result<DiffRenderer<'_>, CommandError> {
         let formats = diff_util::diff_formats_for(&self.settings, args)?;
-        Ok(self.diff_renderer(formats))
+        let secret_matcher = self.secret_paths_matcher()?;
+        let tools_by_path = self.diff_tools_by_path(args)?;
+        Ok(self
+            .diff_renderer(formats)
+            .with_secret_paths(secret_matcher)
+            .with_tools_by_path(tools_by_path))
     }
 
     /// Loads textual diff renderer from the settings and log-like command
@@ -826,7 +884,63 @@ impl WorkspaceCommandHelper {
         patch: bool,
     ) -> Result<Option<DiffRenderer<'_>>, CommandError> {
         let formats = diff_util::diff_formats_for_log(&self.settings, args, patch)?;
-        Ok((!formats.is_empty()).then(|| self.diff_renderer(formats)))
+        if formats.is_empty() {
+            return Ok(None);
+        }
+        let secret_matcher = self.secret_paths_matcher()?;
+        let tools_by_path = self.diff_tools_by_path(args)?;
+        Ok(Some(
+            self.diff_renderer(formats)
+                .with_secret_paths(secret_matcher)
+                .with_tools_by_path(tools_by_path),
+        ))
+    }
+
+    /// Loads matcher of the `secrets.paths` filesets. The paths are relative to
+    /// the workspace root.
+    fn secret_paths_matcher(&self) -> Result<Box<dyn Matcher>, CommandError> {
+        let file_args: Vec<String> = self
+            .settings
+            .config()
+            .get("secrets.paths")
+            .optional()?
+            .unwrap_or_default();
+        let path_converter = self.workspace_root_path_converter();
+        let expressions: Vec<_> = file_args
+            .iter()
+            .map(|arg| fileset::parse_maybe_bare(arg, &path_converter))
+            .try_collect()
+            .map_err(|err| config_error_with_message("Invalid `secrets.paths`", err))?;
+        Ok(FilesetExpression::union_all(expressions).to_matcher())
+    }
+
+    /// Loads external diff tools of the `ui.diff-tool-by-path` filesets. The
+    /// paths are relative to the workspace root.
+    fn diff_tools_by_path(
+        &self,
+        args: &DiffFormatArgs,
+    ) -> Result<Vec<(FilesetExpression, ExternalMergeTool)>, CommandError> {
+        let path_converter = self.workspace_root_path_converter();
+        diff_util::diff_tools_by_path_for(&self.settings, args)?
+            .into_iter()
+            .map(|(text, tool)| {
+                let expression =
+                    fileset::parse_maybe_bare(&text, &path_converter).map_err(|err| {
+                        config_error_with_message("Invalid `ui.diff-tool-by-path`", err)
+                    })?;
+                Ok((expression, tool))
+            })
+            .try_collect()
+    }
+
+    /// Path converter for filesets in the config, which are relative to the
+    /// workspace root.
+    fn workspace_root_path_converter(&self) -> RepoPathUiConverter {
+        let workspace_root = self.workspace.workspace_root();
+        RepoPathUiConverter::Fs {
+            cwd: workspace_root.clone(),
+            base: workspace_root.clone(),
+        }
     }
 
     /// Loads diff editor from the settings.
@@ -959,6 +1073,12 @@ impl WorkspaceCommandHelper {
         Ok((self.attach_revset_evaluator(expression)?, modifier))
     }
 
+    /// Parses the default revset (`revsets.log`), which is used when no
+    /// revisions are specified to `jj log`.
+    pub fn parse_default_revset(&self) -> Result<RevsetExpressionEvaluator<'_>, CommandError> {
+        self.parse_revset(&RevisionArg::from(self.settings.default_revset()))
+    }
+
     /// Parses the given revset expressions and concatenates them all.
     pub fn parse_union_revsets(
         &self,
@@ -983,7 +1103,8 @@ impl WorkspaceCommandHelper {
             self.revset_extensions.clone(),
             self.id_prefix_context()?,
             expression,
-        ))
+        )
+        .with_cache(&self.user_repo.revset_cache))
     }
 
     pub(crate) fn revset_parse_context(&self) -> RevsetParseContext {
@@ -1060,9 +1181,11 @@ impl WorkspaceCommandHelper {
     pub fn commit_template_language(&self) -> Result<CommitTemplateLanguage<'_>, CommandError> {
         Ok(CommitTemplateLanguage::new(
             self.repo().as_ref(),
+            &self.settings,
             self.workspace_id(),
             self.revset_parse_context(),
             self.id_prefix_context()?,
+            self.path_converter(),
             &self.commit_template_extensions,
         ))
     }
@@ -1225,16 +1348,40 @@ See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-workin
             };
         self.user_repo = ReadonlyUserRepo::new(repo);
         let progress = crate::progress::snapshot_progress(ui);
-        let new_tree_id = locked_ws.locked_wc().snapshot(SnapshotOptions {
+        let (new_tree_id, stats) = locked_ws.locked_wc().snapshot(SnapshotOptions {
             base_ignores,
-            fsmonitor_settings: self.settings.fsmonitor_settings()?,
+            fsmonitor_settings: FsmonitorSettings::from_config_with_factories(
+                self.settings.config(),
+                &self.fsmonitor_factories,
+            )?,
             progress: progress.as_ref().map(|x| x as _),
             max_new_file_size: self.settings.max_new_file_size()?,
+            follow_symlinks: self.settings.follow_symlinks()?,
         })?;
         drop(progress);
+        if !stats.conflicted_paths.is_empty() {
+            writeln!(
+                ui.status(),
+                "Snapshotted modified files that still contain conflicts:"
+            )?;
+            for path in &stats.conflicted_paths {
+                writeln!(
+                    ui.status(),
+                    "  {}",
+                    self.path_converter.format_file_path(path)
+                )?;
+            }
+        }
+        if !stats.skipped_large_files.is_empty() {
+            print_skipped_large_files(ui, &self.path_converter, &stats.skipped_large_files)?;
+        }
         if new_tree_id != *wc_commit.tree_id() {
-            let mut tx =
-                start_repo_transaction(&self.user_repo.repo, &self.settings, &self.string_args);
+            let mut tx = start_repo_transaction(
+                &self.user_repo.repo,
+                &self.settings,
+                &self.string_args,
+                &self.global_args.op_tag,
+            );
             tx.set_is_snapshot(true);
             let mut_repo = tx.mut_repo();
             let commit = mut_repo
@@ -1307,15 +1454,85 @@ See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-workin
     }
 
     pub fn start_transaction(&mut self) -> WorkspaceCommandTransaction {
-        let tx = start_repo_transaction(self.repo(), &self.settings, &self.string_args);
+        let tx = start_repo_transaction(
+            self.repo(),
+            &self.settings,
+            &self.string_args,
+            &self.global_args.op_tag,
+        );
         WorkspaceCommandTransaction { helper: self, tx }
     }
 
+    /// Renders the operation description by the
+    /// `operation.description-template` setting if configured. Falls back
+    /// to the default `description` if the template is invalid, fails to
+    /// evaluate, or renders to an empty text.
+    fn format_operation_description(
+        &self,
+        ui: &Ui,
+        description: String,
+        num_rebased: usize,
+    ) -> Result<String, CommandError> {
+        let Some(template_text) = self
+            .settings
+            .config()
+            .get_string("operation.description-template")
+            .optional()?
+        else {
+            return Ok(description);
+        };
+        let language = operation_description_template_language(&self.settings);
+        let template = match template_builder::parse(
+            &language,
+            &template_text,
+            &self.template_aliases_map,
+            GenericTemplateLanguage::wrap_self,
+        ) {
+            Ok(template) => template,
+            Err(err) => {
+                writeln!(
+                    ui.warning_default(),
+                    "Failed to parse `operation.description-template`: {}",
+                    err.kind()
+                )?;
+                writeln!(
+                    ui.hint_default(),
+                    "Using the default operation description."
+                )?;
+                return Ok(description);
+            }
+        };
+        let context = OperationDescriptionContext {
+            description,
+            args: format_command_args(&self.string_args),
+            num_rebased,
+        };
+        let mut output = Vec::new();
+        let warning = match template.try_format(&context, &mut PlainTextFormatter::new(&mut output))
+        {
+            Ok(()) => {
+                let text = String::from_utf8_lossy(&output);
+                if !text.trim().is_empty() {
+                    return Ok(text.into_owned());
+                }
+                "`operation.description-template` rendered an empty description".to_owned()
+            }
+            Err(err) => format!("Failed to render `operation.description-template`: {err}"),
+        };
+        writeln!(ui.warning_default(), "{warning}")?;
+        writeln!(
+            ui.hint_default(),
+            "Using the default operation description."
+        )?;
+        Ok(context.description)
+    }
+
     fn finish_transaction(
         &mut self,
         ui: &mut Ui,
         mut tx: Transaction,
         description: impl Into<String>,
+        export_git_refs: bool,
     ) -> Result<(), CommandError> {
         if !tx.mut_repo().has_changes() {
             writeln!(ui.status(), "Nothing changed.")?;
@@ -1365,10 +1582,13 @@ See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-workin
             if let Some(wc_commit) = &maybe_new_wc_commit {
                 git::reset_head(tx.mut_repo(), &git_repo, wc_commit)?;
             }
-            let failed_branches = git::export_refs(tx.mut_repo())?;
-            print_failed_git_export(ui, &failed_branches)?;
+            if export_git_refs {
+                let failed_branches = git::export_refs(tx.mut_repo())?;
+                print_failed_git_export(ui, &failed_branches)?;
+            }
         }
 
+        let description = self.format_operation_description(ui, description.into(), num_rebased)?;
         self.user_repo = ReadonlyUserRepo::new(tx.commit(description));
         self.report_repo_changes(ui, &old_repo)?;
 
@@ -1646,9 +1866,11 @@ impl WorkspaceCommandTransaction<'_> {
         let id_prefix_context = IdPrefixContext::new(self.helper.revset_extensions.clone());
         let language = CommitTemplateLanguage::new(
             self.tx.repo(),
+            &self.helper.settings,
             self.helper.workspace_id(),
             self.helper.revset_parse_context(),
             &id_prefix_context,
+            self.helper.path_converter(),
             &self.helper.commit_template_extensions,
         );
         let template = self
@@ -1663,7 +1885,19 @@ impl WorkspaceCommandTransaction<'_> {
     }
 
     pub fn finish(self, ui: &mut Ui, description: impl Into<String>) -> Result<(), CommandError> {
-        self.helper.finish_transaction(ui, self.tx, description)
+        self.helper
+            .finish_transaction(ui, self.tx, description, true)
+    }
+
+    /// Like [`Self::finish`], but doesn't export refs to the colocated Git
+    /// repo. Use this if the caller has exported the refs selectively.
+    pub fn finish_without_git_export(
+        self,
+        ui: &mut Ui,
+        description: impl Into<String>,
+    ) -> Result<(), CommandError> {
+        self.helper
+            .finish_transaction(ui, self.tx, description, false)
     }
 
     /// Returns the wrapped [`Transaction`] for circumstances where
@@ -1742,8 +1976,18 @@ pub fn start_repo_transaction(
     repo: &Arc<ReadonlyRepo>,
     settings: &UserSettings,
     string_args: &[String],
+    op_tags: &[(String, String)],
 ) -> Transaction {
     let mut tx = repo.start_transaction(settings);
+    for (key, value) in op_tags {
+        tx.set_tag(key.clone(), value.clone());
+    }
+    tx.set_tag("args".to_string(), format_command_args(string_args));
+    tx
+}
+
+/// Formats the command-line arguments as recorded in the operation metadata.
+fn format_command_args(string_args: &[String]) -> String {
     // TODO: Either do better shell-escaping here or store the values in some list
     // type (which we currently don't have).
     let shell_escape = |arg: &String| {
@@ -1768,8 +2012,36 @@ pub fn start_repo_transaction(
     };
     let mut quoted_strings = vec!["jj".to_string()];
     quoted_strings.extend(string_args.iter().skip(1).map(shell_escape));
-    tx.set_tag("args".to_string(), quoted_strings.join(" "));
-    tx
+    quoted_strings.join(" ")
+}
+
+/// Values available to the `operation.description-template` setting.
+#[derive(Clone, Debug)]
+struct OperationDescriptionContext {
+    description: String,
+    args: String,
+    num_rebased: usize,
+}
+
+fn operation_description_template_language(
+    settings: &UserSettings,
+) -> GenericTemplateLanguage<'static, OperationDescriptionContext> {
+    type L = GenericTemplateLanguage<'static, OperationDescriptionContext>;
+    let mut language = L::new(settings);
+    language.add_keyword("description", |self_property| {
+        let out_property = self_property.map(|context| context.description);
+        Ok(L::wrap_string(out_property))
+    });
+    language.add_keyword("args", |self_property| {
+        let out_property = self_property.map(|context| context.args);
+        Ok(L::wrap_string(out_property))
+    });
+    language.add_keyword("num_rebased", |self_property| {
+        let out_property =
+            self_property.and_then(|context| Ok(i64::try_from(context.num_rebased)?));
+        Ok(L::wrap_integer(out_property))
+    });
+    language
 }
 
 /// Whether the working copy is stale or not.
@@ -1828,6 +2100,34 @@ pub fn check_stale_working_copy(
     }
 }
 
+#[instrument(skip_all)]
+pub(crate) fn print_skipped_large_files(
+    ui: &Ui,
+    path_converter: &RepoPathUiConverter,
+    skipped_files: &[SkippedLargeFile],
+) -> io::Result<()> {
+    writeln!(
+        ui.warning_default(),
+        "Refused to snapshot some files because they are too large:"
+    )?;
+    for file in skipped_files {
+        let SkippedLargeFile {
+            path,
+            size,
+            max_size,
+        } = file;
+        writeln!(
+            ui.warning_no_heading(),
+            "  {}: {}",
+            path_converter.format_file_path(path),
+            format_file_size_excess(*size, *max_size)
+        )?;
+    }
+    let max_size = skipped_files.iter().map(|file| file.size.0).max().unwrap();
+    writeln!(ui.hint_default(), "{}", large_file_hint(max_size))?;
+    Ok(())
+}
+
 #[instrument(skip_all)]
 pub fn print_conflicted_paths(
     conflicts: &[(RepoPathBuf, MergedTreeValue)],
@@ -2128,6 +2428,11 @@ pub fn get_new_config_file_path(
             new_config_path()?.ok_or_else(|| user_error("No repo config path found to edit"))?
         }
         ConfigSource::Repo => command.workspace_loader()?.repo_path().join("config.toml"),
+        ConfigSource::Workspace => command
+            .workspace_loader()?
+            .workspace_root()
+            .join(".jj")
+            .join("config.toml"),
         _ => {
             return Err(user_error(format!(
                 "Can't get path for config source {config_source:?}"
@@ -2318,6 +2623,8 @@ impl fmt::Display for RemoteBranchNamePattern {
 #[allow(rustdoc::bare_urls)]
 #[derive(clap::Parser, Clone, Debug)]
 #[command(name = "jj")]
+#[command(disable_help_subcommand = true)]
+#[command(after_long_help = help::show_keyword_hint_after_help())]
 pub struct Args {
     #[command(flatten)]
     pub global_args: GlobalArgs,
@@ -2379,6 +2686,13 @@ pub struct GlobalArgs {
     /// Enable debug logging
     #[arg(long, global = true)]
     pub debug: bool,
+    /// Attach a custom tag to the operation (can be repeated)
+    ///
+    /// The tag will be recorded in the metadata of the operation created by
+    /// the command, and can be displayed by `jj op log`. The key may contain
+    /// ASCII alphanumeric characters, `-`, `_`, and `.`.
+    #[arg(long, value_name = "KEY=VALUE", global = true, value_parser = parse_op_tag_arg)]
+    pub op_tag: Vec<(String, String)>,
 
     #[command(flatten)]
     pub early_args: EarlyArgs,
@@ -2389,6 +2703,11 @@ pub struct EarlyArgs {
     /// When to colorize output (always, never, debug, auto)
     #[arg(long, value_name = "WHEN", global = true)]
     pub color: Option<ColorChoice>,
+    /// Disable colorized output (same as `--color=never`)
+    #[arg(long, global = true, action = ArgAction::SetTrue, conflicts_with = "color")]
+    // Parsing with ignore_errors will crash if this is bool, so use
+    // Option<bool>.
+    pub no_color: Option<bool>,
     /// Silence non-primary command output
     ///
     /// For example, `jj file list ` will still list files, but it won't tell
@@ -2411,6 +2730,62 @@ pub struct EarlyArgs {
     pub config_toml: Vec<String>,
 }
 
+/// Maximum total size in bytes of the custom operation tags.
+const MAX_OP_TAGS_SIZE: usize = 4096;
+
+/// Parses duration argument such as `30d` or `12h`.
+pub fn parse_duration_arg(s: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid duration {s:?}. Expected e.g. \"30d\" or \"12h\"");
+    let (digits, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+    let count: u64 = digits.parse().map_err(|_| invalid())?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    count
+        .checked_mul(unit_secs)
+        .map(Duration::from_secs)
+        .ok_or_else(invalid)
+}
+
+/// Operation tags recorded by Jujutsu itself, which can't be overridden.
+const RESERVED_OP_TAG_KEYS: &[&str] = &["args"];
+
+fn parse_op_tag_arg(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| "expected KEY=VALUE".to_owned())?;
+    if key.is_empty()
+        || !key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+    {
+        return Err(format!("invalid tag key: {key:?}"));
+    }
+    if RESERVED_OP_TAG_KEYS.contains(&key) {
+        return Err(format!("tag key is reserved: {key:?}"));
+    }
+    Ok((key.to_owned(), value.to_owned()))
+}
+
+fn validate_op_tags(op_tags: &[(String, String)]) -> Result<(), CommandError> {
+    let total_size: usize = op_tags
+        .iter()
+        .map(|(key, value)| key.len() + value.len())
+        .sum();
+    if total_size > MAX_OP_TAGS_SIZE {
+        return Err(user_error(format!(
+            "Operation tags are too large ({total_size} bytes; the limit is {MAX_OP_TAGS_SIZE} \
+             bytes)"
+        )));
+    }
+    Ok(())
+}
+
 /// Wrapper around revset expression argument.
 ///
 /// An empty string is rejected early by the CLI value parser, but it's still
@@ -2599,6 +2974,9 @@ fn handle_early_args(
     if let Some(choice) = args.color {
         args.config_toml.push(format!(r#"ui.color="{choice}""#));
     }
+    if args.no_color.unwrap_or_default() {
+        args.config_toml.push(r#"ui.color="never""#.to_owned());
+    }
     if args.quiet.unwrap_or_default() {
         args.config_toml.push(r#"ui.quiet=true"#.to_string());
     }
@@ -2646,6 +3024,7 @@ pub fn parse_args(
         .try_get_matches_from(string_args)?;
 
     let args: Args = Args::from_arg_matches(&matches).unwrap();
+    validate_op_tags(&args.global_args.op_tag)?;
     if args.global_args.debug {
         // TODO: set up debug logging as early as possible
         tracing_subscription.enable_debug_logging()?;
@@ -2670,16 +3049,24 @@ pub struct CliRunner {
     extra_configs: Vec<config::Config>,
     store_factories: StoreFactories,
     working_copy_factories: WorkingCopyFactories,
+    fsmonitor_factories: FsmonitorFactories,
     revset_extensions: RevsetExtensions,
     commit_template_extensions: Vec<Arc<dyn CommitTemplateLanguageExtension>>,
     operation_template_extensions: Vec<Arc<dyn OperationTemplateLanguageExtension>>,
+    help_keywords: Vec<HelpKeyword>,
+    config_schema_fragments: Vec<serde_json::Value>,
     dispatch_fn: CliDispatchFn,
     start_hook_fns: Vec<CliDispatchFn>,
+    dispatch_hook_fns: Vec<DispatchHookFn>,
     process_global_args_fns: Vec<ProcessGlobalArgsFn>,
 }
 
 type CliDispatchFn = Box<dyn FnOnce(&mut Ui, &CommandHelper) -> Result<(), CommandError>>;
 
+/// Function called after the command is dispatched with the subcommand name
+/// (e.g. `"branch list"`), the elapsed time, and the result.
+pub type DispatchHookFn = Box<dyn FnOnce(&str, Duration, &Result<(), CommandError>)>;
+
 type ProcessGlobalArgsFn = Box<dyn FnOnce(&mut Ui, &ArgMatches) -> Result<(), CommandError>>;
 
 impl CliRunner {
@@ -2694,11 +3081,15 @@ impl CliRunner {
             extra_configs: vec![],
             store_factories: StoreFactories::default(),
             working_copy_factories: default_working_copy_factories(),
+            fsmonitor_factories: FsmonitorFactories::new(),
             revset_extensions: Default::default(),
             commit_template_extensions: vec![],
             operation_template_extensions: vec![],
+            help_keywords: help::builtin_keywords(),
+            config_schema_fragments: vec![],
             dispatch_fn: Box::new(crate::commands::run_command),
             start_hook_fns: vec![],
+            dispatch_hook_fns: vec![],
             process_global_args_fns: vec![],
         }
     }
@@ -2730,6 +3121,13 @@ impl CliRunner {
         self
     }
 
+    /// Adds filesystem monitors which can be selected by name by the
+    /// `core.fsmonitor` config.
+    pub fn add_fsmonitor_factories(mut self, fsmonitor_factories: FsmonitorFactories) -> Self {
+        merge_factories_map(&mut self.fsmonitor_factories, fsmonitor_factories);
+        self
+    }
+
     pub fn add_symbol_resolver_extension(
         mut self,
         symbol_resolver: Box<dyn SymbolResolverExtension>,
@@ -2765,11 +3163,59 @@ impl CliRunner {
         self
     }
 
+    /// Registers a help topic to be shown by `jj help -k <name>`. The
+    /// `content` is a Markdown text.
+    pub fn add_help_keyword(
+        mut self,
+        name: &'static str,
+        description: &'static str,
+        content: &'static str,
+    ) -> Self {
+        if self
+            .help_keywords
+            .iter()
+            .any(|keyword| keyword.name == name)
+        {
+            panic!("Conflict registering help keyword '{name}'");
+        }
+        self.help_keywords.push(HelpKeyword {
+            name,
+            description,
+            content,
+        });
+        self.app = help::set_keyword_values(self.app, &self.help_keywords);
+        self
+    }
+
+    /// Registers a JSON schema fragment describing the config options added by
+    /// an extension. The `properties` of the fragment are merged into the
+    /// schema printed by `jj util config-schema`.
+    pub fn add_config_schema(mut self, fragment: &str) -> Self {
+        let fragment: serde_json::Value = serde_json::from_str(fragment)
+            .unwrap_or_else(|err| panic!("Invalid config schema fragment: {err}"));
+        self.config_schema_fragments.push(fragment);
+        if let Err(name) = merged_config_schema(&self.config_schema_fragments) {
+            panic!("Conflict registering config schema for '{name}'");
+        }
+        self
+    }
+
     pub fn add_start_hook(mut self, start_hook_fn: CliDispatchFn) -> Self {
         self.start_hook_fns.push(start_hook_fn);
         self
     }
 
+    /// Registers a function to observe the command execution, e.g. to emit
+    /// metrics. The hook is called with the subcommand name, the time spent in
+    /// the command, and the command result, which can't be altered by the hook.
+    ///
+    /// The hook isn't called if the command-line arguments can't be parsed or
+    /// if a start hook fails.
+    pub fn add_dispatch_hook(mut self, dispatch_hook_fn: DispatchHookFn) -> Self {
+        self.dispatch_hook_fns.push(dispatch_hook_fn);
+        self
+    }
+
     /// Registers new subcommands in addition to the default ones.
     pub fn add_subcommand<C, F>(mut self, custom_dispatch_fn: F) -> Self
     where
@@ -2828,14 +3274,18 @@ impl CliRunner {
             .map_err(|err| map_workspace_load_error(err, None));
         layered_configs.read_user_config()?;
         let mut repo_config_path = None;
+        let mut workspace_config_path = None;
         if let Ok(loader) = &maybe_cwd_workspace_loader {
             layered_configs.read_repo_config(loader.repo_path())?;
+            layered_configs.read_workspace_config(loader.workspace_root())?;
             repo_config_path = Some(layered_configs.repo_config_path(loader.repo_path()));
+            workspace_config_path =
+                Some(layered_configs.workspace_config_path(loader.workspace_root()));
         }
         let config = layered_configs.merge();
         ui.reset(&config).map_err(|e| {
             let user_config_path = layered_configs.user_config_path().unwrap_or(None);
-            let paths = [repo_config_path, user_config_path]
+            let paths = [workspace_config_path, repo_config_path, user_config_path]
                 .into_iter()
                 .flatten()
                 .map(|path| format!("- {}", path.display()))
@@ -2861,6 +3311,7 @@ impl CliRunner {
             let loader = WorkspaceLoader::init(&cwd.join(path))
                 .map_err(|err| map_workspace_load_error(err, Some(path)))?;
             layered_configs.read_repo_config(loader.repo_path())?;
+            layered_configs.read_workspace_config(loader.workspace_root())?;
             Ok(loader)
         } else {
             maybe_cwd_workspace_loader
@@ -2894,14 +3345,31 @@ impl CliRunner {
             revset_extensions: self.revset_extensions.into(),
             commit_template_extensions: self.commit_template_extensions,
             operation_template_extensions: self.operation_template_extensions,
+            help_keywords: self.help_keywords,
+            config_schema_fragments: self.config_schema_fragments,
             maybe_workspace_loader,
             store_factories: self.store_factories,
             working_copy_factories: self.working_copy_factories,
+            fsmonitor_factories: self.fsmonitor_factories.into(),
         };
         for start_hook_fn in self.start_hook_fns {
             start_hook_fn(ui, &command_helper)?;
         }
-        (self.dispatch_fn)(ui, &command_helper)
+        let start_time = Instant::now();
+        let result = (self.dispatch_fn)(ui, &command_helper);
+        if !self.dispatch_hook_fns.is_empty() {
+            let elapsed = start_time.elapsed();
+            let subcommand_name =
+                iter::successors(command_helper.matches().subcommand(), |(_, matches)| {
+                    matches.subcommand()
+                })
+                .map(|(name, _)| name)
+                .join(" ");
+            for dispatch_hook_fn in self.dispatch_hook_fns {
+                dispatch_hook_fn(&subcommand_name, elapsed, &result);
+            }
+        }
+        result
     }
 
     #[must_use]
@@ -30,6 +30,7 @@ use jj_lib::repo_path::{RepoPathBuf, UiPathParseError};
 use jj_lib::revset::{
     RevsetEvaluationError, RevsetParseError, RevsetParseErrorKind, RevsetResolutionError,
 };
+use jj_lib::settings::HumanByteSize;
 use jj_lib::signing::SignInitError;
 use jj_lib::str_util::StringPatternParseError;
 use jj_lib::working_copy::{ResetError, SnapshotError, WorkingCopyStateError};
@@ -295,47 +296,54 @@ impl From<OpsetEvaluationError> for CommandError {
 impl From<SnapshotError> for CommandError {
     fn from(err: SnapshotError) -> Self {
         match err {
+            // Kept for working copy implementations that still report it.
+            #[allow(deprecated)]
             SnapshotError::NewFileTooLarge {
                 path,
                 size,
                 max_size,
-            } => {
-                // if the size difference is < 1KiB, then show exact bytes.
-                // otherwise, show in human-readable form; this avoids weird cases
-                // where a file is 400 bytes too large but the error says something
-                // like '1.0MiB, maximum size allowed is ~1.0MiB'
-                let size_diff = size.0 - max_size.0;
-                let err_str = if size_diff <= 1024 {
-                    format!(
-                        "it is {} bytes too large; the maximum size allowed is {} bytes ({}).",
-                        size_diff, max_size.0, max_size,
-                    )
-                } else {
-                    format!("it is {}; the maximum size allowed is ~{}.", size, max_size,)
-                };
-
-                user_error(format!(
-                    "Failed to snapshot the working copy\nThe file '{}' is too large to be \
-                     snapshotted: {}",
-                    path.display(),
-                    err_str,
-                ))
-                .hinted(format!(
-                    "This is to prevent large files from being added on accident. You can fix \
-                     this error by:
-  - Adding the file to `.gitignore`
-  - Run `jj config set --repo snapshot.max-new-file-size {}`
-    This will increase the maximum file size allowed for new files, in this repository only.
-  - Run `jj --config-toml 'snapshot.max-new-file-size={}' st`
-    This will increase the maximum file size allowed for new files, for this command only.",
-                    size.0, size.0
-                ))
-            }
+            } => user_error(format!(
+                "Failed to snapshot the working copy\nThe file '{}' is too large to be \
+                 snapshotted: {}.",
+                path.display(),
+                format_file_size_excess(size, max_size),
+            ))
+            .hinted(large_file_hint(size.0)),
             err => internal_error_with_message("Failed to snapshot the working copy", err),
         }
     }
 }
 
+/// Describes how much `size` exceeds `max_size`.
+pub(crate) fn format_file_size_excess(size: HumanByteSize, max_size: HumanByteSize) -> String {
+    // if the size difference is < 1KiB, then show exact bytes.
+    // otherwise, show in human-readable form; this avoids weird cases
+    // where a file is 400 bytes too large but the message says something
+    // like '1.0MiB, maximum size allowed is ~1.0MiB'
+    let size_diff = size.0 - max_size.0;
+    if size_diff <= 1024 {
+        format!(
+            "it is {} bytes too large; the maximum size allowed is {} bytes ({})",
+            size_diff, max_size.0, max_size,
+        )
+    } else {
+        format!("it is {size}; the maximum size allowed is ~{max_size}")
+    }
+}
+
+/// Hint for new files larger than `snapshot.max-new-file-size`. `max_size` is
+/// the limit that would allow them to be snapshotted.
+pub(crate) fn large_file_hint(max_size: u64) -> String {
+    format!(
+        "This is to prevent large files from being added on accident. You can fix this by:
+  - Adding the file to `.gitignore`
+  - Run `jj config set --repo snapshot.max-new-file-size {max_size}`
+    This will increase the maximum file size allowed for new files, in this repository only.
+  - Run `jj --config-toml 'snapshot.max-new-file-size={max_size}' st`
+    This will increase the maximum file size allowed for new files, for this command only."
+    )
+}
+
 impl From<OpStoreError> for CommandError {
     fn from(err: OpStoreError) -> Self {
         internal_error_with_message("Failed to load an operation", err)
@@ -693,7 +701,7 @@ fn try_handle_command_result(
     }
 }
 
-fn print_error(
+pub(crate) fn print_error(
     ui: &Ui,
     heading: &str,
     err: &dyn error::Error,
@@ -16,7 +16,7 @@ use std::fmt::Debug;
 use std::io;
 use std::io::Write as _;
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use clap::Subcommand;
 use criterion::measurement::Measurement;
@@ -41,6 +41,8 @@ pub enum BenchCommand {
     ResolvePrefix(BenchResolvePrefixArgs),
     #[command(name = "revset")]
     Revset(BenchRevsetArgs),
+    #[command(name = "revsetcompare")]
+    RevsetCompare(BenchRevsetCompareArgs),
 }
 
 /// Find the common ancestor(s) of a set of commits
@@ -74,6 +76,19 @@ pub struct BenchRevsetArgs {
     criterion: CriterionArgs,
 }
 
+/// Compare the time taken to walk the revisions in two revsets
+#[derive(clap::Args, Clone, Debug)]
+pub struct BenchRevsetCompareArgs {
+    revision1: RevisionArg,
+    revision2: RevisionArg,
+    /// Number of runs to discard before measuring
+    #[arg(long, default_value_t = 3)]
+    warmup: u32,
+    /// Number of measured runs, which must be at least 1
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+}
+
 /// Resolve a commit ID prefix
 #[derive(clap::Args, Clone, Debug)]
 pub struct BenchResolvePrefixArgs {
@@ -193,10 +208,70 @@ pub(crate) fn cmd_bench(
             group.finish();
             criterion.final_summary();
         }
+        BenchCommand::RevsetCompare(args) => {
+            let workspace_command = command.workspace_helper(ui)?;
+            let mut medians = vec![];
+            for revset in [&args.revision1, &args.revision2] {
+                let expression =
+                    revset::optimize(workspace_command.parse_revset(revset)?.expression().clone());
+                let mut durations = vec![];
+                let mut num_commits = 0;
+                for i in 0..args.warmup + args.iterations {
+                    // Reload repo to clear caches as in `jj bench revset`.
+                    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+                    workspace_command.repo().readonly_index();
+                    let before = Instant::now();
+                    num_commits = walk_revset(&workspace_command, expression.clone());
+                    let after = Instant::now();
+                    if i >= args.warmup {
+                        durations.push(after.duration_since(before));
+                    }
+                }
+                durations.sort();
+                let median = durations[durations.len() / 2];
+                writeln!(
+                    ui.stdout(),
+                    "{revset}: median {:?} (min {:?}, max {:?}), {num_commits} commits",
+                    median,
+                    durations[0],
+                    durations[durations.len() - 1],
+                )?;
+                medians.push(median);
+            }
+            writeln!(
+                ui.stdout(),
+                "Ratio ({} / {}): {:.2}",
+                args.revision2,
+                args.revision1,
+                ratio(medians[1], medians[0]),
+            )?;
+        }
     }
     Ok(())
 }
 
+/// Evaluates the expression and returns the number of commits. Short-prefixes
+/// aren't parsed nor evaluated.
+fn walk_revset(
+    workspace_command: &WorkspaceCommandHelper,
+    expression: Rc<RevsetExpression>,
+) -> usize {
+    let repo = workspace_command.repo().as_ref();
+    let symbol_resolver =
+        DefaultSymbolResolver::new(repo, &([] as [Box<dyn SymbolResolverExtension>; 0]));
+    let resolved = expression
+        .resolve_user_expression(repo, &symbol_resolver)
+        .unwrap();
+    let revset = resolved.evaluate(repo).unwrap();
+    revset.iter().count()
+}
+
+/// Returns how many times `a` is slower than `b`.
+fn ratio(a: Duration, b: Duration) -> f64 {
+    // Avoid dividing by zero if the timer resolution is too coarse.
+    a.as_secs_f64() / b.as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
 fn bench_revset<M: Measurement>(
     ui: &mut Ui,
     command: &CommandHelper,
@@ -207,17 +282,7 @@ fn bench_revset<M: Measurement>(
     writeln!(ui.status(), "----------Testing revset: {revset}----------")?;
     let expression = revset::optimize(workspace_command.parse_revset(revset)?.expression().clone());
     // Time both evaluation and iteration.
-    let routine = |workspace_command: &WorkspaceCommandHelper, expression: Rc<RevsetExpression>| {
-        // Evaluate the expression without parsing/evaluating short-prefixes.
-        let repo = workspace_command.repo().as_ref();
-        let symbol_resolver =
-            DefaultSymbolResolver::new(repo, &([] as [Box<dyn SymbolResolverExtension>; 0]));
-        let resolved = expression
-            .resolve_user_expression(repo, &symbol_resolver)
-            .unwrap();
-        let revset = resolved.evaluate(repo).unwrap();
-        revset.iter().count()
-    };
+    let routine = walk_revset;
     let before = Instant::now();
     let result = routine(workspace_command, expression.clone());
     let after = Instant::now();
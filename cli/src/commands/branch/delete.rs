@@ -13,12 +13,13 @@
 // limitations under the License.
 
 use itertools::Itertools as _;
-use jj_lib::op_store::RefTarget;
+use jj_lib::git;
+use jj_lib::op_store::{RefTarget, RemoteRefState};
 use jj_lib::str_util::StringPattern;
 
 use super::find_local_branches;
 use crate::cli_util::CommandHelper;
-use crate::command_error::CommandError;
+use crate::command_error::{user_error, CommandError};
 use crate::ui::Ui;
 
 /// Delete an existing branch and propagate the deletion to remotes on the
@@ -32,6 +33,14 @@ pub struct BranchDeleteArgs {
     /// https://github.com/martinvonz/jj/blob/main/docs/revsets.md#string-patterns.
     #[arg(required = true, value_parser = StringPattern::parse)]
     names: Vec<StringPattern>,
+
+    /// Also delete the branch from the given remote on the next push
+    ///
+    /// The corresponding remote branch will be tracked so that `jj git push`
+    /// deletes it. If the branch doesn't exist on the remote, it will only be
+    /// deleted locally.
+    #[arg(long = "remote", value_name = "REMOTE")]
+    remotes: Vec<String>,
 }
 
 pub fn cmd_branch_delete(
@@ -42,8 +51,32 @@ pub fn cmd_branch_delete(
     let mut workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo().clone();
     let matched_branches = find_local_branches(repo.view(), &args.names)?;
+    if args
+        .remotes
+        .iter()
+        .any(|remote| remote == git::REMOTE_NAME_FOR_LOCAL_GIT_REPO)
+    {
+        return Err(user_error(format!(
+            "Cannot delete branches from remote: {}",
+            git::REMOTE_NAME_FOR_LOCAL_GIT_REPO
+        )));
+    }
     let mut tx = workspace_command.start_transaction();
     for (name, _) in &matched_branches {
+        for remote in &args.remotes {
+            let mut remote_ref = tx.repo().get_remote_branch(name, remote);
+            if remote_ref.is_absent() {
+                writeln!(
+                    ui.status(),
+                    "Branch {name} doesn't exist on remote {remote}, deleting it locally only."
+                )?;
+            } else if !remote_ref.is_tracking() {
+                // Mark the remote branch as tracked without merging its target
+                // into the local branch, so the deletion will be pushed.
+                remote_ref.state = RemoteRefState::Tracking;
+                tx.mut_repo().set_remote_branch(name, remote, remote_ref);
+            }
+        }
         tx.mut_repo()
             .set_local_branch_target(name, RefTarget::absent());
     }
@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Reverse;
 use std::collections::HashSet;
 
+use itertools::Itertools as _;
+use jj_lib::backend::{CommitId, Signature};
+use jj_lib::commit::Commit;
 use jj_lib::git;
+use jj_lib::repo::Repo;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::str_util::StringPattern;
 
@@ -64,6 +69,13 @@ pub struct BranchListArgs {
     #[arg(long, short)]
     revisions: Vec<RevisionArg>,
 
+    /// Show branches whose targets contain the given revisions
+    ///
+    /// A branch is listed if its local target or any of its remote targets is
+    /// a descendant of (or the same as) any of the given revisions.
+    #[arg(long, value_name = "REVISIONS")]
+    contains: Vec<RevisionArg>,
+
     /// Render each branch using the given template
     ///
     /// All 0-argument methods of the `RefName` type are available as keywords.
@@ -71,6 +83,24 @@ pub struct BranchListArgs {
     /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
     #[arg(long, short = 'T')]
     template: Option<String>,
+
+    /// Sort branches by the given key
+    ///
+    /// When sorting by date, the most recently updated branches are listed
+    /// first. A branch pointing to multiple commits (e.g. a conflicted branch)
+    /// is sorted by the newest of them.
+    #[arg(long, value_enum, default_value_t = SortKey::Name)]
+    sort: SortKey,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+enum SortKey {
+    /// Sort by branch name
+    Name,
+    /// Sort by committer date of the target commits
+    CommitterDate,
+    /// Sort by author date of the target commits
+    AuthorDate,
 }
 
 pub fn cmd_branch_list(
@@ -111,6 +141,25 @@ pub fn cmd_branch_list(
         None
     };
 
+    let contains_targets = if !args.contains.is_empty() {
+        let expression = workspace_command.parse_union_revsets(&args.contains)?;
+        let targets = RevsetExpression::branches(StringPattern::everything()).union(
+            &RevsetExpression::remote_branches(
+                StringPattern::everything(),
+                StringPattern::everything(),
+            ),
+        );
+        // Intersects with the set of branch targets to minimize the lookup space.
+        let expression = expression.expression().descendants().intersection(&targets);
+        let ids: HashSet<_> = workspace_command
+            .attach_revset_evaluator(expression)?
+            .evaluate_to_commit_ids()?
+            .collect();
+        Some(ids)
+    } else {
+        None
+    };
+
     let template = {
         let language = workspace_command.commit_template_language()?;
         let text = match &args.template {
@@ -127,12 +176,56 @@ pub fn cmd_branch_list(
 
     let mut found_deleted_local_branch = false;
     let mut found_deleted_tracking_local_branch = false;
-    let branches_to_list = view.branches().filter(|(name, target)| {
-        branch_names_to_list
-            .as_ref()
-            .map_or(true, |branch_names| branch_names.contains(name))
-            && (!args.conflicted || target.local_target.has_conflict())
-    });
+    let mut branches_to_list = view
+        .branches()
+        .filter(|(name, target)| {
+            branch_names_to_list
+                .as_ref()
+                .map_or(true, |branch_names| branch_names.contains(name))
+                && (!args.conflicted || target.local_target.has_conflict())
+                && contains_targets.as_ref().map_or(true, |ids| {
+                    let local_ids = target.local_target.added_ids();
+                    let remote_ids = target
+                        .remote_refs
+                        .iter()
+                        .flat_map(|(_, remote_ref)| remote_ref.target.added_ids());
+                    local_ids.chain(remote_ids).any(|id| ids.contains(id))
+                })
+        })
+        .collect_vec();
+    // Branches are already sorted by name, which is preserved for ties.
+    let signature_to_sort_by: Option<fn(&Commit) -> &Signature> = match args.sort {
+        SortKey::Name => None,
+        SortKey::CommitterDate => Some(Commit::committer),
+        SortKey::AuthorDate => Some(Commit::author),
+    };
+    if let Some(signature_of) = signature_to_sort_by {
+        let store = repo.store();
+        let timestamp_millis = |id: &CommitId| -> Result<i64, CommandError> {
+            let commit = store.get_commit(id)?;
+            Ok(signature_of(&commit).timestamp.timestamp.0)
+        };
+        let mut keyed_branches = Vec::with_capacity(branches_to_list.len());
+        for (name, branch_target) in branches_to_list {
+            let remote_ids = branch_target
+                .remote_refs
+                .iter()
+                .flat_map(|(_, remote_ref)| remote_ref.target.added_ids());
+            let newest = branch_target
+                .local_target
+                .added_ids()
+                .chain(remote_ids)
+                .map(timestamp_millis)
+                .process_results(|timestamps| timestamps.max())?;
+            keyed_branches.push((newest, (name, branch_target)));
+        }
+        keyed_branches.sort_by_key(|&(newest, _)| Reverse(newest));
+        branches_to_list = keyed_branches
+            .into_iter()
+            .map(|(_, branch)| branch)
+            .collect();
+    }
+
     for (name, branch_target) in branches_to_list {
         let local_target = branch_target.local_target;
         let remote_refs = branch_target.remote_refs;
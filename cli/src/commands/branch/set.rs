@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use clap::builder::NonEmptyStringValueParser;
+use itertools::Itertools as _;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::RefTarget;
 
@@ -33,6 +34,9 @@ pub struct BranchSetArgs {
     allow_backwards: bool,
 
     /// The branches to update
+    ///
+    /// All branches are updated to the same revision in a single operation.
+    /// If any of them can't be updated, none of them will be.
     #[arg(required = true, value_parser = NonEmptyStringValueParser::new())]
     names: Vec<String>,
 }
@@ -46,9 +50,10 @@ pub fn cmd_branch_set(
     let target_commit =
         workspace_command.resolve_single_rev(args.revision.as_ref().unwrap_or(&RevisionArg::AT))?;
     let repo = workspace_command.repo().as_ref();
-    let branch_names = &args.names;
+    let branch_names = args.names.iter().map(String::as_str).unique().collect_vec();
     let mut new_branch_names: Vec<&str> = Vec::new();
-    for name in branch_names {
+    let mut backward_branch_names: Vec<&str> = Vec::new();
+    for &name in &branch_names {
         let old_target = repo.view().get_local_branch(name);
         // If a branch is absent locally but is still tracking remote branches,
         // we are resurrecting the local branch, not "creating" a new branch.
@@ -56,12 +61,20 @@ pub fn cmd_branch_set(
             new_branch_names.push(name);
         }
         if !args.allow_backwards && !is_fast_forward(repo, old_target, target_commit.id()) {
-            return Err(user_error_with_hint(
-                format!("Refusing to move branch backwards or sideways: {name}"),
-                "Use --allow-backwards to allow it.",
-            ));
+            backward_branch_names.push(name);
         }
     }
+    // Validate all branches before updating any of them so that the operation
+    // is applied atomically.
+    if !backward_branch_names.is_empty() {
+        return Err(user_error_with_hint(
+            format!(
+                "Refusing to move branch backwards or sideways: {}",
+                backward_branch_names.join(", ")
+            ),
+            "Use --allow-backwards to allow it.",
+        ));
+    }
 
     if branch_names.len() > 1 {
         writeln!(
@@ -72,7 +85,7 @@ pub fn cmd_branch_set(
     }
 
     let mut tx = workspace_command.start_transaction();
-    for branch_name in branch_names {
+    for branch_name in &branch_names {
         tx.mut_repo()
             .set_local_branch_target(branch_name, RefTarget::normal(target_commit.id().clone()));
     }
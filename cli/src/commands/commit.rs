@@ -36,6 +36,9 @@ pub(crate) struct CommitArgs {
     /// The change description to use (don't open editor)
     #[arg(long = "message", short, value_name = "MESSAGE")]
     message_paragraphs: Vec<String>,
+    /// Keep the current description, even if it's empty (don't open editor)
+    #[arg(long, conflicts_with = "message_paragraphs")]
+    allow_empty_description: bool,
     /// Put these paths in the first commit
     #[arg(value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
@@ -98,6 +101,8 @@ new working-copy commit.
 
     let description = if !args.message_paragraphs.is_empty() {
         join_message_paragraphs(&args.message_paragraphs)
+    } else if args.allow_empty_description {
+        commit.description().to_owned()
     } else {
         edit_description(tx.base_repo(), &template, command.settings())?
     };
@@ -13,13 +13,17 @@
 // limitations under the License.
 
 use std::io::Write;
+use std::path::PathBuf;
 
+use itertools::Itertools as _;
+use jj_lib::settings::UserSettings;
 use tracing::instrument;
 
 use crate::cli_util::{get_new_config_file_path, run_ui_editor, CommandHelper};
-use crate::command_error::{config_error, user_error, CommandError};
+use crate::command_error::{config_error, user_error, user_error_with_message, CommandError};
 use crate::config::{
-    to_toml_value, write_config_value_to_file, AnnotatedValue, ConfigNamePathBuf, ConfigSource,
+    create_config_file_if_missing, migrate_config_file, to_toml_value, write_config_value_to_file,
+    AnnotatedValue, ConfigMigration, ConfigNamePathBuf, ConfigSource,
 };
 use crate::generic_templater::GenericTemplateLanguage;
 use crate::template_builder::TemplateLanguage as _;
@@ -36,6 +40,10 @@ pub(crate) struct ConfigLevelArgs {
     /// Target the repo-level config
     #[arg(long, group = "config_level")]
     repo: bool,
+
+    /// Target the workspace-level config
+    #[arg(long, group = "config_level")]
+    workspace: bool,
 }
 
 impl ConfigLevelArgs {
@@ -48,6 +56,8 @@ impl ConfigLevelArgs {
             Some(ConfigSource::User)
         } else if self.repo {
             Some(ConfigSource::Repo)
+        } else if self.workspace {
+            Some(ConfigSource::Workspace)
         } else {
             None
         }
@@ -69,6 +79,7 @@ pub(crate) enum ConfigCommand {
     Get(ConfigGetArgs),
     #[command(visible_alias("l"))]
     List(ConfigListArgs),
+    Migrate(ConfigMigrateArgs),
     #[command(visible_alias("p"))]
     Path(ConfigPathArgs),
     #[command(visible_alias("s"))]
@@ -130,6 +141,20 @@ pub(crate) struct ConfigSetArgs {
     level: ConfigLevelArgs,
 }
 
+/// Rename deprecated options in config files to their new names
+///
+/// By default, the user, repo, and workspace config files are migrated.
+/// Options already set by their new names are left untouched.
+#[derive(clap::Args, Clone, Debug)]
+#[command(mut_group("config_level", |g| g.required(false)))]
+pub(crate) struct ConfigMigrateArgs {
+    #[command(flatten)]
+    level: ConfigLevelArgs,
+    /// Only report the options to be renamed without modifying the files
+    #[arg(long)]
+    dry_run: bool,
+}
+
 /// Start an editor on a jj config file.
 ///
 /// Creates the file if it doesn't already exist regardless of what the editor
@@ -149,6 +174,9 @@ pub(crate) struct ConfigEditArgs {
 pub(crate) struct ConfigPathArgs {
     #[command(flatten)]
     pub level: ConfigLevelArgs,
+    /// Print the file that `jj config set` would modify for this option
+    #[arg(long, value_name = "NAME")]
+    pub value_of: Option<ConfigNamePathBuf>,
 }
 
 #[instrument(skip_all)]
@@ -161,6 +189,7 @@ pub(crate) fn cmd_config(
         ConfigCommand::Edit(args) => cmd_config_edit(ui, command, args),
         ConfigCommand::Get(args) => cmd_config_get(ui, command, args),
         ConfigCommand::List(args) => cmd_config_list(ui, command, args),
+        ConfigCommand::Migrate(args) => cmd_config_migrate(ui, command, args),
         ConfigCommand::Path(args) => cmd_config_path(ui, command, args),
         ConfigCommand::Set(args) => cmd_config_set(ui, command, args),
     }
@@ -168,9 +197,11 @@ pub(crate) fn cmd_config(
 
 // AnnotatedValue will be cloned internally in the templater. If the cloning
 // cost matters, wrap it with Rc.
-fn config_template_language() -> GenericTemplateLanguage<'static, AnnotatedValue> {
+fn config_template_language(
+    settings: &UserSettings,
+) -> GenericTemplateLanguage<'static, AnnotatedValue> {
     type L = GenericTemplateLanguage<'static, AnnotatedValue>;
-    let mut language = L::new();
+    let mut language = L::new(settings);
     // "name" instead of "path" to avoid confusion with the source file path
     language.add_keyword("name", |self_property| {
         let out_property = self_property.map(|annotated| annotated.path.to_string());
@@ -196,7 +227,7 @@ pub(crate) fn cmd_config_list(
     args: &ConfigListArgs,
 ) -> Result<(), CommandError> {
     let template = {
-        let language = config_template_language();
+        let language = config_template_language(command.settings());
         let text = match &args.template {
             Some(value) => value.to_owned(),
             None => command
@@ -289,14 +320,95 @@ pub(crate) fn cmd_config_set(
     command: &CommandHelper,
     args: &ConfigSetArgs,
 ) -> Result<(), CommandError> {
-    let config_path = get_new_config_file_path(&args.level.expect_source_kind(), command)?;
+    let config_path = get_config_file_path_to_set(&args.level, command)?;
+    write_config_value_to_file(&args.name, &args.value, &config_path)
+}
+
+/// Returns the config file which `jj config set` would modify.
+fn get_config_file_path_to_set(
+    level: &ConfigLevelArgs,
+    command: &CommandHelper,
+) -> Result<PathBuf, CommandError> {
+    let config_path = get_new_config_file_path(&level.expect_source_kind(), command)?;
     if config_path.is_dir() {
         return Err(user_error(format!(
             "Can't set config in path {path} (dirs not supported)",
             path = config_path.display()
         )));
     }
-    write_config_value_to_file(&args.name, &args.value, &config_path)
+    Ok(config_path)
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_config_migrate(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ConfigMigrateArgs,
+) -> Result<(), CommandError> {
+    let sources = if let Some(source) = args.level.get_source_kind() {
+        vec![source]
+    } else if command.workspace_loader().is_ok() {
+        vec![
+            ConfigSource::User,
+            ConfigSource::Repo,
+            ConfigSource::Workspace,
+        ]
+    } else {
+        vec![ConfigSource::User]
+    };
+    let mut config_paths = vec![];
+    for source in &sources {
+        let path = get_new_config_file_path(source, command)?;
+        if path.is_dir() {
+            // The user config can be a directory of TOML files.
+            let mut file_paths: Vec<_> = path
+                .read_dir()
+                .and_then(|entries| entries.map_ok(|entry| entry.path()).try_collect())
+                .map_err(|err| {
+                    user_error_with_message(
+                        format!("Failed to read directory {}", path.display()),
+                        err,
+                    )
+                })?;
+            file_paths.retain(|path| path.is_file() && path.extension() == Some("toml".as_ref()));
+            file_paths.sort();
+            config_paths.extend(file_paths);
+        } else if path.is_file() {
+            config_paths.push(path);
+        }
+    }
+
+    let mut num_renamed = 0;
+    for path in &config_paths {
+        for migration in migrate_config_file(path, args.dry_run)? {
+            match migration {
+                ConfigMigration::Renamed { old_name, new_name } => {
+                    writeln!(
+                        ui.status(),
+                        "{verb} {old_name} to {new_name} in {path}",
+                        verb = if args.dry_run {
+                            "Would rename"
+                        } else {
+                            "Renamed"
+                        },
+                        path = path.display()
+                    )?;
+                    num_renamed += 1;
+                }
+                ConfigMigration::Conflicted { old_name, new_name } => {
+                    writeln!(
+                        ui.warning_default(),
+                        "Not renaming {old_name} in {path} because {new_name} is already set",
+                        path = path.display()
+                    )?;
+                }
+            }
+        }
+    }
+    if num_renamed == 0 {
+        writeln!(ui.status(), "No deprecated config options to rename.")?;
+    }
+    Ok(())
 }
 
 #[instrument(skip_all)]
@@ -306,6 +418,12 @@ pub(crate) fn cmd_config_edit(
     args: &ConfigEditArgs,
 ) -> Result<(), CommandError> {
     let config_path = get_new_config_file_path(&args.level.expect_source_kind(), command)?;
+    create_config_file_if_missing(&config_path).map_err(|err| {
+        user_error_with_message(
+            format!("Failed to create config file {}", config_path.display()),
+            err,
+        )
+    })?;
     run_ui_editor(command.settings(), &config_path)
 }
 
@@ -315,7 +433,13 @@ pub(crate) fn cmd_config_path(
     command: &CommandHelper,
     args: &ConfigPathArgs,
 ) -> Result<(), CommandError> {
-    let config_path = get_new_config_file_path(&args.level.expect_source_kind(), command)?;
+    // There's only one file per level, so the option name doesn't matter
+    // other than that it should be settable.
+    let config_path = if args.value_of.is_some() {
+        get_config_file_path_to_set(&args.level, command)?
+    } else {
+        get_new_config_file_path(&args.level.expect_source_kind(), command)?
+    };
     writeln!(
         ui.stdout(),
         "{}",
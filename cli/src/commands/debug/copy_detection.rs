@@ -0,0 +1,131 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+use jj_lib::copy_detection::{self, CopyDetectionOptions, CopyOperation};
+use jj_lib::matchers::EverythingMatcher;
+
+use crate::cli_util::{CommandHelper, RevisionArg};
+use crate::command_error::CommandError;
+use crate::diff_util;
+use crate::ui::Ui;
+
+/// Pairs within this many percentage points below the threshold are shown by
+/// `--scores`.
+const NEAR_THRESHOLD_MARGIN: u32 = 10;
+
+/// Show renamed and copied files detected in a revision
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugCopyDetectionArgs {
+    /// Show copies detected in this revision, compared to its parent(s)
+    #[arg(default_value = "@")]
+    revision: RevisionArg,
+    /// Minimum similarity in percent
+    #[arg(
+        long,
+        default_value_t = copy_detection::DEFAULT_SIMILARITY_THRESHOLD,
+        value_parser = clap::value_parser!(u32).range(0..=100),
+    )]
+    threshold: u32,
+    /// Show the similarity of each pair, including pairs just below the
+    /// threshold
+    #[arg(long)]
+    scores: bool,
+    /// Also consider unmodified files as copy sources
+    #[arg(long)]
+    find_copies_harder: bool,
+}
+
+pub fn cmd_debug_copy_detection(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugCopyDetectionArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(&args.revision)?;
+    let from_tree = commit.parent_tree(workspace_command.repo().as_ref())?;
+    let to_tree = commit.tree()?;
+    let options = CopyDetectionOptions {
+        threshold: args.threshold,
+        ..diff_util::copy_detection_options_for(command.settings(), args.find_copies_harder)?
+    };
+    // Score with a lower threshold so that near misses aren't filtered out
+    let scoring_options = CopyDetectionOptions {
+        threshold: args.threshold.saturating_sub(NEAR_THRESHOLD_MARGIN),
+        ..options.clone()
+    };
+    let candidates = copy_detection::score_copy_candidates(
+        &from_tree,
+        &to_tree,
+        &EverythingMatcher,
+        &scoring_options,
+    )?;
+    let copies = copy_detection::select_copies(&candidates, options.threshold);
+
+    let mut formatter = ui.stdout_formatter();
+    if !args.scores {
+        for copy in copies.iter() {
+            let source = workspace_command.format_file_path(&copy.source);
+            let target = workspace_command.format_file_path(&copy.target);
+            writeln!(formatter, "{source} -> {target}")?;
+        }
+        return Ok(());
+    }
+    let near_misses = candidates
+        .iter()
+        .filter(|candidate| {
+            (scoring_options.threshold..options.threshold).contains(&candidate.similarity)
+        })
+        .map(|candidate| {
+            (
+                &candidate.source,
+                &candidate.target,
+                None,
+                candidate.similarity,
+            )
+        });
+    let detected = copies.iter().map(|copy| {
+        (
+            &copy.source,
+            &copy.target,
+            Some(copy.operation),
+            copy.similarity,
+        )
+    });
+    let rows = detected
+        .merge_by(near_misses, |(_, a, _, _), (_, b, _, _)| a <= b)
+        .collect_vec();
+    for (source, target, operation, similarity) in rows {
+        let source = workspace_command.format_file_path(source);
+        let target = workspace_command.format_file_path(target);
+        match operation {
+            Some(CopyOperation::Rename) => {
+                writeln!(formatter, "R {similarity:>3}% {source} -> {target}")?;
+            }
+            Some(CopyOperation::Copy) => {
+                writeln!(formatter, "C {similarity:>3}% {source} -> {target}")?;
+            }
+            None => {
+                writeln!(
+                    formatter,
+                    "- {similarity:>3}% {source} -> {target} (below threshold)"
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -16,9 +16,12 @@ use std::fmt::Debug;
 use std::io::Write as _;
 
 use jj_lib::fileset;
+use jj_lib::matchers::Matcher;
+use jj_lib::repo_path::RepoPath;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::commands::file::list::walk_working_copy_dir;
 use crate::ui::Ui;
 
 /// Parse fileset expression
@@ -26,6 +29,12 @@ use crate::ui::Ui;
 pub struct DebugFilesetArgs {
     #[arg(value_hint = clap::ValueHint::AnyPath)]
     path: String,
+    /// List files on disk under this directory which match the fileset
+    #[arg(long, value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    matched_paths: Option<String>,
+    /// Also list files which are ignored by .gitignore files
+    #[arg(long, requires = "matched_paths")]
+    include_ignored: bool,
 }
 
 pub fn cmd_debug_fileset(
@@ -44,5 +53,38 @@ pub fn cmd_debug_fileset(
     let matcher = expression.to_matcher();
     writeln!(ui.stdout(), "-- Matcher:")?;
     writeln!(ui.stdout(), "{matcher:#?}")?;
+
+    if let Some(dir) = &args.matched_paths {
+        let start_dir = workspace_command.parse_file_path(dir)?;
+        let git_ignore = if args.include_ignored {
+            None
+        } else {
+            Some(workspace_command.base_ignores()?)
+        };
+        let mut matched_paths = vec![];
+        // Walk towards and under the start_dir, and collect files under it.
+        walk_working_copy_dir(
+            workspace_command.workspace_root(),
+            RepoPath::root(),
+            git_ignore,
+            false,
+            &mut |path, is_dir, ignored| {
+                if is_dir {
+                    return Ok(
+                        !ignored && (path.starts_with(&start_dir) || start_dir.starts_with(path))
+                    );
+                }
+                if !ignored && path.starts_with(&start_dir) && matcher.matches(path) {
+                    matched_paths.push(path.to_owned());
+                }
+                Ok(false)
+            },
+        )?;
+        writeln!(ui.stdout())?;
+        writeln!(ui.stdout(), "-- Matched paths:")?;
+        for path in &matched_paths {
+            writeln!(ui.stdout(), "{}", workspace_command.format_file_path(path))?;
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,85 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::Write as _;
+
+use jj_lib::repo_path::RepoPath;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Show which ignore rule applies to a path
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugGitignoreArgs {
+    #[arg(value_hint = clap::ValueHint::AnyPath)]
+    path: String,
+}
+
+pub fn cmd_debug_gitignore(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugGitignoreArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let workspace_root = workspace_command.workspace_root();
+    let path = workspace_command.parse_file_path(&args.path)?;
+
+    // Chain the .gitignore files from the workspace root down to the parent
+    // directory as the snapshot does.
+    let mut git_ignore = workspace_command.base_ignores()?;
+    let mut dirs = vec![];
+    let mut dir = path.parent();
+    while let Some(parent) = dir {
+        dirs.push(parent);
+        dir = parent.parent();
+    }
+    for dir in dirs.into_iter().rev() {
+        let disk_dir = dir.to_fs_path(workspace_root);
+        git_ignore = git_ignore
+            .chain_with_file(&dir.to_internal_dir_string(), disk_dir.join(".gitignore"))?;
+    }
+
+    let ui_path = workspace_command.format_file_path(&path);
+    let internal_path = if path.to_fs_path(workspace_root).is_dir() {
+        path.to_internal_dir_string()
+    } else {
+        path.as_internal_file_string().to_owned()
+    };
+    let Some(rule) = git_ignore.matched_rule(&internal_path) else {
+        writeln!(ui.stdout(), "{ui_path}: not ignored (no matching rule)")?;
+        return Ok(());
+    };
+    let source = match &rule.source {
+        Some(source) => match source.strip_prefix(workspace_root) {
+            Ok(relative) => workspace_command.format_file_path(RepoPath::from_internal_string(
+                &relative.to_string_lossy().replace('\\', "/"),
+            )),
+            Err(_) => source.display().to_string(),
+        },
+        None => "<unknown>".to_owned(),
+    };
+    let status = if rule.is_ignored {
+        "ignored"
+    } else {
+        "not ignored"
+    };
+    writeln!(
+        ui.stdout(),
+        "{ui_path}: {status} by {source}: {}",
+        rule.pattern
+    )?;
+    Ok(())
+}
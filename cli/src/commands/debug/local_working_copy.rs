@@ -15,10 +15,14 @@
 use std::fmt::Debug;
 use std::io::Write as _;
 
+use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::local_working_copy::LocalWorkingCopy;
 use jj_lib::working_copy::WorkingCopy;
 
 use super::check_local_disk_wc;
 use crate::cli_util::CommandHelper;
+#[cfg(not(feature = "watchman"))]
+use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::ui::Ui;
 
@@ -26,17 +30,24 @@ use crate::ui::Ui;
 ///
 /// This command only works with a standard local-disk working copy.
 #[derive(clap::Args, Clone, Debug)]
-pub struct DebugLocalWorkingCopyArgs {}
+pub struct DebugLocalWorkingCopyArgs {
+    /// Show the Watchman filesystem monitor state instead of the file states
+    #[arg(long)]
+    watchman_status: bool,
+}
 
 pub fn cmd_debug_local_working_copy(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &DebugLocalWorkingCopyArgs,
+    args: &DebugLocalWorkingCopyArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
     let wc = check_local_disk_wc(workspace_command.working_copy().as_any())?;
     writeln!(ui.stdout(), "Current operation: {:?}", wc.operation_id())?;
     writeln!(ui.stdout(), "Current tree: {:?}", wc.tree_id()?)?;
+    if args.watchman_status {
+        return print_watchman_status(ui, command, wc);
+    }
     for (file, state) in wc.file_states()? {
         writeln!(
             ui.stdout(),
@@ -49,3 +60,42 @@ pub fn cmd_debug_local_working_copy(
     }
     Ok(())
 }
+
+fn print_watchman_status(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    wc: &LocalWorkingCopy,
+) -> Result<(), CommandError> {
+    let config = match command.fsmonitor_settings()? {
+        FsmonitorSettings::Watchman(config) => config,
+        _ => {
+            writeln!(ui.stdout(), "Watchman: disabled")?;
+            return Ok(());
+        }
+    };
+    writeln!(ui.stdout(), "Watchman: enabled")?;
+    #[cfg(feature = "watchman")]
+    {
+        match wc.watchman_clock()? {
+            Some(clock) => writeln!(ui.stdout(), "Last clock: {clock:?}")?,
+            None => writeln!(ui.stdout(), "Last clock: none")?,
+        }
+        writeln!(
+            ui.stdout(),
+            "Snapshot trigger: {}",
+            if wc.is_watchman_trigger_registered(&config)? {
+                "registered"
+            } else {
+                "not registered"
+            }
+        )?;
+        Ok(())
+    }
+    #[cfg(not(feature = "watchman"))]
+    {
+        let _ = (wc, config); // use the variables
+        Err(user_error(
+            "Cannot query Watchman because jj was not compiled with the `watchman` feature",
+        ))
+    }
+}
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod copy_detection;
 pub mod fileset;
+pub mod gitignore;
 pub mod index;
 pub mod local_working_copy;
 pub mod operation;
@@ -30,7 +32,9 @@ use std::fmt::Debug;
 use clap::Subcommand;
 use jj_lib::local_working_copy::LocalWorkingCopy;
 
+use self::copy_detection::{cmd_debug_copy_detection, DebugCopyDetectionArgs};
 use self::fileset::{cmd_debug_fileset, DebugFilesetArgs};
+use self::gitignore::{cmd_debug_gitignore, DebugGitignoreArgs};
 use self::index::{cmd_debug_index, DebugIndexArgs};
 use self::local_working_copy::{cmd_debug_local_working_copy, DebugLocalWorkingCopyArgs};
 use self::operation::{cmd_debug_operation, DebugOperationArgs};
@@ -49,7 +53,9 @@ use crate::ui::Ui;
 #[derive(Subcommand, Clone, Debug)]
 #[command(hide = true)]
 pub enum DebugCommand {
+    CopyDetection(DebugCopyDetectionArgs),
     Fileset(DebugFilesetArgs),
+    Gitignore(DebugGitignoreArgs),
     Index(DebugIndexArgs),
     LocalWorkingCopy(DebugLocalWorkingCopyArgs),
     #[command(visible_alias = "view")]
@@ -70,7 +76,9 @@ pub fn cmd_debug(
     subcommand: &DebugCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        DebugCommand::CopyDetection(args) => cmd_debug_copy_detection(ui, command, args),
         DebugCommand::Fileset(args) => cmd_debug_fileset(ui, command, args),
+        DebugCommand::Gitignore(args) => cmd_debug_gitignore(ui, command, args),
         DebugCommand::Index(args) => cmd_debug_index(ui, command, args),
         DebugCommand::LocalWorkingCopy(args) => cmd_debug_local_working_copy(ui, command, args),
         DebugCommand::Operation(args) => cmd_debug_operation(ui, command, args),
@@ -14,6 +14,7 @@
 
 use std::fmt::Debug;
 use std::io::Write as _;
+use std::time::{Duration, Instant};
 
 use jj_lib::object_id::ObjectId;
 use jj_lib::revset;
@@ -27,6 +28,18 @@ use crate::ui::Ui;
 #[derive(clap::Args, Clone, Debug)]
 pub struct DebugRevsetArgs {
     revision: String,
+    /// Evaluate the revset repeatedly and report the time taken instead of
+    /// printing the commit IDs
+    #[arg(long)]
+    benchmark: bool,
+    /// Number of times to evaluate the revset with `--benchmark`
+    #[arg(
+        long,
+        requires = "benchmark",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    iterations: u32,
 }
 
 pub fn cmd_debug_revset(
@@ -63,9 +76,39 @@ pub fn cmd_debug_revset(
     writeln!(ui.stdout(), "{revset:#?}")?;
     writeln!(ui.stdout())?;
 
+    if args.benchmark {
+        let mut durations = vec![];
+        let mut num_commits = 0;
+        for _ in 0..args.iterations {
+            let start = Instant::now();
+            num_commits = expression.evaluate(repo)?.iter().count();
+            durations.push(start.elapsed());
+        }
+        durations.sort();
+        writeln!(ui.stdout(), "-- Benchmark:")?;
+        writeln!(ui.stdout(), "Iterations: {}", args.iterations)?;
+        writeln!(ui.stdout(), "Commits: {num_commits}")?;
+        writeln!(ui.stdout(), "Min: {}", format_duration(durations[0]))?;
+        writeln!(
+            ui.stdout(),
+            "Median: {}",
+            format_duration(durations[durations.len() / 2])
+        )?;
+        writeln!(
+            ui.stdout(),
+            "Max: {}",
+            format_duration(durations[durations.len() - 1])
+        )?;
+        return Ok(());
+    }
+
     writeln!(ui.stdout(), "-- Commit IDs:")?;
     for commit_id in revset.iter() {
         writeln!(ui.stdout(), "{}", commit_id.hex())?;
     }
     Ok(())
 }
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
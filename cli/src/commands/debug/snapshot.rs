@@ -13,21 +13,114 @@
 // limitations under the License.
 
 use std::fmt::Debug;
+use std::io::Write as _;
 
-use crate::cli_util::CommandHelper;
-use crate::command_error::CommandError;
+use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::repo::Repo as _;
+use jj_lib::working_copy::SnapshotOptions;
+
+use crate::cli_util::{print_skipped_large_files, CommandHelper};
+use crate::command_error::{user_error_with_hint, CommandError};
 use crate::ui::Ui;
 
 /// Trigger a snapshot in the op log
 #[derive(clap::Args, Clone, Debug)]
-pub struct DebugSnapshotArgs {}
+pub struct DebugSnapshotArgs {
+    /// Print how many written files had contents already seen by this process
+    #[arg(long)]
+    stats: bool,
+    /// Snapshot once with Watchman and once with a full scan of the working
+    /// copy, and report the files whose snapshots differ
+    ///
+    /// Neither snapshot is recorded.
+    #[arg(long, conflicts_with = "stats")]
+    compare_watchman: bool,
+}
 
 pub fn cmd_debug_snapshot(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &DebugSnapshotArgs,
+    args: &DebugSnapshotArgs,
 ) -> Result<(), CommandError> {
-    // workspace helper will snapshot as needed
-    command.workspace_helper(ui)?;
+    if args.compare_watchman {
+        return compare_watchman_snapshot(ui, command);
+    }
+    if !args.stats {
+        // workspace helper will snapshot as needed
+        command.workspace_helper(ui)?;
+        return Ok(());
+    }
+    let mut workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let store = workspace_command.repo().store().clone();
+    store.enable_write_file_stats();
+    workspace_command.maybe_snapshot(ui)?;
+    let stats = store.write_file_stats().unwrap();
+    writeln!(
+        ui.stdout(),
+        "Files written: {} unseen, {} already seen",
+        stats.unseen_count,
+        stats.seen_count
+    )?;
+    Ok(())
+}
+
+fn compare_watchman_snapshot(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    let fsmonitor_settings = command.fsmonitor_settings()?;
+    if !matches!(fsmonitor_settings, FsmonitorSettings::Watchman(_)) {
+        return Err(user_error_with_hint(
+            "Watchman is not configured",
+            r#"Set `core.fsmonitor = "watchman"` to enable it."#,
+        ));
+    }
+    let mut workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let base_ignores = workspace_command.base_ignores()?;
+    let max_new_file_size = command.settings().max_new_file_size()?;
+    let follow_symlinks = command.settings().follow_symlinks()?;
+    let mut snapshot = |fsmonitor_settings| -> Result<_, CommandError> {
+        let (mut locked_ws, _wc_commit) = workspace_command.start_working_copy_mutation()?;
+        let old_tree_id = locked_ws.locked_wc().old_tree_id().clone();
+        let (new_tree_id, stats) = locked_ws.locked_wc().snapshot(SnapshotOptions {
+            base_ignores: base_ignores.clone(),
+            fsmonitor_settings,
+            progress: None,
+            max_new_file_size,
+            follow_symlinks,
+        })?;
+        // The lock is released without updating the working-copy state.
+        Ok((old_tree_id, new_tree_id, stats))
+    };
+    let (old_tree_id, watchman_tree_id, _) = snapshot(fsmonitor_settings)?;
+    let (_, full_tree_id, full_stats) = snapshot(FsmonitorSettings::None)?;
+    if !full_stats.skipped_large_files.is_empty() {
+        print_skipped_large_files(
+            ui,
+            workspace_command.path_converter(),
+            &full_stats.skipped_large_files,
+        )?;
+    }
+
+    let store = workspace_command.repo().store();
+    let old_tree = store.get_root_tree(&old_tree_id)?;
+    let watchman_tree = store.get_root_tree(&watchman_tree_id)?;
+    let full_tree = store.get_root_tree(&full_tree_id)?;
+    let mut num_differences = 0;
+    for (path, diff) in watchman_tree.diff(&full_tree, &EverythingMatcher) {
+        let (watchman_value, full_value) = diff?;
+        let old_value = old_tree.path_value(&path)?;
+        let kind = if watchman_value == old_value {
+            "Missed by Watchman"
+        } else if full_value == old_value {
+            "Falsely reported by Watchman"
+        } else {
+            "Snapshotted differently"
+        };
+        let ui_path = workspace_command.format_file_path(&path);
+        writeln!(ui.stdout(), "{kind}: {ui_path}")?;
+        num_differences += 1;
+    }
+    if num_differences == 0 {
+        writeln!(ui.status(), "Watchman and full scan snapshots match")?;
+    }
     Ok(())
 }
@@ -14,24 +14,77 @@
 
 use std::fmt::Debug;
 use std::io::Write as _;
+use std::slice;
+
+use itertools::Itertools as _;
 
 use crate::cli_util::CommandHelper;
-use crate::command_error::CommandError;
-use crate::template_parser;
+use crate::command_error::{print_error, user_error, CommandError};
+use crate::commit_templater::CommitTemplateLanguage;
+use crate::template_builder;
+use crate::template_parser::{self, ExpressionKind};
 use crate::ui::Ui;
 
 /// Parse a template
 #[derive(clap::Args, Clone, Debug)]
 pub struct DebugTemplateArgs {
     template: String,
+    /// Build the template as a commit template without evaluating it
+    ///
+    /// Type errors and unknown keywords are reported without printing the
+    /// parsed tree. Top-level concatenated parts are checked independently,
+    /// so more than one error can be reported at a time.
+    #[arg(long, alias = "type-check-only")]
+    type_check: bool,
+    /// Maximum number of errors to report with `--type-check`
+    #[arg(long, default_value_t = 10, requires = "type_check")]
+    max_errors: usize,
 }
 
 pub fn cmd_debug_template(
     ui: &mut Ui,
-    _command: &CommandHelper,
+    command: &CommandHelper,
     args: &DebugTemplateArgs,
 ) -> Result<(), CommandError> {
+    if args.type_check {
+        return type_check_template(ui, command, args);
+    }
     let node = template_parser::parse_template(&args.template)?;
     writeln!(ui.stdout(), "{node:#?}")?;
     Ok(())
 }
+
+fn type_check_template(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugTemplateArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let language = workspace_command.commit_template_language()?;
+    let aliases_map = workspace_command.template_aliases_map();
+    let node = template_parser::parse(&args.template, aliases_map)?;
+    let parts = match &node.kind {
+        ExpressionKind::Concat(nodes) => nodes.as_slice(),
+        _ => slice::from_ref(&node),
+    };
+    let errors = parts
+        .iter()
+        .filter_map(|part| {
+            template_builder::build(&language, part, CommitTemplateLanguage::wrap_commit)
+                .map_err(|err| err.extend_alias_candidates(aliases_map))
+                .err()
+        })
+        .collect_vec();
+    if errors.is_empty() {
+        writeln!(ui.status(), "Template type-checks OK")?;
+        return Ok(());
+    }
+    let num_errors = errors.len();
+    for err in errors.into_iter().take(args.max_errors) {
+        let cmd_err = CommandError::from(err);
+        print_error(ui, "Error: ", cmd_err.error.as_ref(), &cmd_err.hints)?;
+    }
+    Err(user_error(format!(
+        "Template has {num_errors} type error(s)"
+    )))
+}
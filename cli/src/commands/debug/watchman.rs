@@ -20,11 +20,17 @@ use std::io::Write as _;
 
 use clap::Subcommand;
 #[cfg(feature = "watchman")]
-use jj_lib::fsmonitor::{FsmonitorSettings, WatchmanConfig};
+use jj_lib::fsmonitor::watchman::{self, WatchmanFsmonitor};
+#[cfg(feature = "watchman")]
+use jj_lib::fsmonitor::{Fsmonitor as _, FsmonitorClock, FsmonitorSettings, WatchmanConfig};
 #[cfg(feature = "watchman")]
 use jj_lib::local_working_copy::LocalWorkingCopy;
+#[cfg(feature = "watchman")]
+use jj_lib::protos::working_copy::watchman_clock::WatchmanClock;
 
 use crate::cli_util::CommandHelper;
+#[cfg(feature = "watchman")]
+use crate::command_error::user_error_with_message;
 use crate::command_error::{user_error, CommandError};
 use crate::ui::Ui;
 
@@ -34,6 +40,15 @@ pub enum DebugWatchmanCommand {
     Status,
     QueryClock,
     QueryChangedFiles,
+    /// Query Watchman for the files changed since the given clock
+    ///
+    /// Unlike `query-changed-files`, this doesn't use the clock saved in the
+    /// working copy state. The paths are printed as reported by Watchman.
+    QueryChanged {
+        /// Watchman clock to query from (e.g. `c:1234:5678`)
+        #[arg(long)]
+        since: String,
+    },
     ResetClock,
 }
 
@@ -50,7 +65,7 @@ pub fn cmd_debug_watchman(
     match subcommand {
         DebugWatchmanCommand::Status => {
             // TODO(ilyagr): It would be nice to add colors here
-            let config = match command.settings().fsmonitor_settings()? {
+            let config = match command.fsmonitor_settings()? {
                 FsmonitorSettings::Watchman(config) => {
                     writeln!(ui.stdout(), "Watchman is enabled via `core.fsmonitor`.")?;
                     writeln!(
@@ -107,6 +122,32 @@ pub fn cmd_debug_watchman(
             let (_clock, changed_files) = wc.query_watchman(&WatchmanConfig::default())?;
             writeln!(ui.stdout(), "Changed files: {changed_files:?}")?;
         }
+        DebugWatchmanCommand::QueryChanged { since } => {
+            let config = match command.fsmonitor_settings()? {
+                FsmonitorSettings::Watchman(config) => config,
+                _ => WatchmanConfig::default(),
+            };
+            let previous_clock = FsmonitorClock {
+                watchman_clock: Some(WatchmanClock::StringClock(since.clone())),
+            };
+            let (clock, changed_files) = WatchmanFsmonitor::new(config)
+                .query_changed_files(workspace_command.workspace_root(), Some(previous_clock))
+                .map_err(|err| user_error_with_message("Failed to query Watchman", err))?;
+            if let Some(clock) = clock {
+                writeln!(ui.stdout(), "Clock: {:?}", watchman::Clock::from(clock))?;
+            }
+            match changed_files {
+                Some(changed_files) => {
+                    for path in changed_files {
+                        writeln!(ui.stdout(), "{}", path.display())?;
+                    }
+                }
+                None => writeln!(
+                    ui.stdout(),
+                    "Watchman reported a fresh instance; all files may have changed."
+                )?,
+            }
+        }
         DebugWatchmanCommand::ResetClock => {
             let (mut locked_ws, _commit) = workspace_command.start_working_copy_mutation()?;
             let Some(locked_local_wc): Option<&mut LockedLocalWorkingCopy> =
@@ -12,15 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::io::{self, Read, Write};
 
+use itertools::Itertools as _;
+use jj_lib::commit::{Commit, CommitIteratorExt as _};
 use jj_lib::object_id::ObjectId;
 use tracing::instrument;
 
-use crate::cli_util::{CommandHelper, RevisionArg};
+use crate::cli_util::{short_commit_hash, CommandHelper, RevisionArg, WorkspaceCommandHelper};
 use crate::command_error::CommandError;
 use crate::description_util::{
-    description_template_for_describe, edit_description, join_message_paragraphs,
+    description_template_for_describe, edit_description, edit_multiple_descriptions,
+    join_message_paragraphs,
 };
 use crate::ui::Ui;
 
@@ -28,6 +32,10 @@ use crate::ui::Ui;
 ///
 /// Starts an editor to let you edit the description of a change. The editor
 /// will be $EDITOR, or `pico` if that's not defined (`Notepad` on Windows).
+///
+/// With `--batch`, the descriptions of all the revisions in the given revset
+/// are edited in a single editor. Each description follows a
+/// `JJ: describe <commit>` line.
 #[derive(clap::Args, Clone, Debug)]
 #[command(visible_aliases = &["desc"])]
 pub(crate) struct DescribeArgs {
@@ -58,6 +66,13 @@ pub(crate) struct DescribeArgs {
     /// $ JJ_USER='Foo Bar' JJ_EMAIL=foo@bar.com jj describe --reset-author
     #[arg(long)]
     reset_author: bool,
+    /// Edit the descriptions of all the revisions in the revset in a single
+    /// editor
+    ///
+    /// If the section of a revision is deleted, its description is left
+    /// unchanged.
+    #[arg(long, conflicts_with_all = ["message_paragraphs", "stdin", "no_edit"])]
+    batch: bool,
 }
 
 #[instrument(skip_all)]
@@ -67,6 +82,9 @@ pub(crate) fn cmd_describe(
     args: &DescribeArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
+    if args.batch {
+        return describe_batch(ui, command, workspace_command, args);
+    }
     let commit = workspace_command.resolve_single_rev(&args.revision)?;
     workspace_command.check_rewritable([commit.id()])?;
     let description = if args.stdin {
@@ -99,3 +117,82 @@ pub(crate) fn cmd_describe(
     }
     Ok(())
 }
+
+fn describe_batch(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    mut workspace_command: WorkspaceCommandHelper,
+    args: &DescribeArgs,
+) -> Result<(), CommandError> {
+    let commits: Vec<Commit> = workspace_command
+        .parse_revset(&args.revision)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    workspace_command.check_rewritable(commits.iter().ids())?;
+    // Present the descriptions in topological order, parents first.
+    let templates: Vec<_> = commits
+        .iter()
+        .rev()
+        .map(|commit| -> Result<_, CommandError> {
+            let template = description_template_for_describe(
+                ui,
+                command.settings(),
+                &workspace_command,
+                commit,
+            )?;
+            Ok((commit.id().clone(), template))
+        })
+        .try_collect()?;
+    let parsed =
+        edit_multiple_descriptions(workspace_command.repo(), &templates, command.settings())?;
+    if !parsed.missing.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "The descriptions of these commits were not found in the edited text and were \
+             left unchanged: {}",
+            parsed.missing.iter().map(short_commit_hash).join(", ")
+        )?;
+    }
+    let new_descriptions: HashMap<_, _> = commits
+        .iter()
+        .filter_map(|commit| {
+            let description = parsed.descriptions.get(commit.id())?;
+            (*description != *commit.description() || args.reset_author)
+                .then(|| (commit.id().clone(), description.clone()))
+        })
+        .collect();
+    if new_descriptions.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let mut num_described = 0;
+    let mut num_rebased = 0;
+    tx.mut_repo().transform_descendants(
+        command.settings(),
+        new_descriptions.keys().cloned().collect(),
+        |rewriter| {
+            let old_commit_id = rewriter.old_commit().id().clone();
+            let mut commit_builder = rewriter.rebase(command.settings())?;
+            if let Some(description) = new_descriptions.get(&old_commit_id) {
+                commit_builder = commit_builder.set_description(description);
+                if args.reset_author {
+                    let new_author = commit_builder.committer().clone();
+                    commit_builder = commit_builder.set_author(new_author);
+                }
+                num_described += 1;
+            } else {
+                num_rebased += 1;
+            }
+            commit_builder.write()?;
+            Ok(())
+        },
+    )?;
+    writeln!(ui.status(), "Updated {num_described} commits")?;
+    if num_rebased > 0 {
+        writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+    }
+    tx.finish(ui, format!("describe {} commits", new_descriptions.len()))?;
+    Ok(())
+}
@@ -12,11 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use regex::bytes::Regex;
 use tracing::instrument;
 
 use crate::cli_util::{print_unmatched_explicit_paths, CommandHelper, RevisionArg};
-use crate::command_error::CommandError;
-use crate::diff_util::DiffFormatArgs;
+use crate::command_error::{user_error_with_hint, CommandError};
+use crate::diff_util::{self, DiffFormatArgs};
 use crate::ui::Ui;
 
 /// Compare file contents between two revisions
@@ -48,6 +49,35 @@ pub(crate) struct DiffArgs {
     /// Restrict the diff to these paths
     #[arg(value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+    /// Detect renamed and copied files by comparing their contents
+    ///
+    /// Files added in the diff are compared with the removed and modified
+    /// files. Only the `--summary` and `--git` formats show the detected
+    /// renames and copies.
+    #[arg(long)]
+    copies: bool,
+    /// Like `--copies`, but also consider unmodified files as copy sources
+    ///
+    /// This can be slow in large repositories.
+    #[arg(long)]
+    find_copies_harder: bool,
+    /// Ignore changes whose lines all match the given regular expression
+    ///
+    /// A hunk is hidden if all of its added and removed lines match. A hunk
+    /// mixing matching and non-matching lines is shown in full. The `--git`
+    /// and `--stat` formats hide such hunks, and the other formats hide files
+    /// whose changes are all hidden. External diff tools aren't supported.
+    #[arg(long, value_name = "REGEX", value_parser = Regex::new)]
+    ignore_matching_lines: Option<Regex>,
+    /// Highlight blocks of lines that were moved within a file
+    ///
+    /// Removed lines that were added elsewhere in the same file (and vice
+    /// versa) are shown in different colors. A block must have at least 3
+    /// identical consecutive lines to be detected as moved, so lines edited
+    /// while moving are shown as regular changes. Only the `--git` format
+    /// highlights moved lines.
+    #[arg(long)]
+    color_moved: bool,
     #[command(flatten)]
     format: DiffFormatArgs,
 }
@@ -76,7 +106,24 @@ pub(crate) fn cmd_diff(
     }
     let fileset_expression = workspace_command.parse_file_patterns(&args.paths)?;
     let matcher = fileset_expression.to_matcher();
-    let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
+    let mut diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
+    if args.copies || args.find_copies_harder {
+        let options =
+            diff_util::copy_detection_options_for(command.settings(), args.find_copies_harder)?;
+        diff_renderer = diff_renderer.with_copy_detection(options);
+    }
+    if let Some(regex) = &args.ignore_matching_lines {
+        if diff_renderer.has_external_tools() {
+            return Err(user_error_with_hint(
+                "--ignore-matching-lines can't be used with external diff tools",
+                "Use a builtin format such as `--git`.",
+            ));
+        }
+        diff_renderer = diff_renderer.with_ignore_matching_lines(regex.clone());
+    }
+    if args.color_moved {
+        diff_renderer = diff_renderer.with_color_moved();
+    }
     ui.request_pager();
     diff_renderer.show_diff(
         ui,
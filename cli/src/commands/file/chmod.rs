@@ -12,7 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{BTreeSet, HashMap};
+use std::iter;
+
+use itertools::Itertools as _;
 use jj_lib::backend::TreeValue;
+use jj_lib::commit::CommitIteratorExt as _;
 use jj_lib::merged_tree::MergedTreeBuilder;
 use jj_lib::object_id::ObjectId;
 use tracing::instrument;
@@ -27,8 +32,8 @@ enum ChmodMode {
     // We use short names for enum values so that errors say that the possible values are `n, x`.
     #[value(name = "n", alias("normal"))]
     Normal,
-    /// Make a path executable (alias: executable)
-    #[value(name = "x", alias("executable"))]
+    /// Make a path executable (alias: executable, +x)
+    #[value(name = "x", aliases(["executable", "+x"]))]
     Executable,
 }
 
@@ -36,14 +41,39 @@ enum ChmodMode {
 ///
 /// Unlike the POSIX `chmod`, `jj file chmod` also works on Windows, on
 /// conflicted files, and on arbitrary revisions.
+///
+/// Symlinks and Git submodules don't have the executable bit, so they're
+/// skipped with a warning.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileChmodArgs {
-    mode: ChmodMode,
-    /// The revision to update
-    #[arg(long, short, default_value = "@")]
-    revision: RevisionArg,
+    /// The mode to set
+    #[arg(value_enum, required_unless_present = "clear_executable_paths")]
+    mode: Option<ChmodMode>,
+    /// Make the given paths non-executable, same as the `n` mode
+    #[arg(
+        short = 'x',
+        hide = true,
+        num_args = 1..,
+        value_name = "PATHS",
+        conflicts_with_all = ["mode", "paths"],
+        value_hint = clap::ValueHint::AnyPath
+    )]
+    clear_executable_paths: Option<Vec<String>>,
+    /// The revisions to update
+    ///
+    /// Descendants of the updated revisions are rebased.
+    #[arg(
+        long = "revision",
+        short,
+        default_value = "@",
+        value_name = "REVISIONS"
+    )]
+    revisions: Vec<RevisionArg>,
     /// Paths to change the executable bit for
-    #[arg(required = true, value_hint = clap::ValueHint::AnyPath)]
+    #[arg(
+        required_unless_present = "clear_executable_paths",
+        value_hint = clap::ValueHint::AnyPath
+    )]
     paths: Vec<String>,
 }
 
@@ -70,72 +100,102 @@ pub(crate) fn cmd_file_chmod(
     command: &CommandHelper,
     args: &FileChmodArgs,
 ) -> Result<(), CommandError> {
-    let executable_bit = match args.mode {
+    // clap ensures that either the mode or `-x` is specified, but not both.
+    let mode = args.mode.unwrap_or(ChmodMode::Normal);
+    let paths = args.clear_executable_paths.as_ref().unwrap_or(&args.paths);
+    let executable_bit = match mode {
         ChmodMode::Executable => true,
         ChmodMode::Normal => false,
     };
 
     let mut workspace_command = command.workspace_helper(ui)?;
-    let commit = workspace_command.resolve_single_rev(&args.revision)?;
-    workspace_command.check_rewritable([commit.id()])?;
-    let tree = commit.tree()?;
+    let commits: Vec<_> = workspace_command
+        .parse_union_revsets(&args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    workspace_command.check_rewritable(commits.iter().ids())?;
+    let trees: Vec<_> = commits.iter().map(|commit| commit.tree()).try_collect()?;
     // TODO: No need to add special case for empty paths when switching to
     // parse_union_filesets(). paths = [] should be "none()" if supported.
-    let fileset_expression = workspace_command.parse_file_patterns(&args.paths)?;
+    let fileset_expression = workspace_command.parse_file_patterns(paths)?;
     let matcher = fileset_expression.to_matcher();
-    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, &trees)?;
 
     let mut tx = workspace_command.start_transaction();
-    let store = tree.store();
-    let mut tree_builder = MergedTreeBuilder::new(commit.tree_id().clone());
-    for (repo_path, result) in tree.entries_matching(matcher.as_ref()) {
-        let tree_value = result?;
-        let user_error_with_path = |msg: &str| {
-            user_error(format!(
-                "{msg} at '{}'.",
-                tx.base_workspace_helper().format_file_path(&repo_path)
-            ))
-        };
-        let all_files = tree_value
-            .adds()
-            .flatten()
-            .all(|tree_value| matches!(tree_value, TreeValue::File { .. }));
-        if !all_files {
-            let message = if tree_value.is_resolved() {
-                "Found neither a file nor a conflict"
+    let mut new_tree_ids = HashMap::new();
+    let mut skipped_paths = BTreeSet::new();
+    for (commit, tree) in iter::zip(&commits, &trees) {
+        let mut tree_builder = MergedTreeBuilder::new(commit.tree_id().clone());
+        for (repo_path, result) in tree.entries_matching(matcher.as_ref()) {
+            let tree_value = result?;
+            if tree_value.is_resolved() {
+                if !matches!(tree_value.as_normal(), Some(TreeValue::File { .. })) {
+                    skipped_paths.insert(repo_path);
+                    continue;
+                }
             } else {
-                "Some of the sides of the conflict are not files"
-            };
-            return Err(user_error_with_path(message));
-        }
-        let new_tree_value = tree_value.map(|value| match value {
-            Some(TreeValue::File { id, executable: _ }) => Some(TreeValue::File {
-                id: id.clone(),
-                executable: executable_bit,
-            }),
-            Some(TreeValue::Conflict(_)) => {
-                panic!("Conflict sides must not themselves be conflicts")
+                let all_files = tree_value
+                    .adds()
+                    .flatten()
+                    .all(|tree_value| matches!(tree_value, TreeValue::File { .. }));
+                if !all_files {
+                    return Err(user_error(format!(
+                        "Some of the sides of the conflict are not files at '{}'.",
+                        tx.base_workspace_helper().format_file_path(&repo_path)
+                    )));
+                }
             }
-            value => value.clone(),
-        });
-        tree_builder.set_or_remove(repo_path, new_tree_value);
+            let new_tree_value = tree_value.map(|value| match value {
+                Some(TreeValue::File { id, executable: _ }) => Some(TreeValue::File {
+                    id: id.clone(),
+                    executable: executable_bit,
+                }),
+                Some(TreeValue::Conflict(_)) => {
+                    panic!("Conflict sides must not themselves be conflicts")
+                }
+                value => value.clone(),
+            });
+            tree_builder.set_or_remove(repo_path, new_tree_value);
+        }
+        let new_tree_id = tree_builder.write_tree(tree.store())?;
+        new_tree_ids.insert(commit.id().clone(), new_tree_id);
+    }
+    for repo_path in &skipped_paths {
+        writeln!(
+            ui.warning_default(),
+            "Skipping '{}', which is neither a file nor a conflict",
+            tx.base_workspace_helper().format_file_path(repo_path)
+        )?;
     }
 
-    let new_tree_id = tree_builder.write_tree(store)?;
-    tx.mut_repo()
-        .rewrite_commit(command.settings(), &commit)
-        .set_tree_id(new_tree_id)
-        .write()?;
+    tx.mut_repo().transform_descendants(
+        command.settings(),
+        commits.iter().ids().cloned().collect(),
+        |rewriter| {
+            let Some(new_tree_id) = new_tree_ids.get(rewriter.old_commit().id()) else {
+                return Ok(());
+            };
+            rewriter
+                .reparent(command.settings())?
+                .set_tree_id(new_tree_id.clone())
+                .write()?;
+            Ok(())
+        },
+    )?;
+    let description = if let [commit] = &*commits {
+        format!("commit {}", commit.id().hex())
+    } else {
+        format!("{} commits", commits.len())
+    };
     tx.finish(
         ui,
         format!(
-            "make paths {} in commit {}",
+            "make paths {} in {description}",
             if executable_bit {
                 "executable"
             } else {
                 "non-executable"
             },
-            commit.id().hex(),
         ),
     )
 }
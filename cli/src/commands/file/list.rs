@@ -12,20 +12,47 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeSet;
 use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
 
+use itertools::Itertools as _;
+use jj_lib::gitignore::GitIgnoreFile;
+use jj_lib::matchers::{Matcher, PrefixMatcher};
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::{RepoPath, RepoPathBuf, RepoPathComponent};
 use tracing::instrument;
 
-use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::CommandError;
+use crate::cli_util::{CommandHelper, RevisionArg, WorkspaceCommandHelper};
+use crate::command_error::{user_error, user_error_with_message, CommandError};
 use crate::ui::Ui;
 
 /// List files in a revision
+///
+/// With `--ignored`, lists files in the working copy which are excluded by
+/// ignore rules instead. Files in an ignored directory are listed as the
+/// directory unless some of them are tracked. Tracked files which match ignore
+/// rules, and untracked files outside the sparse patterns, are listed in
+/// separate sections.
+///
+/// With `--modified-in`, lists files changed by any of the given revisions
+/// instead.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileListArgs {
     /// The revision to list files in
-    #[arg(long, short, default_value = "@")]
+    #[arg(long, short, default_value = "@", conflicts_with = "ignored")]
     revision: RevisionArg,
+    /// List ignored files in the working copy
+    #[arg(long)]
+    ignored: bool,
+    /// List files changed by any of these revisions compared to their parents
+    #[arg(
+        long,
+        value_name = "REVISIONS",
+        conflicts_with_all = ["revision", "ignored"]
+    )]
+    modified_in: Vec<RevisionArg>,
     /// Only list files matching these prefixes (instead of all files)
     #[arg(value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
@@ -55,6 +82,12 @@ pub(crate) fn cmd_file_list(
     args: &FileListArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
+    if args.ignored {
+        return list_ignored_files(ui, &workspace_command, &args.paths);
+    }
+    if !args.modified_in.is_empty() {
+        return list_modified_files(ui, &workspace_command, &args.modified_in, &args.paths);
+    }
     let commit = workspace_command.resolve_single_rev(&args.revision)?;
     let tree = commit.tree()?;
     let matcher = workspace_command
@@ -70,3 +103,193 @@ pub(crate) fn cmd_file_list(
     }
     Ok(())
 }
+
+fn list_modified_files(
+    ui: &mut Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    revision_args: &[RevisionArg],
+    paths: &[String],
+) -> Result<(), CommandError> {
+    let repo = workspace_command.repo();
+    let matcher = workspace_command.parse_file_patterns(paths)?.to_matcher();
+    let mut modified_paths = BTreeSet::new();
+    for commit in workspace_command
+        .parse_union_revsets(revision_args)?
+        .evaluate_to_commits()?
+    {
+        let commit = commit?;
+        let parent_tree = commit.parent_tree(repo.as_ref())?;
+        for (path, diff) in parent_tree.diff(&commit.tree()?, matcher.as_ref()) {
+            diff?;
+            modified_paths.insert(path);
+        }
+    }
+    ui.request_pager();
+    for path in &modified_paths {
+        writeln!(ui.stdout(), "{}", workspace_command.format_file_path(path))?;
+    }
+    Ok(())
+}
+
+fn list_ignored_files(
+    ui: &mut Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    paths: &[String],
+) -> Result<(), CommandError> {
+    let wc_commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let wc_commit = workspace_command.repo().store().get_commit(wc_commit_id)?;
+    let tracked_paths: BTreeSet<RepoPathBuf> =
+        wc_commit.tree()?.entries().map(|(path, _)| path).collect();
+    let mut collector = IgnoredFilesCollector {
+        tracked_paths: &tracked_paths,
+        sparse_matcher: PrefixMatcher::new(workspace_command.working_copy().sparse_patterns()?),
+        ignored: vec![],
+        tracked_ignored: vec![],
+        outside_sparse: vec![],
+    };
+    walk_working_copy_dir(
+        workspace_command.workspace_root(),
+        RepoPath::root(),
+        Some(workspace_command.base_ignores()?),
+        false,
+        &mut |path, is_dir, ignored| Ok(collector.visit_entry(path, is_dir, ignored)),
+    )?;
+
+    let matcher = workspace_command.parse_file_patterns(paths)?.to_matcher();
+    let sections = [
+        ("Ignored files:", &collector.ignored),
+        (
+            "Tracked files matching ignore rules:",
+            &collector.tracked_ignored,
+        ),
+        (
+            "Untracked files outside sparse patterns:",
+            &collector.outside_sparse,
+        ),
+    ];
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (header, entries) in sections {
+        let entries = entries
+            .iter()
+            .filter(|(path, is_dir)| {
+                matcher.matches(path) || *is_dir && !matcher.visit(path).is_nothing()
+            })
+            .collect_vec();
+        if entries.is_empty() {
+            continue;
+        }
+        writeln!(formatter.labeled("header"), "{header}")?;
+        for (path, is_dir) in entries {
+            let ui_path = workspace_command.format_file_path(path);
+            let suffix = if *is_dir {
+                std::path::MAIN_SEPARATOR_STR
+            } else {
+                ""
+            };
+            writeln!(formatter, "{ui_path}{suffix}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Classifies files in the working copy which weren't snapshotted because of
+/// ignore rules or sparse patterns.
+struct IgnoredFilesCollector<'a> {
+    tracked_paths: &'a BTreeSet<RepoPathBuf>,
+    sparse_matcher: PrefixMatcher,
+    /// Untracked ignored files, or directories without tracked files.
+    ignored: Vec<(RepoPathBuf, bool)>,
+    tracked_ignored: Vec<(RepoPathBuf, bool)>,
+    outside_sparse: Vec<(RepoPathBuf, bool)>,
+}
+
+impl IgnoredFilesCollector<'_> {
+    fn has_tracked_paths_in(&self, dir: &RepoPath) -> bool {
+        self.tracked_paths
+            .range(dir.to_owned()..)
+            .next()
+            .is_some_and(|path| path.starts_with(dir))
+    }
+
+    /// Records the entry at `path`. Returns true if the directory should be
+    /// walked into.
+    fn visit_entry(&mut self, path: &RepoPath, is_dir: bool, ignored: bool) -> bool {
+        if is_dir {
+            if ignored && !self.has_tracked_paths_in(path) && self.sparse_matcher.matches(path) {
+                self.ignored.push((path.to_owned(), true));
+                false
+            } else {
+                true
+            }
+        } else {
+            if self.tracked_paths.contains(path) {
+                if ignored {
+                    self.tracked_ignored.push((path.to_owned(), false));
+                }
+            } else if !self.sparse_matcher.matches(path) {
+                self.outside_sparse.push((path.to_owned(), false));
+            } else if ignored {
+                self.ignored.push((path.to_owned(), false));
+            }
+            false
+        }
+    }
+}
+
+/// Walks the working-copy directory `dir` recursively in file name order,
+/// skipping `.jj` and `.git` directories.
+///
+/// The `visit` function is called with the path of each entry, whether it's a
+/// directory, and whether it's ignored by the `git_ignore` rules (chained with
+/// the `.gitignore` files found on the way) or by an ignored parent
+/// directory. A directory is walked into only if `visit` returns true.
+pub(crate) fn walk_working_copy_dir(
+    workspace_root: &Path,
+    dir: &RepoPath,
+    git_ignore: Option<Arc<GitIgnoreFile>>,
+    dir_ignored: bool,
+    visit: &mut dyn FnMut(&RepoPath, bool, bool) -> Result<bool, CommandError>,
+) -> Result<(), CommandError> {
+    let disk_dir = dir.to_fs_path(workspace_root);
+    let git_ignore = git_ignore
+        .map(|git_ignore| {
+            git_ignore.chain_with_file(&dir.to_internal_dir_string(), disk_dir.join(".gitignore"))
+        })
+        .transpose()?;
+    let read_dir_error = |err| {
+        user_error_with_message(
+            format!("Failed to read directory {}", disk_dir.display()),
+            err,
+        )
+    };
+    let entries = disk_dir
+        .read_dir()
+        .and_then(|entries| entries.try_collect::<_, Vec<_>, _>())
+        .map_err(read_dir_error)?;
+    for entry in entries.iter().sorted_by_key(|entry| entry.file_name()) {
+        let file_name = entry.file_name();
+        let name = file_name
+            .to_str()
+            .ok_or_else(|| user_error(format!("Invalid UTF-8 path: {}", entry.path().display())))?;
+        if name == ".jj" || name == ".git" {
+            continue;
+        }
+        let path = dir.join(RepoPathComponent::new(name));
+        let is_dir = entry.file_type().map_err(read_dir_error)?.is_dir();
+        let ignored = dir_ignored
+            || git_ignore.as_ref().is_some_and(|git_ignore| {
+                if is_dir {
+                    git_ignore.matches(&path.to_internal_dir_string())
+                } else {
+                    git_ignore.matches(path.as_internal_file_string())
+                }
+            });
+        if visit(&path, is_dir, ignored)? && is_dir {
+            walk_working_copy_dir(workspace_root, &path, git_ignore.clone(), ignored, visit)?;
+        }
+    }
+    Ok(())
+}
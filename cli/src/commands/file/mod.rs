@@ -14,6 +14,7 @@
 
 pub mod chmod;
 pub mod list;
+pub mod r#move;
 pub mod show;
 
 use crate::cli_util::CommandHelper;
@@ -25,6 +26,7 @@ use crate::ui::Ui;
 pub enum FileCommand {
     Chmod(chmod::FileChmodArgs),
     List(list::FileListArgs),
+    Move(r#move::FileMoveArgs),
     Show(show::FileShowArgs),
 }
 
@@ -36,6 +38,7 @@ pub fn cmd_file(
     match subcommand {
         FileCommand::Chmod(args) => chmod::cmd_file_chmod(ui, command, args),
         FileCommand::List(args) => list::cmd_file_list(ui, command, args),
+        FileCommand::Move(args) => r#move::cmd_file_move(ui, command, args),
         FileCommand::Show(args) => show::cmd_file_show(ui, command, args),
     }
 }
@@ -0,0 +1,230 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::iter;
+
+use itertools::Itertools as _;
+use jj_lib::backend::{BackendError, MergedTreeId};
+use jj_lib::commit::{Commit, CommitIteratorExt as _};
+use jj_lib::matchers::PrefixMatcher;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::MergedTreeBuilder;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo_path::{RepoPath, RepoPathBuf};
+use tracing::instrument;
+
+use crate::cli_util::{short_commit_hash, CommandHelper, RevisionArg};
+use crate::command_error::{user_error, user_error_with_hint, CommandError};
+use crate::ui::Ui;
+
+/// Move or rename a file or directory in the repo
+///
+/// The path is moved in each of the given revisions, and descendants of the
+/// revisions are rebased. If the source is a directory, all the files in it
+/// are moved.
+///
+/// The move is recorded as a deletion and an addition. Use `jj diff --copies`
+/// to show it as a rename.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct FileMoveArgs {
+    /// The revisions to update
+    #[arg(
+        long = "revision",
+        short,
+        default_value = "@",
+        value_name = "REVISIONS"
+    )]
+    revisions: Vec<RevisionArg>,
+    /// Overwrite the destination file, or files at its parent paths, if they
+    /// already exist
+    ///
+    /// An existing directory is never overwritten.
+    #[arg(long)]
+    force: bool,
+    /// The path to move
+    #[arg(value_hint = clap::ValueHint::AnyPath)]
+    source: String,
+    /// The new path
+    #[arg(value_hint = clap::ValueHint::AnyPath)]
+    destination: String,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_file_move(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FileMoveArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let source = workspace_command.parse_file_path(&args.source)?;
+    let destination = workspace_command.parse_file_path(&args.destination)?;
+    if source.is_root() || destination.is_root() {
+        return Err(user_error("Cannot move the root of the repo"));
+    }
+    if destination.starts_with(&source) {
+        return Err(user_error(format!(
+            "Cannot move '{}' into itself",
+            workspace_command.format_file_path(&source)
+        )));
+    }
+    let commits: Vec<_> = workspace_command
+        .parse_union_revsets(&args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    workspace_command.check_rewritable(commits.iter().ids())?;
+
+    let mut tx = workspace_command.start_transaction();
+    let mut new_tree_ids = HashMap::new();
+    for commit in &commits {
+        let new_tree_id = match move_path_in_commit(commit, &source, &destination, args.force) {
+            Ok(tree_id) => tree_id,
+            Err(MovePathError::SourceNotFound) => {
+                return Err(user_error(format!(
+                    "No such path '{}' in commit {}",
+                    tx.base_workspace_helper().format_file_path(&source),
+                    short_commit_hash(commit.id()),
+                )));
+            }
+            Err(MovePathError::DestinationExists) => {
+                return Err(user_error_with_hint(
+                    format!(
+                        "Destination '{}' already exists in commit {}",
+                        tx.base_workspace_helper().format_file_path(&destination),
+                        short_commit_hash(commit.id()),
+                    ),
+                    "Use --force to overwrite it.",
+                ));
+            }
+            Err(MovePathError::DestinationIsDirectory) => {
+                return Err(user_error(format!(
+                    "Destination '{}' is a directory in commit {}",
+                    tx.base_workspace_helper().format_file_path(&destination),
+                    short_commit_hash(commit.id()),
+                )));
+            }
+            Err(MovePathError::DestinationAncestorIsFile(path)) => {
+                return Err(user_error_with_hint(
+                    format!(
+                        "Destination parent '{}' is a file in commit {}",
+                        tx.base_workspace_helper().format_file_path(&path),
+                        short_commit_hash(commit.id()),
+                    ),
+                    "Use --force to replace it with a directory.",
+                ));
+            }
+            Err(MovePathError::Backend(err)) => return Err(err.into()),
+        };
+        new_tree_ids.insert(commit.id().clone(), new_tree_id);
+    }
+
+    tx.mut_repo().transform_descendants(
+        command.settings(),
+        commits.iter().ids().cloned().collect(),
+        |rewriter| {
+            let Some(new_tree_id) = new_tree_ids.get(rewriter.old_commit().id()) else {
+                return Ok(());
+            };
+            rewriter
+                .reparent(command.settings())?
+                .set_tree_id(new_tree_id.clone())
+                .write()?;
+            Ok(())
+        },
+    )?;
+    let description = if let [commit] = &*commits {
+        format!("commit {}", commit.id().hex())
+    } else {
+        format!("{} commits", commits.len())
+    };
+    tx.finish(
+        ui,
+        format!(
+            "move {} to {} in {description}",
+            source.as_internal_file_string(),
+            destination.as_internal_file_string(),
+        ),
+    )
+}
+
+enum MovePathError {
+    SourceNotFound,
+    DestinationExists,
+    DestinationIsDirectory,
+    DestinationAncestorIsFile(RepoPathBuf),
+    Backend(BackendError),
+}
+
+impl From<BackendError> for MovePathError {
+    fn from(err: BackendError) -> Self {
+        MovePathError::Backend(err)
+    }
+}
+
+/// Moves the entries at or under `source` to `destination`, and returns the
+/// new tree id.
+fn move_path_in_commit(
+    commit: &Commit,
+    source: &RepoPath,
+    destination: &RepoPath,
+    force: bool,
+) -> Result<MergedTreeId, MovePathError> {
+    let tree = commit.tree()?;
+    let source_entries: Vec<_> = tree
+        .entries_matching(&PrefixMatcher::new([source]))
+        .map(|(path, result)| result.map(|value| (path, value)))
+        .try_collect()?;
+    if source_entries.is_empty() {
+        return Err(MovePathError::SourceNotFound);
+    }
+    let destination_value = tree.path_value(destination)?;
+    if destination_value.is_tree() {
+        return Err(MovePathError::DestinationIsDirectory);
+    }
+    if destination_value.is_present() && !force {
+        return Err(MovePathError::DestinationExists);
+    }
+    // A file at any ancestor of the destination would be replaced by a
+    // directory.
+    let mut ancestor_files = vec![];
+    for dir in iter::successors(destination.parent(), |dir| dir.parent()) {
+        if dir.is_root() {
+            break;
+        }
+        let value = tree.path_value(dir)?;
+        if value.is_present() && !value.is_tree() {
+            if !force {
+                return Err(MovePathError::DestinationAncestorIsFile(dir.to_owned()));
+            }
+            ancestor_files.push(dir.to_owned());
+        }
+    }
+
+    let mut tree_builder = MergedTreeBuilder::new(commit.tree_id().clone());
+    if destination_value.is_present() {
+        tree_builder.set_or_remove(destination.to_owned(), Merge::absent());
+    }
+    for path in ancestor_files {
+        tree_builder.set_or_remove(path, Merge::absent());
+    }
+    for (path, value) in source_entries {
+        let relative_path = path.strip_prefix(source).unwrap();
+        let new_path = relative_path
+            .components()
+            .fold(destination.to_owned(), |dir, name| dir.join(name));
+        tree_builder.set_or_remove(path, Merge::absent());
+        tree_builder.set_or_remove(new_path, value);
+    }
+    Ok(tree_builder.write_tree(tree.store())?)
+}
@@ -12,10 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::{self, Write};
+use std::{io, iter};
 
+use itertools::Itertools as _;
 use jj_lib::backend::BackendResult;
-use jj_lib::conflicts::{materialize_tree_value, MaterializedTreeValue};
+use jj_lib::conflicts::{is_conflict_marker_line, materialize_tree_value, MaterializedTreeValue};
 use jj_lib::fileset::{FilePattern, FilesetExpression};
 use jj_lib::merge::MergedTreeValue;
 use jj_lib::repo::Repo;
@@ -24,7 +25,8 @@ use pollster::FutureExt;
 use tracing::instrument;
 
 use crate::cli_util::{
-    print_unmatched_explicit_paths, CommandHelper, RevisionArg, WorkspaceCommandHelper,
+    print_unmatched_explicit_paths, short_commit_hash, CommandHelper, RevisionArg,
+    WorkspaceCommandHelper,
 };
 use crate::command_error::{user_error, CommandError};
 use crate::ui::Ui;
@@ -33,11 +35,14 @@ use crate::ui::Ui;
 ///
 /// If the given path is a directory, files in the directory will be visited
 /// recursively.
+///
+/// If multiple revisions are specified, the files are printed for each
+/// revision in order, preceded by a header line identifying the revision.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileShowArgs {
-    /// The revision to get the file contents from
+    /// The revision(s) to get the file contents from
     #[arg(long, short, default_value = "@")]
-    revision: RevisionArg,
+    revision: Vec<RevisionArg>,
     /// Paths to print
     #[arg(required = true, value_hint = clap::ValueHint::FilePath)]
     paths: Vec<String>,
@@ -67,34 +72,72 @@ pub(crate) fn cmd_file_show(
     args: &FileShowArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
-    let commit = workspace_command.resolve_single_rev(&args.revision)?;
-    let tree = commit.tree()?;
+    let commits: Vec<_> = args
+        .revision
+        .iter()
+        .map(|revision| workspace_command.resolve_single_rev(revision))
+        .try_collect()?;
     // TODO: No need to add special case for empty paths when switching to
     // parse_union_filesets(). paths = [] should be "none()" if supported.
     let fileset_expression = workspace_command.parse_file_patterns(&args.paths)?;
-
-    // Try fast path for single file entry
-    if let Some(path) = get_single_path(&fileset_expression) {
-        let value = tree.path_value(path)?;
-        if value.is_absent() {
-            let ui_path = workspace_command.format_file_path(path);
-            return Err(user_error(format!("No such path: {ui_path}")));
-        }
-        if !value.is_tree() {
-            ui.request_pager();
-            write_tree_entries(ui, &workspace_command, [(path, Ok(value))])?;
-            return Ok(());
+    if let [commit] = &commits[..] {
+        let tree = commit.tree()?;
+        // Try fast path for single file entry
+        if let Some(path) = get_single_path(&fileset_expression) {
+            let value = tree.path_value(path)?;
+            if value.is_absent() {
+                let ui_path = workspace_command.format_file_path(path);
+                return Err(user_error(format!("No such path: {ui_path}")));
+            }
+            if !value.is_tree() {
+                ui.request_pager();
+                write_tree_entries(ui, &workspace_command, [(path, Ok(value))])?;
+                return Ok(());
+            }
         }
+
+        let matcher = fileset_expression.to_matcher();
+        ui.request_pager();
+        write_tree_entries(
+            ui,
+            &workspace_command,
+            tree.entries_matching(matcher.as_ref()),
+        )?;
+        print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+        return Ok(());
     }
 
     let matcher = fileset_expression.to_matcher();
+    let trees: Vec<_> = commits.iter().map(|commit| commit.tree()).try_collect()?;
     ui.request_pager();
-    write_tree_entries(
-        ui,
-        &workspace_command,
-        tree.entries_matching(matcher.as_ref()),
-    )?;
-    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+    for (commit, tree) in iter::zip(&commits, &trees) {
+        {
+            let mut formatter = ui.stdout_formatter();
+            write!(formatter, "Revision ")?;
+            workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter, ":")?;
+        }
+        // A file absent in this revision should be distinguishable from an
+        // empty file.
+        if let Some(path) = get_single_path(&fileset_expression) {
+            let value = tree.path_value(path)?;
+            if value.is_absent() {
+                let ui_path = workspace_command.format_file_path(path);
+                writeln!(
+                    ui.warning_default(),
+                    "No such path in revision {}: {ui_path}",
+                    short_commit_hash(commit.id())
+                )?;
+                continue;
+            }
+        }
+        write_tree_entries(
+            ui,
+            &workspace_command,
+            tree.entries_matching(matcher.as_ref()),
+        )?;
+    }
+    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, &trees)?;
     Ok(())
 }
 
@@ -132,7 +175,16 @@ fn write_tree_entries<P: AsRef<RepoPath>>(
                 io::copy(&mut reader, &mut ui.stdout_formatter().as_mut())?;
             }
             MaterializedTreeValue::Conflict { contents, .. } => {
-                ui.stdout_formatter().write_all(&contents)?;
+                let mut formatter = ui.stdout_formatter();
+                let formatter = formatter.as_mut();
+                for line in contents.split_inclusive(|b| *b == b'\n') {
+                    if is_conflict_marker_line(line) {
+                        formatter
+                            .with_label("conflict_marker", |formatter| formatter.write_all(line))?;
+                    } else {
+                        formatter.write_all(line)?;
+                    }
+                }
             }
             MaterializedTreeValue::Symlink { .. } | MaterializedTreeValue::GitSubmodule(_) => {
                 let ui_path = workspace_command.format_file_path(path.as_ref());
@@ -169,10 +169,19 @@ fn do_git_clone(
     source: &str,
     wc_path: &Path,
 ) -> Result<(WorkspaceCommandHelper, GitFetchStats), CommandError> {
+    let working_copy_factory = command.get_new_working_copy_factory()?;
     let (workspace, repo) = if colocate {
-        Workspace::init_colocated_git(command.settings(), wc_path)?
+        Workspace::init_colocated_git_with_working_copy_factory(
+            command.settings(),
+            wc_path,
+            working_copy_factory,
+        )?
     } else {
-        Workspace::init_internal_git(command.settings(), wc_path)?
+        Workspace::init_internal_git_with_working_copy_factory(
+            command.settings(),
+            wc_path,
+            working_copy_factory,
+        )?
     };
     let git_repo = get_git_repo(repo.store())?;
     writeln!(
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jj_lib::git;
+use jj_lib::git::{self, RefName};
+use jj_lib::str_util::StringPattern;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -21,17 +22,53 @@ use crate::ui::Ui;
 
 /// Update the underlying Git repo with changes made in the repo
 #[derive(clap::Args, Clone, Debug)]
-pub struct GitExportArgs {}
+pub struct GitExportArgs {
+    /// Export only the branches matching the given name pattern
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select branches by wildcard pattern. For details, see
+    /// https://github.com/martinvonz/jj/blob/main/docs/revsets.md#string-patterns.
+    ///
+    /// Remote-tracking branches are exported regardless of this option. In
+    /// colocated repos, all branches will be exported again by the next command
+    /// that modifies the repo.
+    #[arg(long, short, value_parser = StringPattern::parse)]
+    branch: Vec<StringPattern>,
+
+    /// Export all branches (default)
+    #[arg(long, conflicts_with = "branch")]
+    all: bool,
+
+    /// Delete previously-exported Git refs of the branches not selected by
+    /// `--branch`
+    #[arg(long, requires = "branch")]
+    prune: bool,
+}
 
 pub fn cmd_git_export(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &GitExportArgs,
+    args: &GitExportArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let mut tx = workspace_command.start_transaction();
-    let failed_branches = git::export_refs(tx.mut_repo())?;
-    tx.finish(ui, "export git refs")?;
+    let failed_branches = if args.branch.is_empty() {
+        git::export_refs(tx.mut_repo())?
+    } else {
+        let git_ref_filter = |ref_name: &RefName| match ref_name {
+            RefName::LocalBranch(branch) => {
+                args.branch.iter().any(|pattern| pattern.matches(branch))
+            }
+            RefName::RemoteBranch { .. } | RefName::Tag(_) => true,
+        };
+        if args.prune {
+            git::export_some_refs_and_prune_others(tx.mut_repo(), git_ref_filter)?
+        } else {
+            git::export_some_refs(tx.mut_repo(), git_ref_filter)?
+        }
+    };
+    // Don't let the automatic export in colocated repos undo the selection.
+    tx.finish_without_git_export(ui, "export git refs")?;
     print_failed_git_export(ui, &failed_branches)?;
     Ok(())
 }
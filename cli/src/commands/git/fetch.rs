@@ -28,6 +28,10 @@ use crate::ui::Ui;
 ///
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
+///
+/// When fetching from multiple remotes, a remote that can't be fetched from
+/// doesn't prevent fetching from the others. The failures are reported at the
+/// end, and the command exits with an error.
 #[derive(clap::Args, Clone, Debug)]
 pub struct GitFetchArgs {
     /// Fetch only some of the branches
@@ -38,8 +42,12 @@ pub struct GitFetchArgs {
     branch: Vec<StringPattern>,
     /// The remote to fetch from (only named remotes are supported, can be
     /// repeated)
-    #[arg(long = "remote", value_name = "remote")]
-    remotes: Vec<String>,
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select remotes by wildcard pattern. For details, see
+    /// https://martinvonz.github.io/jj/latest/revsets#string-patterns.
+    #[arg(long = "remote", value_name = "remote", value_parser = StringPattern::parse)]
+    remotes: Vec<StringPattern>,
     /// Fetch from all remotes
     #[arg(long, conflicts_with = "remotes")]
     all_remotes: bool,
@@ -58,11 +66,12 @@ pub fn cmd_git_fetch(
     } else if args.remotes.is_empty() {
         get_default_fetch_remotes(ui, command.settings(), &git_repo)?
     } else {
-        args.remotes.clone()
+        expand_remote_patterns(&git_repo, &args.remotes)?
     };
     let mut tx = workspace_command.start_transaction();
+    let mut failed_remotes = vec![];
     for remote in &remotes {
-        let stats = with_remote_git_callbacks(ui, None, |cb| {
+        let result = with_remote_git_callbacks(ui, None, |cb| {
             git::fetch(
                 tx.mut_repo(),
                 &git_repo,
@@ -71,7 +80,21 @@ pub fn cmd_git_fetch(
                 cb,
                 &command.settings().git_settings(),
             )
-        })
+        });
+        let stats = match result {
+            // Transport errors are specific to the remote. Other errors would
+            // apply to all remotes, or leave the repo in a bad state.
+            Err(GitFetchError::InternalGitError(err)) if remotes.len() > 1 => {
+                writeln!(
+                    ui.warning_default(),
+                    "Failed to fetch from remote {remote}: {}",
+                    err.message()
+                )?;
+                failed_remotes.push(remote.as_str());
+                continue;
+            }
+            result => result,
+        }
         .map_err(|err| match err {
             GitFetchError::InvalidBranchPattern => {
                 if args
@@ -97,7 +120,45 @@ pub fn cmd_git_fetch(
         ui,
         format!("fetch from git remote(s) {}", remotes.iter().join(",")),
     )?;
-    Ok(())
+    if failed_remotes.is_empty() {
+        Ok(())
+    } else {
+        Err(user_error(format!(
+            "Failed to fetch from remote(s): {}",
+            failed_remotes.join(", ")
+        )))
+    }
+}
+
+/// Expands remote name patterns to the names of existing remotes. Exact
+/// names are passed through so that unknown remotes are reported by fetch.
+fn expand_remote_patterns(
+    git_repo: &git2::Repository,
+    patterns: &[StringPattern],
+) -> Result<Vec<String>, CommandError> {
+    let mut all_remotes = None;
+    let mut remotes = vec![];
+    for pattern in patterns {
+        if let Some(name) = pattern.as_exact() {
+            remotes.push(name.to_owned());
+            continue;
+        }
+        let all_remotes = match &mut all_remotes {
+            Some(all_remotes) => all_remotes,
+            None => all_remotes.insert(get_all_remotes(git_repo)?),
+        };
+        let mut matches = all_remotes
+            .iter()
+            .filter(|name| pattern.matches(name))
+            .peekable();
+        if matches.peek().is_none() {
+            return Err(user_error(format!(
+                "No matching remotes for pattern: {pattern}"
+            )));
+        }
+        remotes.extend(matches.cloned());
+    }
+    Ok(remotes.into_iter().unique().collect())
 }
 
 const DEFAULT_REMOTE: &str = "origin";
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jj_lib::git;
+use itertools::Itertools as _;
+use jj_lib::git::{self, GitImportError};
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -24,20 +25,48 @@ use crate::ui::Ui;
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
 #[derive(clap::Args, Clone, Debug)]
-pub struct GitImportArgs {}
+pub struct GitImportArgs {
+    /// Skip refs pointing to commits that can't be read instead of failing
+    ///
+    /// This is useful if the Git repo is partially corrupted, or is a partial
+    /// clone. The skipped refs will be retried by the next import.
+    #[arg(long)]
+    skip_missing_objects: bool,
+}
 
 pub fn cmd_git_import(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &GitImportArgs,
+    args: &GitImportArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let mut tx = workspace_command.start_transaction();
     // In non-colocated repo, HEAD@git will never be moved internally by jj.
     // That's why cmd_git_export() doesn't export the HEAD ref.
-    git::import_head(tx.mut_repo())?;
-    let stats = git::import_refs(tx.mut_repo(), &command.settings().git_settings())?;
+    match git::import_head(tx.mut_repo()) {
+        Err(err @ GitImportError::MissingHeadTarget { .. }) if args.skip_missing_objects => {
+            writeln!(ui.warning_default(), "Skipped importing Git HEAD: {err}")?;
+        }
+        result => result?,
+    }
+    let git_settings = command.settings().git_settings();
+    let stats = if args.skip_missing_objects {
+        git::import_refs_skipping_missing(tx.mut_repo(), &git_settings)?
+    } else {
+        git::import_refs(tx.mut_repo(), &git_settings)?
+    };
     print_git_import_stats(ui, tx.repo(), &stats, true)?;
+    if !stats.skipped_refs.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "Skipped importing refs with missing objects: {}",
+            stats.skipped_refs.iter().join(", ")
+        )?;
+        writeln!(
+            ui.hint_default(),
+            "The skipped refs will be imported again by the next `jj git import`."
+        )?;
+    }
     tx.finish(ui, "import git refs")?;
     Ok(())
 }
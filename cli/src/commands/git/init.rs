@@ -137,14 +137,21 @@ pub fn do_init(
 
     match &init_mode {
         GitInitMode::Colocate => {
-            let (workspace, repo) =
-                Workspace::init_colocated_git(command.settings(), workspace_root)?;
+            let (workspace, repo) = Workspace::init_colocated_git_with_working_copy_factory(
+                command.settings(),
+                workspace_root,
+                command.get_new_working_copy_factory()?,
+            )?;
             let workspace_command = command.for_loaded_repo(ui, workspace, repo)?;
             maybe_add_gitignore(&workspace_command)?;
         }
         GitInitMode::External(git_repo_path) => {
-            let (workspace, repo) =
-                Workspace::init_external_git(command.settings(), workspace_root, git_repo_path)?;
+            let (workspace, repo) = Workspace::init_external_git_with_working_copy_factory(
+                command.settings(),
+                workspace_root,
+                git_repo_path,
+                command.get_new_working_copy_factory()?,
+            )?;
             // Import refs first so all the reachable commits are indexed in
             // chronological order.
             let colocated = is_colocated_git_workspace(&workspace, &repo);
@@ -166,7 +173,11 @@ pub fn do_init(
             print_trackable_remote_branches(ui, workspace_command.repo().view())?;
         }
         GitInitMode::Internal => {
-            Workspace::init_internal_git(command.settings(), workspace_root)?;
+            Workspace::init_internal_git_with_working_copy_factory(
+                command.settings(),
+                workspace_root,
+                command.get_new_working_copy_factory()?,
+            )?;
         }
     }
     Ok(())
@@ -183,7 +194,12 @@ fn init_git_refs(
     repo: Arc<ReadonlyRepo>,
     colocated: bool,
 ) -> Result<Arc<ReadonlyRepo>, CommandError> {
-    let mut tx = start_repo_transaction(&repo, command.settings(), command.string_args());
+    let mut tx = start_repo_transaction(
+        &repo,
+        command.settings(),
+        command.string_args(),
+        &command.global_args().op_tag,
+    );
     // There should be no old refs to abandon, but enforce it.
     let mut git_settings = command.settings().git_settings();
     git_settings.abandon_unreachable_commits = false;
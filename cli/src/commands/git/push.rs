@@ -20,7 +20,7 @@ use clap::ArgGroup;
 use itertools::Itertools;
 use jj_lib::git::{self, GitBranchPushTargets, GitPushError};
 use jj_lib::object_id::ObjectId;
-use jj_lib::op_store::RefTarget;
+use jj_lib::op_store::{RefTarget, RemoteRef, RemoteRefState};
 use jj_lib::refs::{
     classify_branch_push_action, BranchPushAction, BranchPushUpdate, LocalAndRemoteRef,
 };
@@ -45,7 +45,8 @@ use crate::ui::Ui;
 /// By default, pushes any branches pointing to
 /// `remote_branches(remote=<remote>)..@`. Use `--branch` to push specific
 /// branches. Use `--all` to push all branches. Use `--change` to generate
-/// branch names based on the change IDs of specific commits.
+/// branch names based on the change IDs of specific commits. Use `--named` to
+/// push a commit to a remote branch without creating a local branch.
 ///
 /// Before the command actually moves, creates, or deletes a remote branch, it
 /// makes several [safety checks]. If there is a problem, you may need to run
@@ -59,7 +60,11 @@ use crate::ui::Ui;
 ///     https://martinvonz.github.io/jj/latest/branches/#conflicts
 
 #[derive(clap::Args, Clone, Debug)]
-#[command(group(ArgGroup::new("specific").args(&["branch", "change", "revisions"]).multiple(true)))]
+#[command(group(
+    ArgGroup::new("specific")
+        .args(&["branch", "change", "revisions", "named"])
+        .multiple(true)
+))]
 #[command(group(ArgGroup::new("what").args(&["all", "deleted", "tracked"]).conflicts_with("specific")))]
 pub struct GitPushArgs {
     /// The remote to push to (only named remotes are supported)
@@ -99,6 +104,14 @@ pub struct GitPushArgs {
     /// repeated)
     #[arg(long, short)]
     change: Vec<RevisionArg>,
+    /// Push a commit to the remote branch NAME without creating a local branch
+    /// (can be repeated)
+    ///
+    /// The REVISION must resolve to exactly one commit. The pushed branch is
+    /// recorded as a non-tracking remote branch, so it won't be deleted by
+    /// later pushes.
+    #[arg(long, value_name = "NAME=REVISION")]
+    named: Vec<String>,
     /// Only display what will change on the remote
     #[arg(long)]
     dry_run: bool,
@@ -138,6 +151,7 @@ pub fn cmd_git_push(
     let mut tx = workspace_command.start_transaction();
     let tx_description;
     let mut branch_updates = vec![];
+    let mut named_updates = vec![];
     if args.all {
         for (branch_name, targets) in repo.view().local_remote_branches(&remote) {
             match classify_branch_update(branch_name, &remote, targets) {
@@ -203,8 +217,26 @@ pub fn cmd_git_push(
             }
         }
 
-        let use_default_revset =
-            args.branch.is_empty() && args.change.is_empty() && args.revisions.is_empty();
+        for (name, update) in find_named_updates(tx.base_workspace_helper(), &remote, &args.named)?
+        {
+            if !seen_branches.insert(name) {
+                return Err(user_error(format!(
+                    "Branch {name} is specified more than once"
+                )));
+            }
+            match update {
+                Some(update) => named_updates.push((name.to_owned(), update)),
+                None => writeln!(
+                    ui.status(),
+                    "Branch {name}@{remote} already matches the specified revision",
+                )?,
+            }
+        }
+
+        let use_default_revset = args.branch.is_empty()
+            && args.change.is_empty()
+            && args.revisions.is_empty()
+            && args.named.is_empty();
         let branches_targeted = find_branches_targeted_by_revisions(
             ui,
             tx.base_workspace_helper(),
@@ -228,19 +260,20 @@ pub fn cmd_git_push(
             make_branch_term(
                 &branch_updates
                     .iter()
+                    .chain(&named_updates)
                     .map(|(branch, _)| branch.as_str())
                     .collect_vec()
             ),
             &remote
         );
     }
-    if branch_updates.is_empty() {
+    if branch_updates.is_empty() && named_updates.is_empty() {
         writeln!(ui.status(), "Nothing changed.")?;
         return Ok(());
     }
 
     let mut branch_push_direction = HashMap::new();
-    for (branch_name, update) in &branch_updates {
+    for (branch_name, update) in branch_updates.iter().chain(&named_updates) {
         let BranchPushUpdate {
             old_target: Some(old_target),
             new_target: Some(new_target),
@@ -265,6 +298,7 @@ pub fn cmd_git_push(
     // already been pushed.
     let new_heads = branch_updates
         .iter()
+        .chain(&named_updates)
         .filter_map(|(_, update)| update.new_target.clone())
         .collect_vec();
     let old_heads = repo
@@ -313,7 +347,7 @@ pub fn cmd_git_push(
     }
 
     writeln!(ui.status(), "Branch changes to push to {}:", &remote)?;
-    for (branch_name, update) in &branch_updates {
+    for (branch_name, update) in branch_updates.iter().chain(&named_updates) {
         match (&update.old_target, &update.new_target) {
             (Some(old_target), Some(new_target)) => {
                 let old = short_commit_hash(old_target);
@@ -361,7 +395,12 @@ pub fn cmd_git_push(
         return Ok(());
     }
 
-    let targets = GitBranchPushTargets { branch_updates };
+    let targets = GitBranchPushTargets {
+        branch_updates: branch_updates
+            .into_iter()
+            .chain(named_updates.iter().cloned())
+            .collect(),
+    };
     let mut writer = GitSidebandProgressMessageWriter::new(ui);
     let mut sideband_progress_callback = |progress_message: &[u8]| {
         _ = writer.write(ui, progress_message);
@@ -383,6 +422,16 @@ pub fn cmd_git_push(
         _ => user_error(err),
     })?;
     writer.flush(ui)?;
+    // Unlike pushed local branches, named branches are recorded as
+    // non-tracking so the absent local branches won't be deemed deleted.
+    for (name, update) in &named_updates {
+        let new_remote_ref = RemoteRef {
+            target: RefTarget::resolved(update.new_target.clone()),
+            state: RemoteRefState::New,
+        };
+        tx.mut_repo()
+            .set_remote_branch(name, &remote, new_remote_ref);
+    }
     tx.finish(ui, tx_description)?;
     Ok(())
 }
@@ -538,6 +587,49 @@ fn find_branches_to_push<'a>(
     }
 }
 
+/// Resolves `NAME=REVISION` arguments to updates of the remote branches.
+/// Returns `None` update if the remote branch already points to the revision.
+fn find_named_updates<'a>(
+    workspace_command: &WorkspaceCommandHelper,
+    remote_name: &str,
+    named_args: &'a [String],
+) -> Result<Vec<(&'a str, Option<BranchPushUpdate>)>, CommandError> {
+    let view = workspace_command.repo().view();
+    let mut updates = vec![];
+    for arg in named_args {
+        let Some((name, revision)) = arg.split_once('=').filter(|(name, _)| !name.is_empty())
+        else {
+            return Err(user_error_with_hint(
+                format!("Invalid named push target: {arg}"),
+                "Specify the target as NAME=REVISION, e.g. `--named ci-1=@-`.",
+            ));
+        };
+        if view.get_local_branch(name).is_present() {
+            return Err(user_error_with_hint(
+                format!("Branch {name} already exists locally"),
+                format!("Use `jj git push --branch {name}` to push the local branch."),
+            ));
+        }
+        let commit =
+            workspace_command.resolve_single_rev(&RevisionArg::from(revision.to_owned()))?;
+        let remote_ref = view.get_remote_branch(name, remote_name);
+        if remote_ref.target.has_conflict() {
+            return Err(user_error_with_hint(
+                format!("Branch {name}@{remote_name} is conflicted"),
+                "Run `jj git fetch` to update the conflicted remote branch.",
+            ));
+        }
+        let old_target = remote_ref.target.as_normal().cloned();
+        let new_target = Some(commit.id().clone());
+        let update = (old_target != new_target).then_some(BranchPushUpdate {
+            old_target,
+            new_target,
+        });
+        updates.push((name, update));
+    }
+    Ok(updates)
+}
+
 fn find_branches_targeted_by_revisions<'a>(
     ui: &Ui,
     workspace_command: &'a WorkspaceCommandHelper,
@@ -0,0 +1,116 @@
+// Copyright 2020 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+use clap::builder::{PossibleValue, PossibleValuesParser, StyledStr};
+use crossterm::style::Stylize;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Print this message or the help of the given subcommand(s)
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct HelpArgs {
+    /// Print help for the subcommand(s)
+    pub(crate) command: Vec<String>,
+    /// Show help for keywords instead of commands
+    #[arg(long, short = 'k', conflicts_with = "command")]
+    pub(crate) keyword: Option<String>,
+}
+
+/// Help topic shown by `jj help -k <name>`.
+#[derive(Clone, Debug)]
+pub(crate) struct HelpKeyword {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Markdown text to be printed.
+    pub content: &'static str,
+}
+
+/// Returns the help topics provided by jj itself.
+pub(crate) fn builtin_keywords() -> Vec<HelpKeyword> {
+    vec![
+        HelpKeyword {
+            name: "revsets",
+            description: "A functional language for selecting a set of revisions",
+            content: include_str!("../../../docs/revsets.md"),
+        },
+        HelpKeyword {
+            name: "tutorial",
+            description: "Show a tutorial to get started with jj",
+            content: include_str!("../../../docs/tutorial.md"),
+        },
+    ]
+}
+
+/// Updates the possible values of `jj help -k` to the given `keywords`.
+pub(crate) fn set_keyword_values(app: clap::Command, keywords: &[HelpKeyword]) -> clap::Command {
+    let values = keywords
+        .iter()
+        .map(|keyword| PossibleValue::new(keyword.name).help(keyword.description));
+    let parser = PossibleValuesParser::new(values);
+    app.mut_subcommand("help", |cmd| {
+        cmd.mut_arg("keyword", |arg| arg.value_parser(parser))
+    })
+}
+
+pub(crate) fn show_keyword_hint_after_help() -> StyledStr {
+    let mut ret = StyledStr::new();
+    writeln!(
+        ret,
+        "{} lists available keywords. Use {} to show help for one of these keywords.",
+        "'jj help --help'".bold(),
+        "'jj help -k'".bold(),
+    )
+    .unwrap();
+    ret
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_help(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &HelpArgs,
+) -> Result<(), CommandError> {
+    if let Some(name) = &args.keyword {
+        let keyword = command
+            .help_keywords()
+            .iter()
+            .find(|keyword| keyword.name == name)
+            .expect("clap should check this with `value_parser`");
+        ui.request_pager();
+        write!(ui.stdout(), "{}", keyword.content)?;
+        return Ok(());
+    }
+
+    let bin_name = command
+        .string_args()
+        .first()
+        .map_or(command.app().get_name(), |name| name.as_ref());
+    let mut args_to_show_help = vec![bin_name];
+    args_to_show_help.extend(args.command.iter().map(|s| s.as_str()));
+    args_to_show_help.push("--help");
+
+    let help_err = command
+        .app()
+        .clone()
+        .subcommand_required(true)
+        .try_get_matches_from(&args_to_show_help)
+        .expect_err("Clap library should return a DisplayHelp error in this context");
+    Err(help_err.into())
+}
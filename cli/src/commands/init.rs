@@ -74,7 +74,11 @@ Use `jj git init` instead"
 Set `ui.allow-init-native` to allow initializing a repo with the native backend.",
             ));
         }
-        Workspace::init_local(command.settings(), &wc_path)?;
+        Workspace::init_local_with_working_copy_factory(
+            command.settings(),
+            &wc_path,
+            command.get_new_working_copy_factory()?,
+        )?;
     }
 
     let relative_wc_path = file_util::relative_path(cwd, &wc_path);
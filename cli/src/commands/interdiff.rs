@@ -26,6 +26,12 @@ use crate::ui::Ui;
 /// This excludes changes from other commits by temporarily rebasing `--from`
 /// onto `--to`'s parents. If you wish to compare the same change across
 /// versions, consider `jj obslog -p` instead.
+///
+/// The two commits don't have to be related. If they have different parents,
+/// the output is the difference between the changes each commit makes relative
+/// to its own parents, not between their full trees. For example, `jj
+/// interdiff --from a --to b` on two independent single-commit branches shows
+/// how the patch in `a` differs from the patch in `b`.
 #[derive(clap::Args, Clone, Debug)]
 #[command(group(ArgGroup::new("to_diff").args(&["from", "to"]).multiple(true).required(true)))]
 pub(crate) struct InterdiffArgs {
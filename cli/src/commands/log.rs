@@ -12,17 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jj_lib::backend::CommitId;
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools as _;
+use jj_lib::backend::{CommitId, Signature};
+use jj_lib::commit::Commit;
+use jj_lib::default_index::{AsCompositeIndex as _, DefaultReadonlyIndex};
 use jj_lib::graph::{GraphEdgeType, ReverseGraphIterator, TopoGroupedGraphIterator};
+use jj_lib::hex_util::to_reverse_hex;
+use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo;
 use jj_lib::revset::{RevsetExpression, RevsetFilterPredicate, RevsetIteratorExt};
+use jj_lib::str_util::StringPattern;
+use once_cell::sync::Lazy;
 use tracing::instrument;
 
-use crate::cli_util::{format_template, CommandHelper, LogContentFormat, RevisionArg};
-use crate::command_error::CommandError;
+use crate::cli_util::{
+    format_template, CommandHelper, LogContentFormat, RevisionArg, WorkspaceCommandHelper,
+};
+use crate::command_error::{user_error_with_message, CommandError};
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::diff_util::DiffFormatArgs;
 use crate::graphlog::{get_graphlog, Edge};
+use crate::time_util::{format_absolute_timestamp_with, FormattingItems};
 use crate::ui::Ui;
 
 /// Show revision history
@@ -32,7 +44,7 @@ use crate::ui::Ui;
 /// along with some additional revisions for context.
 ///
 /// Spans of revisions that are not included in the graph per `--revisions` are
-/// rendered as a synthetic node labeled "(elided revisions)".
+/// rendered as a synthetic node labeled with the number of elided revisions.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct LogArgs {
     /// Which revisions to show. If no paths nor revisions are specified, this
@@ -62,16 +74,108 @@ pub(crate) struct LogArgs {
     /// Don't show the graph, show a flat list of revisions
     #[arg(long)]
     no_graph: bool,
+    /// Show only heads, branches, tags, and the working-copy commit
+    ///
+    /// The other revisions are collapsed into synthetic nodes labeled with the
+    /// number of elided revisions. The root commit is also shown so that all
+    /// the lines of development are connected in the graph.
+    #[arg(long, alias = "topology", conflicts_with = "revisions")]
+    summary_only: bool,
     /// Render each revision using the given template
     ///
     /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
     #[arg(long, short = 'T')]
     template: Option<String>,
+    /// Also show the parents of the working-copy commit
+    ///
+    /// This is also enabled by the `ui.log-include-working-copy-parents`
+    /// setting if neither revisions nor paths are specified.
+    #[arg(long)]
+    include_working_copy_parents: bool,
     /// Show patch
     #[arg(long, short = 'p')]
     patch: bool,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
+    /// Print one JSON object per revision (newline-delimited JSON)
+    ///
+    /// Each object has the fields `commit_id`, `change_id`, `parents`,
+    /// `author`, `committer`, `description`, and `branches`. Signatures have
+    /// `name`, `email`, and `timestamp` (in RFC 3339 format) fields. Implies
+    /// `--no-graph`.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "template",
+            "patch",
+            "summary",
+            "stat",
+            "types",
+            "name_only",
+            "git",
+            "color_words",
+            "tool",
+        ]
+    )]
+    json: bool,
+}
+
+/// Counts the revisions on the paths between the `commit_id` and `target`
+/// commits for which `is_shown` returns false.
+fn count_elided_revisions(
+    workspace_command: &WorkspaceCommandHelper,
+    is_shown: &dyn Fn(&CommitId) -> bool,
+    commit_id: &CommitId,
+    target: &CommitId,
+) -> Result<usize, CommandError> {
+    let repo = workspace_command.repo();
+    let index = repo.readonly_index().as_any();
+    let Some(default_index) = index.downcast_ref::<DefaultReadonlyIndex>() else {
+        // The target is an ancestor of the commit, or a descendant if the
+        // graph is reversed.
+        let commit_expression = RevsetExpression::commit(commit_id.clone());
+        let target_expression = RevsetExpression::commit(target.clone());
+        let expression = commit_expression
+            .dag_range_to(&target_expression)
+            .union(&target_expression.dag_range_to(&commit_expression));
+        let revset = workspace_command
+            .attach_revset_evaluator(expression)?
+            .evaluate()?;
+        return Ok(revset.iter().filter(|id| !is_shown(id)).count());
+    };
+    let index = default_index.as_composite();
+    let pos1 = index.commit_id_to_pos(commit_id).unwrap();
+    let pos2 = index.commit_id_to_pos(target).unwrap();
+    let (head_pos, root_pos) = if pos1 > pos2 {
+        (pos1, pos2)
+    } else {
+        (pos2, pos1)
+    };
+    // Ancestors of the head are positioned before the head, and descendants of
+    // the root are positioned after the root.
+    let mut candidates = HashSet::new();
+    let mut to_visit = vec![head_pos];
+    while let Some(pos) = to_visit.pop() {
+        if pos > root_pos && candidates.insert(pos) {
+            to_visit.extend(index.entry_by_pos(pos).parent_positions());
+        }
+    }
+    let mut reachable = HashSet::from([root_pos]);
+    let mut count = 0;
+    for pos in candidates.into_iter().sorted_unstable() {
+        let entry = index.entry_by_pos(pos);
+        if entry
+            .parent_positions()
+            .iter()
+            .any(|p| reachable.contains(p))
+        {
+            reachable.insert(pos);
+            if !is_shown(&entry.commit_id()) {
+                count += 1;
+            }
+        }
+    }
+    Ok(count)
 }
 
 #[instrument(skip_all)]
@@ -85,9 +189,19 @@ pub(crate) fn cmd_log(
     let fileset_expression = workspace_command.parse_file_patterns(&args.paths)?;
     let revset_expression = {
         // only use default revset if neither revset nor path are specified
-        let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
-            workspace_command
-                .parse_revset(&RevisionArg::from(command.settings().default_revset()))?
+        let use_default_revset =
+            args.revisions.is_empty() && args.paths.is_empty() && !args.summary_only;
+        let mut expression = if args.summary_only {
+            let workspace_id = workspace_command.workspace_id().clone();
+            workspace_command.attach_revset_evaluator(RevsetExpression::union_all(&[
+                RevsetExpression::visible_heads(),
+                RevsetExpression::branches(StringPattern::everything()),
+                RevsetExpression::tags(),
+                RevsetExpression::working_copy(workspace_id),
+                RevsetExpression::root(),
+            ]))?
+        } else if use_default_revset {
+            workspace_command.parse_default_revset()?
         } else if !args.revisions.is_empty() {
             workspace_command.parse_union_revsets(&args.revisions)?
         } else {
@@ -100,6 +214,16 @@ pub(crate) fn cmd_log(
             let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
             expression.intersect_with(&RevsetExpression::filter(predicate));
         }
+        let include_working_copy_parents = args.include_working_copy_parents
+            || use_default_revset
+                && command
+                    .settings()
+                    .config()
+                    .get_bool("ui.log-include-working-copy-parents")?;
+        if include_working_copy_parents {
+            let workspace_id = workspace_command.workspace_id().clone();
+            expression.union_with(&RevsetExpression::working_copy(workspace_id).parents());
+        }
         expression
     };
 
@@ -110,10 +234,11 @@ pub(crate) fn cmd_log(
     let store = repo.store();
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
 
-    let use_elided_nodes = command
-        .settings()
-        .config()
-        .get_bool("ui.log-synthetic-elided-nodes")?;
+    let use_elided_nodes = args.summary_only
+        || command
+            .settings()
+            .config()
+            .get_bool("ui.log-synthetic-elided-nodes")?;
     let with_content_format = LogContentFormat::new(ui, command.settings())?;
 
     let template;
@@ -153,8 +278,21 @@ pub(crate) fn cmd_log(
         }
         let limit = args.limit.or(args.deprecated_limit).unwrap_or(usize::MAX);
 
-        if !args.no_graph {
+        if args.json {
+            let iter: Box<dyn Iterator<Item = CommitId>> = if args.reversed {
+                Box::new(revset.iter().reversed())
+            } else {
+                Box::new(revset.iter())
+            };
+            let branches_index = build_branches_index(repo.as_ref());
+            for commit_or_error in iter.commits(store).take(limit) {
+                let commit = commit_or_error?;
+                let value = commit_to_json(&commit, &branches_index)?;
+                writeln!(formatter, "{value}")?;
+            }
+        } else if !args.no_graph {
             let mut graph = get_graphlog(command.settings(), formatter.raw());
+            let is_shown = revset.containing_fn();
             let forward_iter = TopoGroupedGraphIterator::new(revset.iter_graph());
             let iter: Box<dyn Iterator<Item = _>> = if args.reversed {
                 Box::new(ReverseGraphIterator::new(forward_iter))
@@ -214,13 +352,26 @@ pub(crate) fn cmd_log(
                     &String::from_utf8_lossy(&buffer),
                 )?;
                 for elided_target in elided_targets {
+                    let num_elided = count_elided_revisions(
+                        &workspace_command,
+                        &is_shown,
+                        &key.0,
+                        &elided_target,
+                    )?;
                     let elided_key = (elided_target, true);
                     let real_key = (elided_key.0.clone(), false);
                     let edges = [Edge::Direct(real_key)];
                     let mut buffer = vec![];
                     with_content_format.write_graph_text(
                         ui.new_formatter(&mut buffer).as_mut(),
-                        |formatter| writeln!(formatter.labeled("elided"), "(elided revisions)"),
+                        |formatter| {
+                            let noun = if num_elided == 1 {
+                                "revision"
+                            } else {
+                                "revisions"
+                            };
+                            writeln!(formatter.labeled("elided"), "({num_elided} elided {noun})")
+                        },
                         || graph.width(&elided_key, &edges),
                     )?;
                     let node_symbol = format_template(ui, &None, &node_template);
@@ -275,3 +426,40 @@ pub(crate) fn cmd_log(
 
     Ok(())
 }
+
+fn build_branches_index(repo: &dyn Repo) -> HashMap<CommitId, Vec<&str>> {
+    let mut index: HashMap<_, Vec<_>> = HashMap::new();
+    for (name, target) in repo.view().local_branches() {
+        for id in target.added_ids() {
+            index.entry(id.clone()).or_default().push(name);
+        }
+    }
+    index
+}
+
+fn commit_to_json(
+    commit: &Commit,
+    branches_index: &HashMap<CommitId, Vec<&str>>,
+) -> Result<serde_json::Value, CommandError> {
+    Ok(serde_json::json!({
+        "commit_id": commit.id().hex(),
+        "change_id": to_reverse_hex(&commit.change_id().hex()).unwrap(),
+        "parents": commit.parent_ids().iter().map(|id| id.hex()).collect_vec(),
+        "author": signature_to_json(commit.author())?,
+        "committer": signature_to_json(commit.committer())?,
+        "description": commit.description(),
+        "branches": branches_index.get(commit.id()).map_or(&[][..], |names| names),
+    }))
+}
+
+fn signature_to_json(signature: &Signature) -> Result<serde_json::Value, CommandError> {
+    static RFC3339_FORMAT: Lazy<FormattingItems> =
+        Lazy::new(|| FormattingItems::parse("%Y-%m-%dT%H:%M:%S%.3f%:z").unwrap());
+    let timestamp = format_absolute_timestamp_with(&signature.timestamp, &RFC3339_FORMAT)
+        .map_err(|err| user_error_with_message("Invalid timestamp", err))?;
+    Ok(serde_json::json!({
+        "name": signature.name,
+        "email": signature.email,
+        "timestamp": timestamp,
+    }))
+}
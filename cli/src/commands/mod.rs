@@ -29,6 +29,7 @@ mod edit;
 mod file;
 mod fix;
 mod git;
+pub(crate) mod help;
 mod init;
 mod interdiff;
 mod log;
@@ -99,6 +100,7 @@ enum Command {
     Fix(fix::FixArgs),
     #[command(subcommand)]
     Git(git::GitCommand),
+    Help(help::HelpArgs),
     Init(init::InitArgs),
     Interdiff(interdiff::InterdiffArgs),
     Log(log::LogArgs),
@@ -162,7 +164,8 @@ struct DummyCommandArgs {
 }
 
 pub fn default_app() -> clap::Command {
-    Command::augment_subcommands(Args::command())
+    let app = Command::augment_subcommands(Args::command());
+    help::set_keyword_values(app, &help::builtin_keywords())
 }
 
 #[instrument(skip_all)]
@@ -189,6 +192,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Files(args) => file::list::deprecated_cmd_files(ui, command_helper, args),
         Command::Fix(args) => fix::cmd_fix(ui, command_helper, args),
         Command::Git(args) => git::cmd_git(ui, command_helper, args),
+        Command::Help(args) => help::cmd_help(ui, command_helper, args),
         Command::Init(args) => init::cmd_init(ui, command_helper, args),
         Command::Interdiff(args) => interdiff::cmd_interdiff(ui, command_helper, args),
         Command::Log(args) => log::cmd_log(ui, command_helper, args),
@@ -36,7 +36,8 @@ use crate::ui::Ui;
 ///
 /// Note that you can create a merge commit by specifying multiple revisions as
 /// argument. For example, `jj new main @` will create a new commit with the
-/// `main` branch and the working copy as parents.
+/// `main` branch and the working copy as parents. To merge all heads of a
+/// revset, use `--merge`, e.g. `jj new --merge 'heads(trunk()..)'`.
 ///
 /// For more information, see
 /// https://github.com/martinvonz/jj/blob/main/docs/working-copy.md.
@@ -76,6 +77,13 @@ pub(crate) struct NewArgs {
         conflicts_with = "revisions"
     )]
     insert_before: Vec<RevisionArg>,
+    /// Create a merge commit with all heads of the revset as parents
+    #[arg(
+        long,
+        value_name = "REVSET",
+        conflicts_with_all = ["revisions", "insert_after", "insert_before"]
+    )]
+    merge: Option<RevisionArg>,
 }
 
 #[instrument(skip_all)]
@@ -164,10 +172,35 @@ Please use `jj new 'all:x|y'` instead of `jj new --allow-large-revsets x y`.",
             .commits(workspace_command.repo().store())
             .try_collect()?;
     } else {
-        parent_commits = workspace_command
-            .resolve_some_revsets_default_single(&args.revisions)?
-            .into_iter()
-            .collect_vec();
+        parent_commits = if let Some(merge_arg) = &args.merge {
+            let heads_expression = workspace_command
+                .parse_revset(merge_arg)?
+                .expression()
+                .heads();
+            let commits: Vec<_> = workspace_command
+                .attach_revset_evaluator(heads_expression)?
+                .evaluate_to_commits()?
+                .try_collect()?;
+            match commits.len() {
+                0 => {
+                    return Err(user_error(format!(
+                        "Revset \"{merge_arg}\" didn't resolve to any revisions"
+                    )));
+                }
+                1 => writeln!(
+                    ui.hint_default(),
+                    "Revset \"{merge_arg}\" resolved to a single revision, so the new commit \
+                     won't be a merge"
+                )?,
+                _ => {}
+            }
+            commits
+        } else {
+            workspace_command
+                .resolve_some_revsets_default_single(&args.revisions)?
+                .into_iter()
+                .collect_vec()
+        };
         parent_commit_ids = parent_commits.iter().ids().cloned().collect();
         children_commits = vec![];
 
@@ -50,6 +50,11 @@ pub(crate) struct ObslogArgs {
     )]
     deprecated_limit: Option<usize>,
     /// Don't show the graph, show a flat list of revisions
+    ///
+    /// Revisions are listed in reverse topological order, so each revision is
+    /// listed before its predecessors. If a revision has more than one
+    /// predecessor, they are listed in the order recorded in the revision. Use
+    /// the `predecessors()` template method to tell them apart.
     #[arg(long)]
     no_graph: bool,
     /// Render each revision using the given template
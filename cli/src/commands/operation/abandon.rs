@@ -12,15 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::io::Write as _;
 use std::slice;
+use std::time::{Duration, SystemTime};
 
 use itertools::Itertools as _;
+use jj_lib::backend::MillisSinceEpoch;
 use jj_lib::op_store::OperationId;
 use jj_lib::op_walk;
 use jj_lib::operation::Operation;
 
-use crate::cli_util::{short_operation_hash, CommandHelper};
+use crate::cli_util::{parse_duration_arg, short_operation_hash, CommandHelper};
 use crate::command_error::{user_error, user_error_with_hint, CommandError};
 use crate::ui::Ui;
 
@@ -33,12 +36,28 @@ use crate::ui::Ui;
 /// To discard recent operations, use `jj op restore <operation ID>` followed
 /// by `jj op abandon <operation ID>..@-`.
 ///
+/// To discard operations older than some age, use `jj op abandon --older-than
+/// <duration>`. The operation the working copy points to is never abandoned.
+/// If the working copy is stale, its ancestor operations are kept as well.
+///
 /// The abandoned operations, commits, and other unreachable objects can later
 /// be garbage collected by using `jj util gc` command.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationAbandonArgs {
     /// The operation or operation range to abandon
-    operation: String,
+    #[arg(required_unless_present = "older_than")]
+    operation: Option<String>,
+    /// Abandon all operations recorded before the given duration ago
+    ///
+    /// The duration is a number followed by a unit: `s`, `m`, `h`, `d`, or
+    /// `w` (e.g. `30d`).
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration_arg,
+        conflicts_with = "operation"
+    )]
+    older_than: Option<Duration>,
 }
 
 pub fn cmd_op_abandon(
@@ -59,43 +78,62 @@ pub fn cmd_op_abandon(
     }
     let current_head_op = op_walk::resolve_op_for_load(repo_loader, head_op_str)?;
     let resolve_op = |op_str| op_walk::resolve_op_at(op_store, &current_head_op, op_str);
-    let (abandon_root_op, abandon_head_op) =
-        if let Some((root_op_str, head_op_str)) = args.operation.split_once("..") {
-            let root_op = if root_op_str.is_empty() {
-                let id = op_store.root_operation_id();
-                let data = op_store.read_operation(id)?;
-                Operation::new(op_store.clone(), id.clone(), data)
-            } else {
-                resolve_op(root_op_str)?
-            };
-            let head_op = if head_op_str.is_empty() {
-                current_head_op.clone()
-            } else {
-                resolve_op(head_op_str)?
-            };
-            (root_op, head_op)
+    let resolve_root_op = || -> Result<Operation, CommandError> {
+        let id = op_store.root_operation_id();
+        let data = op_store.read_operation(id)?;
+        Ok(Operation::new(op_store.clone(), id.clone(), data))
+    };
+    let (abandon_root_op, abandon_head_ops) = if let Some(duration) = args.older_than {
+        let threshold = SystemTime::now()
+            .checked_sub(duration)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let wc_op_id = workspace.working_copy().operation_id();
+        let root_op = resolve_root_op()?;
+        let old_ops = find_ops_older_than(&current_head_op, wc_op_id, root_op.id(), threshold)?;
+        if old_ops.is_empty() {
+            // Empty range root_op..root_op, which abandons nothing.
+            (root_op.clone(), vec![root_op])
         } else {
-            let op = resolve_op(&args.operation)?;
-            let parent_ops: Vec<_> = op.parents().try_collect()?;
-            let parent_op = match parent_ops.len() {
-                0 => return Err(user_error("Cannot abandon the root operation")),
-                1 => parent_ops.into_iter().next().unwrap(),
-                _ => return Err(user_error("Cannot abandon a merge operation")),
+            (root_op, old_ops)
+        }
+    } else {
+        let operation = args.operation.as_deref().unwrap();
+        let (root_op, head_op) =
+            if let Some((root_op_str, head_op_str)) = operation.split_once("..") {
+                let root_op = if root_op_str.is_empty() {
+                    resolve_root_op()?
+                } else {
+                    resolve_op(root_op_str)?
+                };
+                let head_op = if head_op_str.is_empty() {
+                    current_head_op.clone()
+                } else {
+                    resolve_op(head_op_str)?
+                };
+                (root_op, head_op)
+            } else {
+                let op = resolve_op(operation)?;
+                let parent_ops: Vec<_> = op.parents().try_collect()?;
+                let parent_op = match parent_ops.len() {
+                    0 => return Err(user_error("Cannot abandon the root operation")),
+                    1 => parent_ops.into_iter().next().unwrap(),
+                    _ => return Err(user_error("Cannot abandon a merge operation")),
+                };
+                (parent_op, op)
             };
-            (parent_op, op)
-        };
-
-    if abandon_head_op == current_head_op {
-        return Err(user_error_with_hint(
-            "Cannot abandon the current operation",
-            "Run `jj undo` to revert the current operation, then use `jj op abandon`",
-        ));
-    }
+        if head_op == current_head_op {
+            return Err(user_error_with_hint(
+                "Cannot abandon the current operation",
+                "Run `jj undo` to revert the current operation, then use `jj op abandon`",
+            ));
+        }
+        (root_op, vec![head_op])
+    };
 
     // Reparent descendants, count the number of abandoned operations.
     let stats = op_walk::reparent_range(
         op_store.as_ref(),
-        slice::from_ref(&abandon_head_op),
+        &abandon_head_ops,
         slice::from_ref(&current_head_op),
         &abandon_root_op,
     )?;
@@ -131,3 +169,76 @@ pub fn cmd_op_abandon(
     }
     Ok(())
 }
+
+/// Finds ancestors of the `head_op` which ended before the `threshold`, and
+/// returns the heads of them.
+///
+/// The `head_op` and the working-copy operation are excluded. If the working
+/// copy is stale, its ancestors and descendants are also excluded because
+/// abandoning them would drop the working-copy operation from the history.
+/// Operations having an excluded ancestor are excluded as well.
+fn find_ops_older_than(
+    head_op: &Operation,
+    wc_op_id: &OperationId,
+    root_op_id: &OperationId,
+    threshold: SystemTime,
+) -> Result<Vec<Operation>, CommandError> {
+    let since_epoch = threshold
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    let threshold = MillisSinceEpoch(since_epoch.as_millis().try_into().unwrap());
+    let ops: Vec<Operation> = op_walk::walk_ancestors(slice::from_ref(head_op)).try_collect()?;
+    let mut excluded_ids: HashSet<&OperationId> = HashSet::from([head_op.id(), wc_op_id]);
+    if wc_op_id != head_op.id() {
+        // Operations are sorted in reverse topological order, so ancestors
+        // are propagated from children to parents, and descendants are
+        // propagated from parents to children.
+        let mut wc_ancestor_ids = HashSet::from([wc_op_id]);
+        for op in &ops {
+            if wc_ancestor_ids.contains(op.id()) {
+                wc_ancestor_ids.extend(op.parent_ids());
+            }
+        }
+        let mut wc_descendant_ids = HashSet::from([wc_op_id]);
+        for op in ops.iter().rev() {
+            if op
+                .parent_ids()
+                .iter()
+                .any(|id| wc_descendant_ids.contains(id))
+            {
+                wc_descendant_ids.insert(op.id());
+            }
+        }
+        excluded_ids.extend(wc_ancestor_ids);
+        excluded_ids.extend(wc_descendant_ids);
+    }
+    // reparent_range() abandons all ancestors of the given heads, so an
+    // operation can only be abandoned if its ancestors can also be. The root
+    // operation is the base of the range, which is never abandoned.
+    let mut abandoned_ids: HashSet<&OperationId> = HashSet::new();
+    for op in ops.iter().rev() {
+        let is_old =
+            !excluded_ids.contains(op.id()) && op.metadata().end_time.timestamp < threshold;
+        let parent_ids = op.parent_ids();
+        if is_old
+            && !parent_ids.is_empty()
+            && parent_ids
+                .iter()
+                .all(|id| abandoned_ids.contains(id) || id == root_op_id)
+        {
+            abandoned_ids.insert(op.id());
+        }
+    }
+    // Pass only the heads of the abandoned operations.
+    let abandoned_parent_ids: HashSet<&OperationId> = ops
+        .iter()
+        .filter(|op| abandoned_ids.contains(op.id()))
+        .flat_map(|op| op.parent_ids())
+        .collect();
+    let old_ops = ops
+        .iter()
+        .filter(|op| abandoned_ids.contains(op.id()) && !abandoned_parent_ids.contains(op.id()))
+        .cloned()
+        .collect();
+    Ok(old_ops)
+}
@@ -21,6 +21,10 @@ use crate::operation_templater::OperationTemplateLanguage;
 use crate::ui::Ui;
 
 /// Show the operation log
+///
+/// With the global `--at-op` option, only the given operation and its
+/// ancestors are shown, as if that operation were the head. The given
+/// operation isn't marked as the current operation unless it actually is.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationLogArgs {
     /// Limit number of operations to show
@@ -55,25 +59,29 @@ pub fn cmd_op_log(
     let workspace = command.load_workspace()?;
     let repo_loader = workspace.repo_loader();
     let head_op_str = &command.global_args().at_operation;
-    let head_ops = if head_op_str == "@" {
-        // If multiple head ops can't be resolved without merging, let the
-        // current op be empty. Beware that resolve_op_for_load() will eliminate
-        // redundant heads whereas get_current_head_ops() won't.
-        let current_op = op_walk::resolve_op_for_load(repo_loader, head_op_str).ok();
-        if let Some(op) = current_op {
+    // If multiple head ops can't be resolved without merging, let the current
+    // op be empty. Beware that resolve_op_for_load() will eliminate redundant
+    // heads whereas get_current_head_ops() won't.
+    let current_op = op_walk::resolve_op_for_load(repo_loader, "@").ok();
+    let (head_ops, current_op_id) = if head_op_str == "@" {
+        let head_ops = if let Some(op) = current_op {
             vec![op]
         } else {
             op_walk::get_current_head_ops(
                 repo_loader.op_store(),
                 repo_loader.op_heads_store().as_ref(),
             )?
-        }
+        };
+        let current_op_id = match &*head_ops {
+            [op] => Some(op.id().clone()),
+            _ => None,
+        };
+        (head_ops, current_op_id)
     } else {
-        vec![op_walk::resolve_op_for_load(repo_loader, head_op_str)?]
-    };
-    let current_op_id = match &*head_ops {
-        [op] => Some(op.id()),
-        _ => None,
+        // The current op isn't an ancestor of the given op unless they're the
+        // same, so the given op shouldn't look like the current op.
+        let head_op = op_walk::resolve_op_for_load(repo_loader, head_op_str)?;
+        (vec![head_op], current_op.map(|op| op.id().clone()))
     };
     let with_content_format = LogContentFormat::new(ui, command.settings())?;
 
@@ -81,8 +89,9 @@ pub fn cmd_op_log(
     let op_node_template;
     {
         let language = OperationTemplateLanguage::new(
+            command.settings(),
             repo_loader.op_store().root_operation_id(),
-            current_op_id,
+            current_op_id.as_ref(),
             command.operation_template_extensions(),
         );
         let text = match &args.template {
@@ -17,14 +17,22 @@ mod log;
 mod restore;
 pub mod undo;
 
+use std::io;
+
 use abandon::{cmd_op_abandon, OperationAbandonArgs};
 use clap::Subcommand;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::op_store::RefTarget;
+use jj_lib::refs::{diff_named_ref_targets, diff_named_remote_refs};
+use jj_lib::repo::Repo;
 use log::{cmd_op_log, OperationLogArgs};
 use restore::{cmd_op_restore, OperationRestoreArgs};
 use undo::{cmd_op_undo, OperationUndoArgs};
 
-use crate::cli_util::CommandHelper;
+use crate::cli_util::{CommandHelper, WorkspaceCommandTransaction};
 use crate::command_error::CommandError;
+use crate::formatter::Formatter;
 use crate::ui::Ui;
 
 /// Commands for working with the operation log
@@ -90,3 +98,145 @@ fn view_with_desired_portions_restored(
         wc_commit_ids: repo_source.wc_commit_ids.clone(),
     }
 }
+
+/// Tells which portions of the view were kept as is because they weren't
+/// selected by the `what` argument.
+fn print_unrestored_portions(ui: &Ui, what: &[UndoWhatToRestore]) -> io::Result<()> {
+    if !what.contains(&UndoWhatToRestore::Repo) {
+        writeln!(
+            ui.status(),
+            "Kept the repo state and local branches from the current operation."
+        )?;
+    }
+    if !what.contains(&UndoWhatToRestore::RemoteTracking) {
+        writeln!(
+            ui.status(),
+            "Kept the remote-tracking branches from the current operation."
+        )?;
+    }
+    Ok(())
+}
+
+/// Prints the changes to be made by the transaction compared to its base
+/// view.
+fn print_view_changes(ui: &Ui, tx: &WorkspaceCommandTransaction) -> Result<(), CommandError> {
+    let old_view = tx.base_repo().view();
+    let new_view = tx.repo().view();
+    let Some(mut formatter) = ui.status_formatter() else {
+        return Ok(());
+    };
+    let formatter = formatter.as_mut();
+    let write_commit = |formatter: &mut dyn Formatter, prefix: &str, id: &CommitId| {
+        let commit = tx.repo().store().get_commit(id)?;
+        write!(formatter, "  {prefix} ")?;
+        tx.write_commit_summary(formatter, &commit)?;
+        writeln!(formatter)?;
+        Ok::<_, CommandError>(())
+    };
+    let write_ref =
+        |formatter: &mut dyn Formatter, prefix: &str, name: &str, target: &RefTarget| {
+            if target.is_absent() {
+                return Ok(());
+            }
+            if let Some(id) = target.as_normal() {
+                write!(formatter, "  {prefix} {name}: ")?;
+                let commit = tx.repo().store().get_commit(id)?;
+                tx.write_commit_summary(formatter, &commit)?;
+                writeln!(formatter)?;
+            } else {
+                writeln!(formatter, "  {prefix} {name}: (conflicted)")?;
+            }
+            Ok::<_, CommandError>(())
+        };
+
+    let mut changed = false;
+    let workspace_ids = old_view
+        .wc_commit_ids()
+        .keys()
+        .chain(new_view.wc_commit_ids().keys())
+        .sorted()
+        .dedup();
+    for workspace_id in workspace_ids {
+        let old_id = old_view.get_wc_commit_id(workspace_id);
+        let new_id = new_view.get_wc_commit_id(workspace_id);
+        if old_id == new_id {
+            continue;
+        }
+        changed = true;
+        writeln!(
+            formatter,
+            "Working-copy commit of workspace {}:",
+            workspace_id.as_str()
+        )?;
+        if let Some(id) = old_id {
+            write_commit(formatter, "-", id)?;
+        }
+        if let Some(id) = new_id {
+            write_commit(formatter, "+", id)?;
+        }
+    }
+
+    let removed_heads = old_view
+        .heads()
+        .iter()
+        .filter(|id| !new_view.heads().contains(id))
+        .sorted()
+        .collect_vec();
+    let added_heads = new_view
+        .heads()
+        .iter()
+        .filter(|id| !old_view.heads().contains(id))
+        .sorted()
+        .collect_vec();
+    if !removed_heads.is_empty() || !added_heads.is_empty() {
+        changed = true;
+        writeln!(formatter, "Heads:")?;
+        for id in removed_heads {
+            write_commit(formatter, "-", id)?;
+        }
+        for id in added_heads {
+            write_commit(formatter, "+", id)?;
+        }
+    }
+
+    let changed_branches =
+        diff_named_ref_targets(old_view.local_branches(), new_view.local_branches()).collect_vec();
+    if !changed_branches.is_empty() {
+        changed = true;
+        writeln!(formatter, "Branches:")?;
+        for (name, (old_target, new_target)) in changed_branches {
+            write_ref(formatter, "-", name, old_target)?;
+            write_ref(formatter, "+", name, new_target)?;
+        }
+    }
+
+    let changed_remote_branches = diff_named_remote_refs(
+        old_view.all_remote_branches(),
+        new_view.all_remote_branches(),
+    )
+    .collect_vec();
+    if !changed_remote_branches.is_empty() {
+        changed = true;
+        writeln!(formatter, "Remote-tracking branches:")?;
+        for ((name, remote), (old_ref, new_ref)) in changed_remote_branches {
+            let name = format!("{name}@{remote}");
+            write_ref(formatter, "-", &name, &old_ref.target)?;
+            write_ref(formatter, "+", &name, &new_ref.target)?;
+        }
+    }
+
+    let changed_tags = diff_named_ref_targets(old_view.tags(), new_view.tags()).collect_vec();
+    if !changed_tags.is_empty() {
+        changed = true;
+        writeln!(formatter, "Tags:")?;
+        for (name, (old_target, new_target)) in changed_tags {
+            write_ref(formatter, "-", name, old_target)?;
+            write_ref(formatter, "+", name, new_target)?;
+        }
+    }
+
+    if !changed {
+        writeln!(formatter, "Nothing changed.")?;
+    }
+    Ok(())
+}
@@ -14,7 +14,10 @@
 
 use jj_lib::object_id::ObjectId;
 
-use super::{view_with_desired_portions_restored, UndoWhatToRestore, DEFAULT_UNDO_WHAT};
+use super::{
+    print_unrestored_portions, view_with_desired_portions_restored, UndoWhatToRestore,
+    DEFAULT_UNDO_WHAT,
+};
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::ui::Ui;
@@ -54,6 +57,7 @@ pub fn cmd_op_restore(
     );
     tx.mut_repo().set_view(new_view);
     tx.finish(ui, format!("restore to operation {}", target_op.id().hex()))?;
+    print_unrestored_portions(ui, &args.what)?;
 
     Ok(())
 }
@@ -15,7 +15,10 @@
 use jj_lib::object_id::ObjectId;
 use jj_lib::repo::Repo;
 
-use super::{view_with_desired_portions_restored, UndoWhatToRestore, DEFAULT_UNDO_WHAT};
+use super::{
+    print_unrestored_portions, print_view_changes, view_with_desired_portions_restored,
+    UndoWhatToRestore, DEFAULT_UNDO_WHAT,
+};
 use crate::cli_util::CommandHelper;
 use crate::command_error::{user_error, CommandError};
 use crate::ui::Ui;
@@ -37,6 +40,10 @@ pub struct OperationUndoArgs {
     /// This option is EXPERIMENTAL.
     #[arg(long, value_enum, default_values_t = DEFAULT_UNDO_WHAT)]
     what: Vec<UndoWhatToRestore>,
+
+    /// Only display the changes to be made, without creating a new operation
+    #[arg(long)]
+    dry_run: bool,
 }
 
 pub fn cmd_op_undo(
@@ -65,7 +72,15 @@ pub fn cmd_op_undo(
         &args.what,
     );
     tx.mut_repo().set_view(new_view);
+    if args.dry_run {
+        // Abandon rewritten commits as tx.finish() would do.
+        tx.mut_repo().rebase_descendants(command.settings())?;
+        print_view_changes(ui, &tx)?;
+        writeln!(ui.status(), "Dry-run requested, not undoing.")?;
+        return Ok(());
+    }
     tx.finish(ui, format!("undo operation {}", bad_op.id().hex()))?;
+    print_unrestored_portions(ui, &args.what)?;
 
     Ok(())
 }
@@ -148,6 +148,20 @@ pub(crate) struct RebaseArgs {
     /// If none of `-b`, `-s`, or `-r` is provided, then the default is `-b @`.
     #[arg(long, short)]
     source: Vec<RevisionArg>,
+    /// Leave these revisions behind when rebasing with `-s` (can be repeated)
+    ///
+    /// `jj rebase -s=src --source-exclude=ex -d=dst` is equivalent to `jj
+    /// rebase '-r=(src::) ~ ex' -d=dst`. The excluded revisions stay on top of
+    /// their nearest ancestors which aren't rebased. Their children which are
+    /// rebased are moved onto the excluded revisions' nearest rebased
+    /// ancestors, or onto the destination if there are none.
+    #[arg(
+        long,
+        requires = "source",
+        conflicts_with = "skip_empty",
+        conflicts_with = "simplify_after"
+    )]
+    source_exclude: Vec<RevisionArg>,
     /// Rebase the given revisions, rebasing descendants onto this revision's
     /// parent(s)
     ///
@@ -195,6 +209,14 @@ pub(crate) struct RebaseArgs {
     #[arg(long, conflicts_with = "revisions")]
     skip_empty: bool,
 
+    /// Remove redundant parents of the rebased commits
+    ///
+    /// If a rebased merge commit would end up with one parent being an
+    /// ancestor of another, the ancestor is removed from the parents. Commits
+    /// which aren't rebased are left as is.
+    #[arg(long, conflicts_with = "revisions")]
+    simplify_after: bool,
+
     /// Deprecated. Please prefix the revset with `all:` instead.
     #[arg(long, short = 'L', hide = true)]
     allow_large_revsets: bool,
@@ -218,7 +240,7 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
             true => EmptyBehaviour::AbandonNewlyEmpty,
             false => EmptyBehaviour::Keep,
         },
-        simplify_ancestor_merge: false,
+        simplify_ancestor_merge: args.simplify_after,
     };
     let mut workspace_command = command.workspace_helper(ui)?;
     if !args.revisions.is_empty() {
@@ -237,6 +259,10 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
             EmptyBehaviour::Keep,
             "clap should forbid `-r --skip-empty`"
         );
+        assert!(
+            !rebase_options.simplify_ancestor_merge,
+            "clap should forbid `-r --simplify-after`"
+        );
         let target_commits: Vec<_> = workspace_command
             .parse_union_revsets(&args.revisions)?
             .evaluate_to_commits()?
@@ -287,6 +313,31 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
                 &target_commits,
             )?;
         }
+    } else if !args.source.is_empty() && !args.source_exclude.is_empty() {
+        let new_parents = workspace_command
+            .resolve_some_revsets_default_single(&args.destination)?
+            .into_iter()
+            .collect_vec();
+        let source_commits = workspace_command.resolve_some_revsets_default_single(&args.source)?;
+        let excluded_expression = workspace_command
+            .parse_union_revsets(&args.source_exclude)?
+            .expression()
+            .clone();
+        let target_expression =
+            RevsetExpression::commits(source_commits.iter().ids().cloned().collect())
+                .descendants()
+                .minus(&excluded_expression);
+        let target_commits: Vec<_> = workspace_command
+            .attach_revset_evaluator(target_expression)?
+            .evaluate_to_commits()?
+            .try_collect()?; // in reverse topological order
+        rebase_revisions(
+            ui,
+            command.settings(),
+            &mut workspace_command,
+            &new_parents,
+            &target_commits,
+        )?;
     } else if !args.source.is_empty() {
         let new_parents = workspace_command
             .resolve_some_revsets_default_single(&args.destination)?
@@ -15,13 +15,24 @@
 use std::io::Write;
 
 use itertools::Itertools;
+use jj_lib::conflicts::{materialize_tree_value, MaterializedTreeValue};
 use jj_lib::object_id::ObjectId;
+use pollster::FutureExt;
 use tracing::instrument;
 
 use crate::cli_util::{print_conflicted_paths, CommandHelper, RevisionArg};
 use crate::command_error::{cli_error, CommandError};
 use crate::ui::Ui;
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum ResolveOrder {
+    /// Sort conflicts by path
+    Path,
+    /// Sort conflicts by the size of the materialized conflict, smallest
+    /// first
+    ConflictSize,
+}
+
 /// Resolve a conflicted file with an external merge tool
 ///
 /// Only conflicts that can be resolved with a 3-way merge are supported. See
@@ -49,6 +60,9 @@ pub(crate) struct ResolveArgs {
     /// Specify 3-way merge tool to be used
     #[arg(long, conflicts_with = "list", value_name = "NAME")]
     tool: Option<String>,
+    /// The order in which conflicts are listed and picked for resolution
+    #[arg(long, value_enum, default_value_t = ResolveOrder::Path)]
+    order: ResolveOrder,
     /// Restrict to these paths when searching for a conflict to resolve. We
     /// will attempt to resolve the first conflict we can find. You can use
     /// the `--list` argument to find paths to use here.
@@ -69,10 +83,23 @@ pub(crate) fn cmd_resolve(
         .to_matcher();
     let commit = workspace_command.resolve_single_rev(&args.revision)?;
     let tree = commit.tree()?;
-    let conflicts = tree
+    let mut conflicts = tree
         .conflicts()
         .filter(|path| matcher.matches(&path.0))
         .collect_vec();
+    match args.order {
+        ResolveOrder::Path => {}
+        ResolveOrder::ConflictSize => {
+            let store = tree.store();
+            conflicts.sort_by_cached_key(|(path, value)| {
+                // Conflicts that can't be materialized as text are sorted last.
+                match materialize_tree_value(store, path, value.clone()).block_on() {
+                    Ok(MaterializedTreeValue::Conflict { contents, .. }) => contents.len(),
+                    _ => usize::MAX,
+                }
+            });
+        }
+    }
     if conflicts.is_empty() {
         return Err(cli_error(if args.paths.is_empty() {
             "No conflicts found at this revision"
@@ -11,14 +11,21 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::collections::BTreeMap;
 use std::io::Write;
+use std::ops::RangeInclusive;
 
+use jj_lib::backend::{MergedTreeId, TreeValue};
+use jj_lib::diff::{self, Diff, DiffHunk};
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::{MergedTree, MergedTreeBuilder};
 use jj_lib::object_id::ObjectId;
 use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
 use tracing::instrument;
 
-use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error_with_hint, CommandError};
+use crate::cli_util::{CommandHelper, RevisionArg, WorkspaceCommandHelper};
+use crate::command_error::{user_error, user_error_with_hint, CommandError};
 use crate::description_util::{description_template_for_commit, edit_description};
 use crate::ui::Ui;
 
@@ -37,6 +44,12 @@ use crate::ui::Ui;
 /// description, the second part will not get a description, and you will be
 /// asked for a description only for the first part.
 ///
+/// With `--at`, the changed lines to put in the first revision are specified
+/// as `<path>:<lines>`, where `<lines>` is a comma-separated list of line
+/// numbers or ranges like `3-5` in the revision's version of the file. Each
+/// diff hunk overlapping any of the ranges is selected as a whole. A hunk which
+/// only removes lines is selected by the line following the removal.
+///
 /// Splitting an empty commit is not supported because the same effect can be
 /// achieved with `jj new`.
 #[derive(clap::Args, Clone, Debug)]
@@ -48,6 +61,13 @@ pub(crate) struct SplitArgs {
     /// Specify diff editor to be used (implies --interactive)
     #[arg(long, value_name = "NAME")]
     tool: Option<String>,
+    /// Put the changed lines in these ranges in the first commit
+    #[arg(
+        long,
+        value_name = "PATH:LINES",
+        conflicts_with_all = ["interactive", "tool", "paths"]
+    )]
+    at: Vec<String>,
     /// The revision to split
     #[arg(long, short, default_value = "@")]
     revision: RevisionArg,
@@ -80,10 +100,11 @@ pub(crate) fn cmd_split(
     let matcher = workspace_command
         .parse_file_patterns(&args.paths)?
         .to_matcher();
+    let line_selections = parse_line_selections(&workspace_command, &args.at)?;
     let diff_selector = workspace_command.diff_selector(
         ui,
         args.tool.as_deref(),
-        args.interactive || args.paths.is_empty(),
+        args.interactive || (args.paths.is_empty() && args.at.is_empty()),
     )?;
     let mut tx = workspace_command.start_transaction();
     let end_tree = commit.tree()?;
@@ -102,8 +123,16 @@ the operation will be aborted.
     );
 
     // Prompt the user to select the changes they want for the first commit.
-    let selected_tree_id =
-        diff_selector.select(&base_tree, &end_tree, matcher.as_ref(), Some(&instructions))?;
+    let selected_tree_id = if line_selections.is_empty() {
+        diff_selector.select(&base_tree, &end_tree, matcher.as_ref(), Some(&instructions))?
+    } else {
+        select_lines(
+            tx.base_workspace_helper(),
+            &base_tree,
+            &end_tree,
+            &line_selections,
+        )?
+    };
     if &selected_tree_id == commit.tree_id() && diff_selector.is_interactive() {
         // The user selected everything from the original commit.
         writeln!(ui.status(), "Nothing changed.")?;
@@ -215,3 +244,111 @@ the operation will be aborted.
     tx.finish(ui, format!("split commit {}", commit.id().hex()))?;
     Ok(())
 }
+
+type LineSelections = BTreeMap<RepoPathBuf, Vec<RangeInclusive<usize>>>;
+
+/// Parses `--at` arguments into 1-based line ranges per path.
+fn parse_line_selections(
+    workspace_command: &WorkspaceCommandHelper,
+    specs: &[String],
+) -> Result<LineSelections, CommandError> {
+    let mut selections = LineSelections::new();
+    for spec in specs {
+        let invalid_spec = || {
+            user_error_with_hint(
+                format!("Invalid line range: {spec}"),
+                "Line ranges should be specified as <path>:<start>-<end>, e.g. src/main.rs:3-5",
+            )
+        };
+        let (path, lines) = spec.rsplit_once(':').ok_or_else(invalid_spec)?;
+        let path = workspace_command.parse_file_path(path)?;
+        let ranges = selections.entry(path).or_default();
+        for range in lines.split(',') {
+            let (start, end) = range.split_once('-').unwrap_or((range, range));
+            let start: usize = start.trim().parse().map_err(|_| invalid_spec())?;
+            let end: usize = end.trim().parse().map_err(|_| invalid_spec())?;
+            if start == 0 || start > end {
+                return Err(invalid_spec());
+            }
+            ranges.push(start..=end);
+        }
+    }
+    Ok(selections)
+}
+
+/// Builds a tree with the diff hunks overlapping the selected lines applied to
+/// `base_tree`.
+fn select_lines(
+    workspace_command: &WorkspaceCommandHelper,
+    base_tree: &MergedTree,
+    end_tree: &MergedTree,
+    selections: &LineSelections,
+) -> Result<MergedTreeId, CommandError> {
+    let store = base_tree.store();
+    let mut tree_builder = MergedTreeBuilder::new(base_tree.id());
+    for (path, ranges) in selections {
+        let ui_path = workspace_command.format_file_path(path);
+        let base_content = match base_tree.path_value(path)?.into_resolved() {
+            Ok(None) => vec![],
+            Ok(Some(TreeValue::File { id, .. })) => store.read_file_content(path, &id)?,
+            _ => return Err(user_error(format!("{ui_path} is not a regular file"))),
+        };
+        let (end_content, executable) = match end_tree.path_value(path)?.into_resolved() {
+            Ok(Some(TreeValue::File { id, executable })) => {
+                (store.read_file_content(path, &id)?, executable)
+            }
+            _ => {
+                return Err(user_error(format!(
+                    "{ui_path} is not a regular file in the revision"
+                )))
+            }
+        };
+
+        let mut matched_ranges = vec![false; ranges.len()];
+        let mut selected_content = vec![];
+        let mut end_line = 1;
+        let diff = Diff::for_tokenizer(&[&base_content, &end_content], diff::find_line_ranges);
+        for hunk in diff.hunks() {
+            match hunk {
+                DiffHunk::Matching(content) => {
+                    end_line += count_lines(content);
+                    selected_content.extend_from_slice(content);
+                }
+                DiffHunk::Different(contents) => {
+                    // A removal is located at the line following it
+                    let num_lines = count_lines(contents[1]).max(1);
+                    let hunk_lines = end_line..end_line + num_lines;
+                    let mut selected = false;
+                    for (range, matched) in ranges.iter().zip(&mut matched_ranges) {
+                        if hunk_lines.start <= *range.end() && *range.start() < hunk_lines.end {
+                            *matched = true;
+                            selected = true;
+                        }
+                    }
+                    let side = if selected { 1 } else { 0 };
+                    selected_content.extend_from_slice(contents[side]);
+                    end_line += count_lines(contents[1]);
+                }
+            }
+        }
+        if let Some(index) = matched_ranges.iter().position(|matched| !matched) {
+            let range = &ranges[index];
+            return Err(user_error(format!(
+                "No changed lines in {ui_path} at {}-{}",
+                range.start(),
+                range.end()
+            )));
+        }
+
+        let id = store.write_file(path, &mut selected_content.as_slice())?;
+        tree_builder.set_or_remove(
+            path.clone(),
+            Merge::normal(TreeValue::File { id, executable }),
+        );
+    }
+    Ok(tree_builder.write_tree(store)?)
+}
+
+fn count_lines(content: &[u8]) -> usize {
+    content.split_inclusive(|b| *b == b'\n').count()
+}
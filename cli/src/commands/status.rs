@@ -92,6 +92,25 @@ pub(crate) fn cmd_status(
         }
 
         let wc_revset = RevsetExpression::commit(wc_commit.id().clone());
+        let immutable_revset =
+            revset_util::parse_immutable_expression(&workspace_command.revset_parse_context())?;
+        // The working-copy commit normally can't be immutable, but it can be if
+        // the immutable_heads() alias was changed after it was checked out.
+        if !command.global_args().ignore_immutable
+            && workspace_command
+                .attach_revset_evaluator(wc_revset.intersection(&immutable_revset))?
+                .evaluate_to_commit_ids()?
+                .next()
+                .is_some()
+        {
+            writeln!(ui.warning_default(), "The working-copy commit is immutable")?;
+            writeln!(
+                ui.hint_default(),
+                "Create a new commit on top of it with `jj new`, or configure the set of \
+                 immutable commits via `revset-aliases.immutable_heads()`."
+            )?;
+        }
+
         // Ancestors with conflicts, excluding the current working copy commit.
         let ancestors_conflicts = workspace_command
             .attach_revset_evaluator(
@@ -99,9 +118,7 @@ pub(crate) fn cmd_status(
                     .parents()
                     .ancestors()
                     .filtered(RevsetFilterPredicate::HasConflict)
-                    .minus(&revset_util::parse_immutable_expression(
-                        &workspace_command.revset_parse_context(),
-                    )?),
+                    .minus(&immutable_revset),
             )?
             .evaluate_to_commit_ids()?
             .collect();
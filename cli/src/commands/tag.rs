@@ -12,9 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use jj_lib::backend::CommitId;
+use jj_lib::op_store::RefTarget;
+use jj_lib::revset::RevsetExpression;
 use jj_lib::str_util::StringPattern;
 
-use crate::cli_util::CommandHelper;
+use crate::cli_util::{CommandHelper, RevisionArg, WorkspaceCommandHelper};
 use crate::command_error::CommandError;
 use crate::commit_templater::{CommitTemplateLanguage, RefName};
 use crate::ui::Ui;
@@ -36,6 +42,21 @@ pub struct TagListArgs {
     /// https://github.com/martinvonz/jj/blob/main/docs/revsets.md#string-patterns.
     #[arg(value_parser = StringPattern::parse)]
     pub names: Vec<StringPattern>,
+    /// Show tags whose targets contain the given revisions
+    ///
+    /// A tag is listed if its target is a descendant of (or the same as) any
+    /// of the given revisions.
+    #[arg(long, value_name = "REVISIONS")]
+    contains: Vec<RevisionArg>,
+    /// Show tags whose targets are merged into the given revisions
+    ///
+    /// A tag is listed if its target is an ancestor of (or the same as) any of
+    /// the given revisions.
+    #[arg(long, value_name = "REVISIONS")]
+    merged: Vec<RevisionArg>,
+    /// Show tags whose targets are not merged into the given revisions
+    #[arg(long, value_name = "REVISIONS")]
+    no_merged: Vec<RevisionArg>,
     /// Render each tag using the given template
     ///
     /// All 0-argument methods of the `RefName` type are available as keywords.
@@ -64,6 +85,20 @@ fn cmd_tag_list(
     let repo = workspace_command.repo();
     let view = repo.view();
 
+    // Unlike names, revision filters are AND-ed.
+    let contains_targets = resolve_tag_targets(&workspace_command, &args.contains, |expression| {
+        expression.descendants()
+    })?;
+    let merged_targets = resolve_tag_targets(&workspace_command, &args.merged, |expression| {
+        expression.ancestors()
+    })?;
+    let no_merged_targets =
+        resolve_tag_targets(&workspace_command, &args.no_merged, |expression| {
+            expression.ancestors()
+        })?;
+    let has_any_target =
+        |ids: &HashSet<CommitId>, target: &RefTarget| target.added_ids().any(|id| ids.contains(id));
+
     let template = {
         let language = workspace_command.commit_template_language()?;
         let text = match &args.template {
@@ -82,9 +117,41 @@ fn cmd_tag_list(
         if !args.names.is_empty() && !args.names.iter().any(|pattern| pattern.matches(name)) {
             continue;
         }
+        if contains_targets
+            .as_ref()
+            .is_some_and(|ids| !has_any_target(ids, target))
+            || merged_targets
+                .as_ref()
+                .is_some_and(|ids| !has_any_target(ids, target))
+            || no_merged_targets
+                .as_ref()
+                .is_some_and(|ids| has_any_target(ids, target))
+        {
+            continue;
+        }
         let ref_name = RefName::local_only(name, target.clone());
         template.format(&ref_name, formatter.as_mut())?;
     }
 
     Ok(())
 }
+
+/// Evaluates the tag targets which are in `to_range(revisions)`. Returns
+/// `None` if no revisions are specified.
+fn resolve_tag_targets(
+    workspace_command: &WorkspaceCommandHelper,
+    revisions: &[RevisionArg],
+    to_range: impl FnOnce(&Rc<RevsetExpression>) -> Rc<RevsetExpression>,
+) -> Result<Option<HashSet<CommitId>>, CommandError> {
+    if revisions.is_empty() {
+        return Ok(None);
+    }
+    let expression = workspace_command.parse_union_revsets(revisions)?;
+    // Intersects with the set of tag targets to minimize the lookup space.
+    let expression = to_range(expression.expression()).intersection(&RevsetExpression::tags());
+    let ids = workspace_command
+        .attach_revset_evaluator(expression)?
+        .evaluate_to_commit_ids()?
+        .collect();
+    Ok(Some(ids))
+}
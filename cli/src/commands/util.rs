@@ -16,17 +16,21 @@ use std::io::Write;
 use std::slice;
 use std::time::{Duration, SystemTime};
 
-use clap::{Command, Subcommand};
+use clap::{Command, Subcommand, ValueEnum as _};
+use jj_lib::operation::Operation;
 use jj_lib::repo::Repo;
 use tracing::instrument;
 
-use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error, CommandError};
+use crate::cli_util::{parse_duration_arg, CommandHelper};
+use crate::command_error::{internal_error, user_error, CommandError};
+use crate::complete;
+use crate::config::{merged_config_schema, CONFIG_SCHEMA};
 use crate::ui::Ui;
 
 /// Infrequently used commands such as for generating shell completions
 #[derive(Subcommand, Clone, Debug)]
 pub(crate) enum UtilCommand {
+    Complete(UtilCompleteArgs),
     Completion(UtilCompletionArgs),
     Gc(UtilGcArgs),
     Mangen(UtilMangenArgs),
@@ -53,11 +57,19 @@ Apply it by running one of these:
      compinit
      source <(jj util completion zsh)
      ```
+
+With `--dynamic`, the Bash and Fish scripts also suggest branches and recent
+change ids for arguments taking revisions by querying the repository.
 "#]
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 pub(crate) struct UtilCompletionArgs {
     shell: Option<ShellCompletion>,
+    /// Complete revision arguments dynamically by querying the repository
+    ///
+    /// Only Bash and Fish are supported.
+    #[arg(long)]
+    dynamic: bool,
     /// Deprecated. Use the SHELL positional argument instead.
     #[arg(long, hide = true)]
     bash: bool,
@@ -69,6 +81,21 @@ pub(crate) struct UtilCompletionArgs {
     zsh: bool,
 }
 
+/// Print completion candidates for the word at the given index
+///
+/// This is called by the dynamic completion scripts. Nothing is printed if the
+/// word isn't a revision argument, or if there's no repository.
+#[derive(clap::Args, Clone, Debug)]
+#[command(hide = true)]
+pub(crate) struct UtilCompleteArgs {
+    /// Index of the word to complete
+    #[arg(long)]
+    index: usize,
+    /// Words of the command line, starting with the program name
+    #[arg(last = true)]
+    words: Vec<String>,
+}
+
 /// Run backend-dependent garbage collection.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct UtilGcArgs {
@@ -82,6 +109,14 @@ pub(crate) struct UtilGcArgs {
     /// release.
     #[arg(long)]
     expire: Option<String>,
+    /// Keep all operations recorded within the given duration
+    ///
+    /// Recent operations and their ancestors are kept even if they are no
+    /// longer reachable from the current operation, so the undo history
+    /// survives. The duration is a number followed by a unit: `s`, `m`, `h`,
+    /// `d`, or `w` (e.g. `30d`).
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration_arg)]
+    keep_operations: Option<Duration>,
 }
 
 /// Print a ROFF (manpage)
@@ -114,6 +149,7 @@ pub(crate) fn cmd_util(
     subcommand: &UtilCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        UtilCommand::Complete(args) => cmd_util_complete(ui, command, args),
         UtilCommand::Completion(args) => cmd_util_completion(ui, command, args),
         UtilCommand::Gc(args) => cmd_util_gc(ui, command, args),
         UtilCommand::Mangen(args) => cmd_util_mangen(ui, command, args),
@@ -122,6 +158,27 @@ pub(crate) fn cmd_util(
     }
 }
 
+fn cmd_util_complete(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &UtilCompleteArgs,
+) -> Result<(), CommandError> {
+    if !complete::is_revision_arg_at(command.app(), &args.words, args.index) {
+        return Ok(());
+    }
+    // Outside of a repository, print nothing so the script falls back to the
+    // static completion.
+    let Ok(workspace_command) = command.workspace_helper_no_snapshot(ui) else {
+        return Ok(());
+    };
+    let candidates = complete::revision_candidates(&workspace_command)?;
+    let mut formatter = ui.stdout_formatter();
+    for candidate in candidates {
+        writeln!(formatter, "{candidate}")?;
+    }
+    Ok(())
+}
+
 fn cmd_util_completion(
     ui: &mut Ui,
     command: &CommandHelper,
@@ -163,7 +220,16 @@ fn cmd_util_completion(
         }
     };
 
-    let buf = shell.generate(&mut app);
+    let mut buf = shell.generate(&mut app);
+    if args.dynamic {
+        let script = shell.dynamic_script().ok_or_else(|| {
+            user_error(format!(
+                "Dynamic completion isn't supported for {}",
+                shell.to_possible_value().unwrap().get_name()
+            ))
+        })?;
+        buf.extend_from_slice(script.as_bytes());
+    }
     ui.stdout_formatter().write_all(&buf)?;
     Ok(())
 }
@@ -183,12 +249,38 @@ fn cmd_util_gc(
         Some("now") => SystemTime::now() - Duration::ZERO,
         _ => return Err(user_error("--expire only accepts 'now'")),
     };
+    let keep_ops_newer = args.keep_operations.map(|duration| {
+        SystemTime::now()
+            .checked_sub(duration)
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    });
     let workspace_command = command.workspace_helper(ui)?;
 
     let repo = workspace_command.repo();
-    repo.op_store()
-        .gc(slice::from_ref(repo.op_id()), keep_newer)?;
-    repo.store().gc(repo.index(), keep_newer)?;
+    if let Some(keep_ops_newer) = keep_ops_newer {
+        let kept_op_ids = repo.op_store().gc_keeping_recent_ops(
+            slice::from_ref(repo.op_id()),
+            keep_newer,
+            keep_ops_newer,
+        )?;
+        // Commits referenced by the kept operations may not be indexed at the
+        // current operation.
+        let mut index = repo.readonly_index().start_modification();
+        for id in &kept_op_ids {
+            let data = repo.op_store().read_operation(id)?;
+            let op = Operation::new(repo.op_store().clone(), id.clone(), data);
+            let op_index = repo
+                .index_store()
+                .get_index_at_op(&op, repo.store())
+                .map_err(internal_error)?;
+            index.merge_in(op_index.as_ref());
+        }
+        repo.store().gc(index.as_index(), keep_newer)?;
+    } else {
+        repo.op_store()
+            .gc(slice::from_ref(repo.op_id()), keep_newer)?;
+        repo.store().gc(repo.index(), keep_newer)?;
+    }
     Ok(())
 }
 
@@ -218,12 +310,20 @@ fn cmd_util_markdownhelp(
 
 fn cmd_util_config_schema(
     ui: &mut Ui,
-    _command: &CommandHelper,
+    command: &CommandHelper,
     _args: &UtilConfigSchemaArgs,
 ) -> Result<(), CommandError> {
     // TODO(#879): Consider generating entire schema dynamically vs. static file.
-    let buf = include_bytes!("../config-schema.json");
-    ui.stdout_formatter().write_all(buf)?;
+    let fragments = command.config_schema_fragments();
+    if fragments.is_empty() {
+        ui.stdout_formatter().write_all(CONFIG_SCHEMA.as_bytes())?;
+    } else {
+        // Conflicts should have been detected when the fragments were registered.
+        let schema = merged_config_schema(fragments)
+            .map_err(|name| internal_error(format!("Conflicting config schema for '{name}'")))?;
+        let text = serde_json::to_string_pretty(&schema).map_err(internal_error)?;
+        writeln!(ui.stdout_formatter(), "{text}")?;
+    }
     Ok(())
 }
 
@@ -247,4 +347,41 @@ impl ShellCompletion {
 
         buf
     }
+
+    /// Script to be appended to the static completion script to complete
+    /// revision arguments by `jj util complete`.
+    fn dynamic_script(&self) -> Option<&'static str> {
+        match self {
+            Self::Bash => Some(BASH_DYNAMIC_SCRIPT),
+            Self::Fish => Some(FISH_DYNAMIC_SCRIPT),
+            Self::Elvish | Self::Nushell | Self::PowerShell | Self::Zsh => None,
+        }
+    }
 }
+
+const BASH_DYNAMIC_SCRIPT: &str = r#"
+_jj_dynamic() {
+    local candidates
+    candidates=$(jj util complete --index "${COMP_CWORD}" -- "${COMP_WORDS[@]}" 2>/dev/null)
+    if [[ -n "${candidates}" ]]; then
+        local IFS=$'\n'
+        COMPREPLY=( $(compgen -W "${candidates}" -- "${COMP_WORDS[COMP_CWORD]}") )
+        return 0
+    fi
+    _jj "$@"
+}
+
+if [[ "${BASH_VERSINFO[0]}" -eq 4 && "${BASH_VERSINFO[1]}" -ge 4 || "${BASH_VERSINFO[0]}" -gt 4 ]]; then
+    complete -F _jj_dynamic -o nosort -o bashdefault -o default jj
+else
+    complete -F _jj_dynamic -o bashdefault -o default jj
+fi
+"#;
+
+const FISH_DYNAMIC_SCRIPT: &str = r#"
+function __jj_dynamic_candidates
+    set -l words (commandline -opc) (commandline -ct)
+    jj util complete --index (math (count $words) - 1) -- $words 2>/dev/null
+end
+complete -c jj -a '(__jj_dynamic_candidates)'
+"#;
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::Write;
+use std::io::{self, Write};
 
+use chrono::{DateTime, SecondsFormat};
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -22,14 +23,59 @@ use crate::ui::Ui;
 
 /// Display version information
 #[derive(clap::Args, Clone, Debug)]
-pub(crate) struct VersionArgs {}
+pub(crate) struct VersionArgs {
+    /// Also print the commit jj was built from, the build date, and the
+    /// enabled Cargo features
+    ///
+    /// This is useful for bug reports.
+    #[arg(long)]
+    build_info: bool,
+}
 
 #[instrument(skip_all)]
 pub(crate) fn cmd_version(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &VersionArgs,
+    args: &VersionArgs,
 ) -> Result<(), CommandError> {
     write!(ui.stdout(), "{}", command.app().render_version())?;
+    if args.build_info {
+        print_build_info(ui)?;
+    }
+    Ok(())
+}
+
+fn print_build_info(ui: &Ui) -> io::Result<()> {
+    let git_hash = env!("JJ_BUILD_GIT_HASH");
+    let build_date = env!("JJ_BUILD_TIMESTAMP")
+        .parse()
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .map(|date| date.to_rfc3339_opts(SecondsFormat::Secs, true));
+    let features = env!("JJ_BUILD_FEATURES");
+    let mut stdout = ui.stdout();
+    writeln!(
+        stdout,
+        "commit: {}",
+        if git_hash.is_empty() {
+            "unknown"
+        } else {
+            git_hash
+        }
+    )?;
+    writeln!(
+        stdout,
+        "build date: {}",
+        build_date.as_deref().unwrap_or("unknown")
+    )?;
+    writeln!(
+        stdout,
+        "features: {}",
+        if features.is_empty() {
+            "(none)"
+        } else {
+            features
+        }
+    )?;
     Ok(())
 }
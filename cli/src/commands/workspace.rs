@@ -87,8 +87,9 @@ pub(crate) struct WorkspaceAddArgs {
 
 /// Stop tracking a workspace's working-copy commit in the repo
 ///
-/// The workspace will not be touched on disk. It can be deleted from disk
-/// before or after running this command.
+/// The workspace will not be touched on disk, and the files in it will no
+/// longer be tracked. It can be deleted from disk before or after running this
+/// command.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct WorkspaceForgetArgs {
     /// Names of the workspaces to forget. By default, forgets only the current
@@ -106,6 +107,9 @@ pub(crate) struct WorkspaceRootArgs {}
 
 /// Update a workspace that has become stale
 ///
+/// The paths of the other workspaces aren't recorded in the repo, so use e.g.
+/// `jj -R <path> workspace update-stale` to update the workspace at `<path>`.
+///
 /// For information about stale working copies, see
 /// https://github.com/martinvonz/jj/blob/main/docs/working-copy.md.
 #[derive(clap::Args, Clone, Debug)]
@@ -270,6 +274,15 @@ fn cmd_workspace_forget(
     };
 
     tx.finish(ui, description)?;
+    writeln!(
+        ui.warning_default(),
+        "Files in the forgotten {} are left on disk, but are no longer tracked.",
+        if wss.len() > 1 {
+            "workspaces"
+        } else {
+            "workspace"
+        }
+    )?;
     Ok(())
 }
 
@@ -13,24 +13,30 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::cmp::max;
 use std::collections::HashMap;
-use std::io;
+use std::io::{self, Write as _};
 use std::rc::Rc;
 
 use itertools::Itertools as _;
 use jj_lib::backend::{ChangeId, CommitId};
 use jj_lib::commit::Commit;
+use jj_lib::copy_detection::DetectedCopies;
 use jj_lib::extensions_map::ExtensionsMap;
 use jj_lib::git;
 use jj_lib::hex_util::to_reverse_hex;
 use jj_lib::id_prefix::IdPrefixContext;
+use jj_lib::matchers::EverythingMatcher;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::{RefTarget, RemoteRef, WorkspaceId};
 use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::revset::{self, Revset, RevsetExpression, RevsetModifier, RevsetParseContext};
+use jj_lib::settings::UserSettings;
 use once_cell::unsync::OnceCell;
 
+use crate::formatter::PlainTextFormatter;
 use crate::template_builder::{
     self, merge_fn_map, BuildContext, CoreTemplateBuildFnTable, CoreTemplatePropertyKind,
     IntoTemplateProperty, TemplateBuildMethodFnMap, TemplateLanguage,
@@ -40,7 +46,7 @@ use crate::templater::{
     self, PlainTextFormattedProperty, SizeHint, Template, TemplateFormatter, TemplateProperty,
     TemplatePropertyError, TemplatePropertyExt as _,
 };
-use crate::{revset_util, text_util};
+use crate::{diff_util, revset_util, text_util};
 
 pub trait CommitTemplateLanguageExtension {
     fn build_fn_table<'repo>(&self) -> CommitTemplateBuildFnTable<'repo>;
@@ -50,6 +56,7 @@ pub trait CommitTemplateLanguageExtension {
 
 pub struct CommitTemplateLanguage<'repo> {
     repo: &'repo dyn Repo,
+    settings: &'repo UserSettings,
     workspace_id: WorkspaceId,
     // RevsetParseContext doesn't borrow a repo, but we'll need 'repo lifetime
     // anyway to capture it to evaluate dynamically-constructed user expression
@@ -58,6 +65,7 @@ pub struct CommitTemplateLanguage<'repo> {
     // RevsetParseContext for example.
     revset_parse_context: RevsetParseContext<'repo>,
     id_prefix_context: &'repo IdPrefixContext,
+    path_converter: &'repo RepoPathUiConverter,
     build_fn_table: CommitTemplateBuildFnTable<'repo>,
     keyword_cache: CommitKeywordCache<'repo>,
     cache_extensions: ExtensionsMap,
@@ -68,9 +76,11 @@ impl<'repo> CommitTemplateLanguage<'repo> {
     /// evaluation tree.
     pub fn new(
         repo: &'repo dyn Repo,
+        settings: &'repo UserSettings,
         workspace_id: &WorkspaceId,
         revset_parse_context: RevsetParseContext<'repo>,
         id_prefix_context: &'repo IdPrefixContext,
+        path_converter: &'repo RepoPathUiConverter,
         extensions: &[impl AsRef<dyn CommitTemplateLanguageExtension>],
     ) -> Self {
         let mut build_fn_table = CommitTemplateBuildFnTable::builtin();
@@ -85,9 +95,11 @@ impl<'repo> CommitTemplateLanguage<'repo> {
 
         CommitTemplateLanguage {
             repo,
+            settings,
             workspace_id: workspace_id.clone(),
             revset_parse_context,
             id_prefix_context,
+            path_converter,
             build_fn_table,
             keyword_cache: CommitKeywordCache::default(),
             cache_extensions,
@@ -100,6 +112,10 @@ impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
 
     template_builder::impl_core_wrap_property_fns!('repo, CommitTemplatePropertyKind::Core);
 
+    fn settings(&self) -> &UserSettings {
+        self.settings
+    }
+
     fn build_function(
         &self,
         build_ctx: &BuildContext<Self::Property>,
@@ -397,6 +413,7 @@ pub struct CommitKeywordCache<'repo> {
     tags_index: OnceCell<Rc<RefNamesIndex>>,
     git_refs_index: OnceCell<Rc<RefNamesIndex>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
+    diffs: Rc<CommitDiffCache>,
 }
 
 impl<'repo> CommitKeywordCache<'repo> {
@@ -425,6 +442,74 @@ impl<'repo> CommitKeywordCache<'repo> {
             Ok(revset.containing_fn().into())
         })
     }
+
+    pub fn diffs(&self) -> &Rc<CommitDiffCache> {
+        &self.diffs
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum CommitDiffKind {
+    Summary,
+    Stat { width: usize },
+}
+
+/// Rendered diffs of commits against their first parents.
+#[derive(Default)]
+pub struct CommitDiffCache {
+    rendered: RefCell<HashMap<(CommitId, CommitDiffKind), String>>,
+}
+
+impl CommitDiffCache {
+    fn get_or_render(
+        &self,
+        repo: &dyn Repo,
+        path_converter: &RepoPathUiConverter,
+        commit: &Commit,
+        kind: CommitDiffKind,
+    ) -> Result<String, TemplatePropertyError> {
+        let key = (commit.id().clone(), kind);
+        if let Some(text) = self.rendered.borrow().get(&key) {
+            return Ok(text.clone());
+        }
+        let text = render_commit_diff(repo, path_converter, commit, kind)?;
+        self.rendered.borrow_mut().insert(key, text.clone());
+        Ok(text)
+    }
+}
+
+/// Renders the diff of the `commit` against its first parent. The root commit
+/// has no parent, so the output is empty.
+fn render_commit_diff(
+    repo: &dyn Repo,
+    path_converter: &RepoPathUiConverter,
+    commit: &Commit,
+    kind: CommitDiffKind,
+) -> Result<String, TemplatePropertyError> {
+    let mut parents = commit.parents();
+    let Some(first_parent) = parents.next().transpose()? else {
+        return Ok(String::new());
+    };
+    let is_merge = parents.next().is_some();
+    let from_tree = first_parent.tree()?;
+    let to_tree = commit.tree()?;
+    let matcher = EverythingMatcher;
+    let mut output = Vec::new();
+    let formatter = &mut PlainTextFormatter::new(&mut output);
+    if is_merge {
+        writeln!(formatter, "(diff against the first parent)")?;
+    }
+    let tree_diff = from_tree.diff_stream(&to_tree, &matcher);
+    match kind {
+        CommitDiffKind::Summary => {
+            let copies = DetectedCopies::default();
+            diff_util::show_diff_summary(formatter, tree_diff, path_converter, &copies)?;
+        }
+        CommitDiffKind::Stat { width } => {
+            diff_util::show_diff_stat(repo, formatter, tree_diff, path_converter, width, None)?;
+        }
+    }
+    Ok(String::from_utf8(output).expect("diff output should be utf-8 bytes"))
 }
 
 fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Commit> {
@@ -468,6 +553,15 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(L::wrap_commit_list(out_property))
         },
     );
+    map.insert(
+        "predecessors",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property =
+                self_property.and_then(|commit| Ok(commit.predecessors().try_collect()?));
+            Ok(L::wrap_commit_list(out_property))
+        },
+    );
     map.insert(
         "author",
         |_language, _build_ctx, self_property, function| {
@@ -651,6 +745,53 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
         let out_property = self_property.map(|commit| commit.id() == repo.store().root_commit_id());
         Ok(L::wrap_boolean(out_property))
     });
+    map.insert(
+        "generation",
+        |language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let index = language.repo.index();
+            let out_property = self_property.and_then(|commit| {
+                let generation = index.generation_number(commit.id()).ok_or_else(|| {
+                    let message = "Generation number is not available";
+                    TemplatePropertyError(message.into())
+                })?;
+                Ok(i64::from(generation))
+            });
+            Ok(L::wrap_integer(out_property))
+        },
+    );
+    map.insert(
+        "diff_summary",
+        |language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let path_converter = language.path_converter;
+            let cache = language.keyword_cache.diffs().clone();
+            let out_property = self_property.and_then(move |commit| {
+                cache.get_or_render(repo, path_converter, &commit, CommitDiffKind::Summary)
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
+    map.insert(
+        "diff_stats",
+        |language, build_ctx, self_property, function| {
+            let ([], [width_node]) = function.expect_arguments()?;
+            let width_property = width_node
+                .map(|node| template_builder::expect_usize_expression(language, build_ctx, node))
+                .transpose()?;
+            let repo = language.repo;
+            let path_converter = language.path_converter;
+            let cache = language.keyword_cache.diffs().clone();
+            let out_property = (self_property, width_property).and_then(move |(commit, width)| {
+                let kind = CommitDiffKind::Stat {
+                    width: width.unwrap_or(80),
+                };
+                cache.get_or_render(repo, path_converter, &commit, kind)
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
     map
 }
 
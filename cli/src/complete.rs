@@ -0,0 +1,142 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dynamic shell completion of revision arguments.
+//!
+//! The static completion scripts generated by `clap_complete` can't know about
+//! the repository content. The dynamic scripts call back into `jj` to look up
+//! candidates for arguments taking revisions, and fall back to the static
+//! completion otherwise.
+
+use clap::builder::{ValueParser, ValueParserFactory as _};
+use clap::{Arg, Command};
+
+use crate::cli_util::{short_change_hash, RevisionArg, WorkspaceCommandHelper};
+use crate::command_error::CommandError;
+
+/// Number of commits in the default log revset to suggest.
+const RECENT_COMMITS_LIMIT: usize = 20;
+
+/// Returns true if the word at `index` is a value of an argument taking
+/// revisions.
+///
+/// The `words` should start with the program name. Since this is only a hint
+/// for completion, unknown flags and aliases are treated leniently.
+pub fn is_revision_arg_at(app: &Command, words: &[String], index: usize) -> bool {
+    let mut app = app.clone();
+    app.build();
+    let mut cmd = &app;
+    let mut pending_arg: Option<&Arg> = None;
+    let mut positional_count = 0;
+    let mut escaped = false;
+    for word in words.get(1..index).unwrap_or_default() {
+        if pending_arg.take().is_some() {
+            continue;
+        }
+        if !escaped {
+            if word == "--" {
+                escaped = true;
+                continue;
+            } else if let Some(long) = word.strip_prefix("--") {
+                if !long.contains('=') {
+                    pending_arg = find_long_arg(cmd, long).filter(|arg| takes_value(arg));
+                }
+                continue;
+            } else if let Some(shorts) = word.strip_prefix('-').filter(|s| !s.is_empty()) {
+                for (pos, c) in shorts.char_indices() {
+                    if let Some(arg) = find_short_arg(cmd, c).filter(|arg| takes_value(arg)) {
+                        // "-rREV" has the value attached
+                        if pos + c.len_utf8() == shorts.len() {
+                            pending_arg = Some(arg);
+                        }
+                        break;
+                    }
+                }
+                continue;
+            } else if let Some(subcommand) = cmd.find_subcommand(word) {
+                cmd = subcommand;
+                positional_count = 0;
+                continue;
+            }
+        }
+        positional_count += 1;
+    }
+
+    let current = words.get(index).map_or("", |s| s.as_str());
+    let arg = if pending_arg.is_some() {
+        pending_arg
+    } else if !escaped && current.starts_with('-') {
+        None
+    } else {
+        find_positional_arg(cmd, positional_count)
+    };
+    arg.is_some_and(is_revision_arg)
+}
+
+/// Lists local branch names and change ids of recent commits.
+pub fn revision_candidates(
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<Vec<String>, CommandError> {
+    let repo = workspace_command.repo();
+    let mut candidates: Vec<String> = repo
+        .view()
+        .local_branches()
+        .map(|(name, _)| name.to_owned())
+        .collect();
+    let commits = workspace_command
+        .parse_default_revset()?
+        .evaluate_to_commits()?;
+    for commit in commits.take(RECENT_COMMITS_LIMIT) {
+        candidates.push(short_change_hash(commit?.change_id()));
+    }
+    Ok(candidates)
+}
+
+fn find_long_arg<'a>(cmd: &'a Command, name: &str) -> Option<&'a Arg> {
+    cmd.get_arguments().find(|arg| {
+        arg.get_long_and_visible_aliases()
+            .is_some_and(|names| names.contains(&name))
+    })
+}
+
+fn find_short_arg(cmd: &Command, c: char) -> Option<&Arg> {
+    cmd.get_arguments().find(|arg| {
+        arg.get_short_and_visible_aliases()
+            .is_some_and(|names| names.contains(&c))
+    })
+}
+
+/// Finds the positional argument which the `n`-th positional value belongs
+/// to.
+fn find_positional_arg(cmd: &Command, mut n: usize) -> Option<&Arg> {
+    for arg in cmd.get_positionals() {
+        let multiple = arg
+            .get_num_args()
+            .is_some_and(|range| range.max_values() > 1);
+        if multiple || n == 0 {
+            return Some(arg);
+        }
+        n -= 1;
+    }
+    None
+}
+
+fn takes_value(arg: &Arg) -> bool {
+    arg.get_action().takes_values()
+}
+
+fn is_revision_arg(arg: &Arg) -> bool {
+    let revision_parser = ValueParser::new(RevisionArg::value_parser());
+    arg.get_value_parser().type_id() == revision_parser.type_id()
+}
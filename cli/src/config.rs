@@ -176,6 +176,7 @@ pub enum ConfigSource {
     // TODO: Track explicit file paths, especially for when user config is a dir.
     User,
     Repo,
+    Workspace,
     CommandArg,
 }
 
@@ -194,7 +195,7 @@ pub struct AnnotatedValue {
 /// 2. Base environment variables
 /// 3. [User config](https://github.com/martinvonz/jj/blob/main/docs/config.md#configuration)
 /// 4. Repo config `.jj/repo/config.toml`
-/// 5. TODO: Workspace config `.jj/config.toml`
+/// 5. Workspace config `.jj/config.toml`
 /// 6. Override environment variables
 /// 7. Command-line arguments `--config-toml`
 #[derive(Clone, Debug)]
@@ -203,6 +204,7 @@ pub struct LayeredConfigs {
     env_base: config::Config,
     user: Option<config::Config>,
     repo: Option<config::Config>,
+    workspace: Option<config::Config>,
     env_overrides: config::Config,
     arg_overrides: Option<config::Config>,
 }
@@ -215,6 +217,7 @@ impl LayeredConfigs {
             env_base: env_base(),
             user: None,
             repo: None,
+            workspace: None,
             env_overrides: env_overrides(),
             arg_overrides: None,
         }
@@ -242,6 +245,18 @@ impl LayeredConfigs {
         repo_path.join("config.toml")
     }
 
+    #[instrument]
+    pub fn read_workspace_config(&mut self, workspace_root: &Path) -> Result<(), ConfigError> {
+        self.workspace = Some(read_config_file(
+            &self.workspace_config_path(workspace_root),
+        )?);
+        Ok(())
+    }
+
+    pub fn workspace_config_path(&self, workspace_root: &Path) -> PathBuf {
+        workspace_root.join(".jj").join("config.toml")
+    }
+
     pub fn parse_config_args(&mut self, toml_strs: &[String]) -> Result<(), ConfigError> {
         let config = toml_strs
             .iter()
@@ -271,6 +286,7 @@ impl LayeredConfigs {
             (ConfigSource::Env, Some(&self.env_base)),
             (ConfigSource::User, self.user.as_ref()),
             (ConfigSource::Repo, self.repo.as_ref()),
+            (ConfigSource::Workspace, self.workspace.as_ref()),
             (ConfigSource::Env, Some(&self.env_overrides)),
             (ConfigSource::CommandArg, self.arg_overrides.as_ref()),
         ];
@@ -370,6 +386,15 @@ fn create_config_file(path: &Path) -> std::io::Result<std::fs::File> {
         .open(path)
 }
 
+/// Creates an empty config file at `path` if nothing exists there yet.
+pub fn create_config_file_if_missing(path: &Path) -> std::io::Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    create_config_file(path)?;
+    Ok(())
+}
+
 // The struct exists so that we can mock certain global values in unit tests.
 #[derive(Clone, Default, Debug)]
 struct ConfigEnv {
@@ -445,13 +470,28 @@ pub fn new_config_path() -> Result<Option<PathBuf>, ConfigError> {
     ConfigEnv::new().new_config_path()
 }
 
+/// Returns the `ui.color` value implied by the `CLICOLOR_FORCE` and `NO_COLOR`
+/// environment variables.
+///
+/// "User-level configuration files and per-instance command-line arguments
+/// should override $NO_COLOR." https://no-color.org/ The same applies to
+/// `CLICOLOR_FORCE`, which takes precedence over `NO_COLOR`.
+fn color_choice_from_env() -> Option<&'static str> {
+    let non_empty_var = |name| env::var_os(name).filter(|value| !value.is_empty());
+    if non_empty_var("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+        Some("always")
+    } else if non_empty_var("NO_COLOR").is_some() {
+        Some("never")
+    } else {
+        None
+    }
+}
+
 /// Environment variables that should be overridden by config values
 fn env_base() -> config::Config {
     let mut builder = config::Config::builder();
-    if env::var("NO_COLOR").is_ok() {
-        // "User-level configuration files and per-instance command-line arguments
-        // should override $NO_COLOR." https://no-color.org/
-        builder = builder.set_override("ui.color", "never").unwrap();
+    if let Some(choice) = color_choice_from_env() {
+        builder = builder.set_override("ui.color", choice).unwrap();
     }
     if let Ok(value) = env::var("PAGER") {
         builder = builder.set_override("ui.pager", value).unwrap();
@@ -629,6 +669,209 @@ pub fn write_config_value_to_file(
     })
 }
 
+/// JSON schema of the built-in config options.
+pub const CONFIG_SCHEMA: &str = include_str!("config-schema.json");
+
+/// Returns the JSON schema of the built-in config options merged with the
+/// `fragments` registered by extensions.
+///
+/// Each fragment is a JSON schema object whose `properties` are merged into
+/// the built-in schema. Returns the dotted name of the property if a fragment
+/// redefines an existing option.
+pub fn merged_config_schema(fragments: &[serde_json::Value]) -> Result<serde_json::Value, String> {
+    let mut schema: serde_json::Value =
+        serde_json::from_str(CONFIG_SCHEMA).expect("built-in config schema should be valid");
+    for fragment in fragments {
+        merge_schema_properties(&mut schema, fragment, "")?;
+    }
+    Ok(schema)
+}
+
+fn merge_schema_properties(
+    schema: &mut serde_json::Value,
+    fragment: &serde_json::Value,
+    prefix: &str,
+) -> Result<(), String> {
+    let Some(fragment_properties) = fragment.get("properties").and_then(|v| v.as_object()) else {
+        return Ok(());
+    };
+    let properties = schema
+        .as_object_mut()
+        .ok_or_else(|| prefix.to_owned())?
+        .entry("properties")
+        .or_insert_with(|| serde_json::Value::Object(Default::default()))
+        .as_object_mut()
+        .ok_or_else(|| prefix.to_owned())?;
+    for (name, fragment_property) in fragment_properties {
+        let path = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        match properties.get_mut(name) {
+            None => {
+                properties.insert(name.clone(), fragment_property.clone());
+            }
+            // Tables can be extended, but other options can't be redefined.
+            Some(property)
+                if property.get("properties").is_some()
+                    && fragment_property.get("properties").is_some() =>
+            {
+                merge_schema_properties(property, fragment_property, &path)?;
+            }
+            Some(_) => return Err(path),
+        }
+    }
+    Ok(())
+}
+
+/// Config options which were renamed, as pairs of old and new names.
+const RENAMED_CONFIG_OPTIONS: &[(&str, &str)] = &[
+    ("alias", "aliases"),
+    ("diff.format", "ui.diff.format"),
+    ("ui.default-revset", "revsets.log"),
+];
+
+/// Result of migrating a deprecated config option.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigMigration {
+    /// The option was renamed to the new name.
+    Renamed {
+        old_name: &'static str,
+        new_name: &'static str,
+    },
+    /// The option wasn't renamed because the new name is already set.
+    Conflicted {
+        old_name: &'static str,
+        new_name: &'static str,
+    },
+}
+
+/// Rewrites deprecated config options in the file at `path` to their new
+/// names. The file isn't written if `dry_run` is true.
+///
+/// Options already in the new form are left untouched. Comments and formatting
+/// of the other options are preserved.
+pub fn migrate_config_file(
+    path: &Path,
+    dry_run: bool,
+) -> Result<Vec<ConfigMigration>, CommandError> {
+    let config_toml = std::fs::read_to_string(path).map_err(|err| {
+        user_error_with_message(format!("Failed to read file {}", path.display()), err)
+    })?;
+    let mut doc: toml_edit::Document = config_toml.parse().map_err(|err| {
+        user_error_with_message(format!("Failed to parse file {}", path.display()), err)
+    })?;
+
+    let mut migrations = vec![];
+    for &(old_name, new_name) in RENAMED_CONFIG_OPTIONS {
+        let old_key = old_name.split('.').collect_vec();
+        let new_key = new_name.split('.').collect_vec();
+        if get_toml_item(doc.as_table(), &old_key).is_none() {
+            continue;
+        }
+        if get_toml_item(doc.as_table(), &new_key).is_some() {
+            migrations.push(ConfigMigration::Conflicted { old_name, new_name });
+            continue;
+        }
+        let (item, decor) = remove_toml_item(doc.as_table_mut(), &old_key).unwrap();
+        if !insert_toml_item(doc.as_table_mut(), &new_key, item, decor) {
+            return Err(user_error(format!(
+                "Failed to rename {old_name} to {new_name}: would overwrite non-table value with \
+                 parent table"
+            )));
+        }
+        migrations.push(ConfigMigration::Renamed { old_name, new_name });
+    }
+
+    let renamed = migrations
+        .iter()
+        .any(|migration| matches!(migration, ConfigMigration::Renamed { .. }));
+    if renamed && !dry_run {
+        let mut new_toml = doc.to_string();
+        if !config_toml.starts_with(char::is_whitespace) {
+            // Removing the first option may leave a blank line at the top.
+            new_toml = new_toml.trim_start().to_owned();
+        }
+        std::fs::write(path, new_toml).map_err(|err| {
+            user_error_with_message(format!("Failed to write file {}", path.display()), err)
+        })?;
+    }
+    Ok(migrations)
+}
+
+fn get_toml_item<'a>(
+    table: &'a dyn toml_edit::TableLike,
+    key: &[&str],
+) -> Option<&'a toml_edit::Item> {
+    let (first, rest) = key.split_first()?;
+    let item = table.get(first)?;
+    if rest.is_empty() {
+        Some(item)
+    } else {
+        get_toml_item(item.as_table_like()?, rest)
+    }
+}
+
+/// Removes the item at `key`, and the parent tables which become empty.
+/// Returns the item and the decor (e.g. comments) of its key.
+fn remove_toml_item(
+    table: &mut dyn toml_edit::TableLike,
+    key: &[&str],
+) -> Option<(toml_edit::Item, toml_edit::Decor)> {
+    let (first, rest) = key.split_first()?;
+    let key_decor = table.key_decor(first).cloned().unwrap_or_default();
+    if rest.is_empty() {
+        let item = table.remove(first)?;
+        return Some((item, key_decor));
+    }
+    let child = table.get_mut(first)?.as_table_like_mut()?;
+    let (item, mut decor) = remove_toml_item(child, rest)?;
+    if child.is_empty() {
+        table.remove(first);
+        // Comments on a dotted key belong to its first component.
+        if !has_comment(&decor) {
+            decor = key_decor;
+        }
+    }
+    Some((item, decor))
+}
+
+fn has_comment(decor: &toml_edit::Decor) -> bool {
+    decor
+        .prefix()
+        .and_then(|prefix| prefix.as_str())
+        .is_some_and(|prefix| !prefix.trim().is_empty())
+}
+
+/// Inserts the `item` at `key` with the comments in the given key `decor`,
+/// creating the parent tables as needed. Returns false if a parent is not a
+/// table.
+fn insert_toml_item(
+    table: &mut dyn toml_edit::TableLike,
+    key: &[&str],
+    item: toml_edit::Item,
+    decor: toml_edit::Decor,
+) -> bool {
+    let (first, rest) = key.split_first().expect("key must not be empty");
+    if rest.is_empty() {
+        table.insert(first, item);
+        if let (Some(key_decor), Some(prefix)) = (table.key_decor_mut(first), decor.prefix()) {
+            key_decor.set_prefix(prefix.clone());
+        }
+        return true;
+    }
+    let child = table.entry(first).or_insert_with(|| {
+        let mut child = toml_edit::Table::new();
+        child.set_implicit(true);
+        toml_edit::Item::Table(child)
+    });
+    match child.as_table_like_mut() {
+        Some(child) => insert_toml_item(child, rest, item, decor),
+        None => false,
+    }
+}
+
 /// Command name and arguments specified by config.
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
 #[serde(untagged)]
@@ -768,6 +1011,7 @@ impl TryFrom<Vec<String>> for NonEmptyCommandArgsVec {
 #[cfg(test)]
 mod tests {
     use maplit::hashmap;
+    use serde_json::json;
 
     use super::*;
 
@@ -880,6 +1124,7 @@ mod tests {
             env_base: empty_config.to_owned(),
             user: None,
             repo: None,
+            workspace: None,
             env_overrides: empty_config,
             arg_overrides: None,
         };
@@ -911,6 +1156,7 @@ mod tests {
             env_base: env_base_config,
             user: None,
             repo: Some(repo_config),
+            workspace: None,
             env_overrides: empty_config,
             arg_overrides: None,
         };
@@ -1034,6 +1280,7 @@ mod tests {
             env_base: empty_config.to_owned(),
             user: Some(user_config),
             repo: Some(repo_config),
+            workspace: None,
             env_overrides: empty_config,
             arg_overrides: None,
         };
@@ -1108,6 +1355,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merged_config_schema() {
+        let property = |schema: &serde_json::Value, path: &[&str]| {
+            path.iter()
+                .try_fold(schema, |schema, name| schema.get("properties")?.get(name))
+                .cloned()
+        };
+
+        // New table and new option in an existing table
+        let schema = merged_config_schema(&[json!({
+            "properties": {
+                "frob": {
+                    "type": "object",
+                    "properties": {"level": {"type": "integer"}},
+                },
+                "ui": {
+                    "properties": {"frob-color": {"type": "string"}},
+                },
+            },
+        })])
+        .unwrap();
+        assert_eq!(
+            property(&schema, &["frob", "level"]),
+            Some(json!({"type": "integer"}))
+        );
+        assert_eq!(
+            property(&schema, &["ui", "frob-color"]),
+            Some(json!({"type": "string"}))
+        );
+        // Existing options are preserved
+        assert!(property(&schema, &["ui", "color"]).is_some());
+
+        // Redefining an existing option
+        assert_eq!(
+            merged_config_schema(&[json!({
+                "properties": {"ui": {"properties": {"color": {"type": "string"}}}},
+            })]),
+            Err("ui.color".to_owned())
+        );
+        // Redefining a table as a scalar
+        assert_eq!(
+            merged_config_schema(&[json!({"properties": {"ui": {"type": "string"}}})]),
+            Err("ui".to_owned())
+        );
+        // Conflicts between fragments
+        let fragment = json!({"properties": {"frob": {"type": "string"}}});
+        assert_eq!(
+            merged_config_schema(&[fragment.clone(), fragment]),
+            Err("frob".to_owned())
+        );
+    }
+
     #[test]
     fn test_config_path_home_dir_existing() -> anyhow::Result<()> {
         TestCase {
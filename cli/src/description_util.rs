@@ -1,12 +1,15 @@
+use std::collections::HashMap;
+
 use itertools::Itertools;
+use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::matchers::EverythingMatcher;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::settings::UserSettings;
 
-use crate::cli_util::{edit_temp_file, WorkspaceCommandHelper};
-use crate::command_error::CommandError;
+use crate::cli_util::{edit_temp_file, short_commit_hash, WorkspaceCommandHelper};
+use crate::command_error::{user_error, CommandError};
 use crate::diff_util::DiffFormat;
 use crate::formatter::PlainTextFormatter;
 use crate::text_util;
@@ -40,6 +43,105 @@ JJ: Lines starting with "JJ: " (like this one) will be removed.
     Ok(text_util::complete_newline(description.trim_matches('\n')))
 }
 
+/// Descriptions parsed from the text edited by
+/// [`edit_multiple_descriptions()`].
+#[derive(Clone, Debug, Default)]
+pub struct ParsedBulkDescriptions {
+    /// New descriptions of the commits found in the text.
+    pub descriptions: HashMap<CommitId, String>,
+    /// Commits whose sections were deleted from the text.
+    pub missing: Vec<CommitId>,
+}
+
+/// Edits the descriptions of multiple commits in a single editor buffer.
+///
+/// Each of the `templates` is a pair of commit ID and the initial text to
+/// edit. The descriptions are separated by `JJ: describe <commit>` lines.
+pub fn edit_multiple_descriptions(
+    repo: &ReadonlyRepo,
+    templates: &[(CommitId, String)],
+    settings: &UserSettings,
+) -> Result<ParsedBulkDescriptions, CommandError> {
+    let mut bulk_message = String::new();
+    bulk_message.push_str(
+        "JJ: Enter or edit commit descriptions after the `JJ: describe` lines.\n\
+         JJ: If a section is deleted, the description of the commit is left unchanged.\n",
+    );
+    for (commit_id, template) in templates {
+        bulk_message.push_str(&format!(
+            "\nJJ: describe {} -------\n",
+            short_commit_hash(commit_id)
+        ));
+        bulk_message.push_str(&text_util::complete_newline(template.as_str()));
+    }
+    bulk_message.push_str("\nJJ: Lines starting with \"JJ: \" (like this one) will be removed.\n");
+
+    let bulk_message = edit_temp_file(
+        "description",
+        ".jjdescription",
+        repo.repo_path(),
+        &bulk_message,
+        settings,
+    )?;
+    let commit_ids = templates
+        .iter()
+        .map(|(commit_id, _)| commit_id)
+        .collect_vec();
+    parse_bulk_edit_message(&bulk_message, &commit_ids)
+}
+
+fn parse_bulk_edit_message(
+    message: &str,
+    commit_ids: &[&CommitId],
+) -> Result<ParsedBulkDescriptions, CommandError> {
+    let commit_ids_by_hash: HashMap<_, _> = commit_ids
+        .iter()
+        .map(|&commit_id| (short_commit_hash(commit_id), commit_id))
+        .collect();
+    let mut sections: Vec<(&CommitId, Vec<&str>)> = vec![];
+    for line in message.lines() {
+        if let Some(header) = line.strip_prefix("JJ: describe ") {
+            let hash = header.split_whitespace().next().unwrap_or_default();
+            let commit_id = commit_ids_by_hash
+                .get(hash)
+                .ok_or_else(|| user_error(format!("Found unexpected commit {hash}")))?;
+            if sections.iter().any(|(id, _)| id == commit_id) {
+                return Err(user_error(format!(
+                    "Found multiple descriptions for commit {hash}"
+                )));
+            }
+            sections.push((commit_id, vec![]));
+        } else if line.starts_with("JJ: ") {
+            continue;
+        } else if let Some((_, lines)) = sections.last_mut() {
+            lines.push(line);
+        } else if !line.trim().is_empty() {
+            return Err(user_error(format!(
+                "Found the following line without a commit header: \"{line}\""
+            )));
+        }
+    }
+
+    let descriptions: HashMap<_, _> = sections
+        .into_iter()
+        .map(|(commit_id, lines)| {
+            // Normalize line ending, remove leading and trailing blank lines.
+            let description = lines.join("\n");
+            let description = text_util::complete_newline(description.trim_matches('\n'));
+            (commit_id.clone(), description)
+        })
+        .collect();
+    let missing = commit_ids
+        .iter()
+        .filter(|commit_id| !descriptions.contains_key(commit_id))
+        .map(|&commit_id| commit_id.clone())
+        .collect();
+    Ok(ParsedBulkDescriptions {
+        descriptions,
+        missing,
+    })
+}
+
 /// Combines the descriptions from the input commits. If only one is non-empty,
 /// then that one is used. Otherwise we concatenate the messages and ask the
 /// user to edit the result in their editor.
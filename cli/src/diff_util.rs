@@ -13,18 +13,26 @@
 // limitations under the License.
 
 use std::cmp::max;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::io;
 use std::ops::Range;
+use std::sync::Arc;
 
 use futures::{try_join, Stream, StreamExt};
 use itertools::Itertools;
 use jj_lib::backend::{BackendError, BackendResult, TreeValue};
 use jj_lib::commit::Commit;
-use jj_lib::conflicts::{materialize_tree_value, MaterializedTreeValue};
+use jj_lib::conflicts::{is_conflict_marker_line, materialize_tree_value, MaterializedTreeValue};
+use jj_lib::copy_detection::{
+    self, CopyDetectionOptions, CopyOperation, DetectedCopies, DetectedCopy,
+};
 use jj_lib::diff::{Diff, DiffHunk};
 use jj_lib::files::DiffLine;
-use jj_lib::matchers::Matcher;
+use jj_lib::fileset::FilesetExpression;
+use jj_lib::matchers::{
+    DifferenceMatcher, EverythingMatcher, FilesMatcher, IntersectionMatcher, Matcher,
+    NothingMatcher, UnionMatcher,
+};
 use jj_lib::merge::MergedTreeValue;
 use jj_lib::merged_tree::{MergedTree, TreeDiffStream};
 use jj_lib::object_id::ObjectId;
@@ -34,6 +42,7 @@ use jj_lib::settings::{ConfigResultExt as _, UserSettings};
 use jj_lib::store::Store;
 use jj_lib::{diff, files};
 use pollster::FutureExt;
+use regex::bytes::Regex;
 use thiserror::Error;
 use tracing::instrument;
 use unicode_width::UnicodeWidthStr as _;
@@ -73,6 +82,9 @@ pub struct DiffFormatArgs {
     #[arg(long)]
     pub name_only: bool,
     /// Show a Git-format diff
+    ///
+    /// Conflicted files are shown with the same conflict markers as in the
+    /// working copy, and with an all-zero hash.
     #[arg(long)]
     pub git: bool,
     /// Show a word-level diff with changes indicated only by color
@@ -159,6 +171,37 @@ fn diff_formats_from_args(
     Ok(formats)
 }
 
+#[derive(Clone, Debug, serde::Deserialize)]
+struct DiffToolByPathConfig {
+    files: String,
+    tool: String,
+}
+
+/// Loads `ui.diff-tool-by-path` rules as pairs of fileset expression text and
+/// diff tool. Returns an empty list if the Git, color-words, or tool format is
+/// explicitly requested by the command arguments.
+pub fn diff_tools_by_path_for(
+    settings: &UserSettings,
+    args: &DiffFormatArgs,
+) -> Result<Vec<(String, ExternalMergeTool)>, config::ConfigError> {
+    if args.git || args.color_words || args.tool.is_some() {
+        return Ok(vec![]);
+    }
+    let rules: Vec<DiffToolByPathConfig> = settings
+        .config()
+        .get("ui.diff-tool-by-path")
+        .optional()?
+        .unwrap_or_default();
+    rules
+        .into_iter()
+        .map(|rule| {
+            let tool = merge_tools::get_external_tool_config(settings, &rule.tool)?
+                .unwrap_or_else(|| ExternalMergeTool::with_program(&rule.tool));
+            Ok((rule.files, tool))
+        })
+        .try_collect()
+}
+
 fn default_diff_format(
     settings: &UserSettings,
     num_context_lines: Option<usize>,
@@ -174,6 +217,23 @@ fn default_diff_format(
         .unwrap_or_else(|| ExternalMergeTool::with_diff_args(&args));
         return Ok(DiffFormat::Tool(Box::new(tool)));
     }
+    if let Some(names) = config
+        .get::<Vec<String>>("ui.diff-tool-candidates")
+        .optional()?
+    {
+        // Pick the first installed tool. ":builtin" selects the internal
+        // "format".
+        for name in &names {
+            if name == merge_tools::BUILTIN_EDITOR_NAME {
+                break;
+            }
+            let tool = merge_tools::get_external_tool_config(settings, name)?
+                .unwrap_or_else(|| ExternalMergeTool::with_program(name));
+            if merge_tools::is_external_tool_installed(&tool) {
+                return Ok(DiffFormat::Tool(Box::new(tool)));
+            }
+        }
+    }
     let name = if let Some(name) = config.get_string("ui.diff.format").optional()? {
         name
     } else if let Some(name) = config.get_string("diff.format").optional()? {
@@ -198,6 +258,25 @@ fn default_diff_format(
     }
 }
 
+/// Loads the parameters of copy detection from the settings.
+pub fn copy_detection_options_for(
+    settings: &UserSettings,
+    find_copies_harder: bool,
+) -> Result<CopyDetectionOptions, config::ConfigError> {
+    let mut options = CopyDetectionOptions {
+        find_copies_harder,
+        ..Default::default()
+    };
+    if let Some(limit) = settings
+        .config()
+        .get::<usize>("ui.diff.copy-candidate-limit")
+        .optional()?
+    {
+        options.candidate_limit = limit;
+    }
+    Ok(options)
+}
+
 #[derive(Debug, Error)]
 pub enum DiffRenderError {
     #[error("Failed to generate diff")]
@@ -218,6 +297,31 @@ pub struct DiffRenderer<'a> {
     repo: &'a dyn Repo,
     path_converter: &'a RepoPathUiConverter,
     formats: Vec<DiffFormat>,
+    copy_detection: Option<CopyDetectionOptions>,
+    unified_diff_options: UnifiedDiffOptions,
+    tools_by_path: Vec<(Box<dyn Matcher>, ExternalMergeTool)>,
+    unrouted_matcher: Box<dyn Matcher>,
+}
+
+/// Options for rendering diff hunks in the Git format.
+#[derive(Clone, Debug)]
+pub struct UnifiedDiffOptions {
+    /// Hunks whose added and removed lines all match are hidden.
+    pub ignore_matching_lines: Option<Regex>,
+    /// Whether to highlight blocks of lines that were moved.
+    pub color_moved: bool,
+    /// Files whose hunks are redacted.
+    pub secret_matcher: Arc<dyn Matcher>,
+}
+
+impl Default for UnifiedDiffOptions {
+    fn default() -> Self {
+        UnifiedDiffOptions {
+            ignore_matching_lines: None,
+            color_moved: false,
+            secret_matcher: Arc::new(NothingMatcher),
+        }
+    }
 }
 
 impl<'a> DiffRenderer<'a> {
@@ -230,7 +334,71 @@ impl<'a> DiffRenderer<'a> {
             repo,
             formats,
             path_converter,
+            copy_detection: None,
+            unified_diff_options: UnifiedDiffOptions::default(),
+            tools_by_path: vec![],
+            unrouted_matcher: Box::new(EverythingMatcher),
+        }
+    }
+
+    /// Enables detection of renamed and copied files by comparing their
+    /// contents. Only the summary and Git formats show the detected copies.
+    pub fn with_copy_detection(mut self, options: CopyDetectionOptions) -> Self {
+        self.copy_detection = Some(options);
+        self
+    }
+
+    /// Hides changes whose added and removed lines all match the given regex.
+    /// The Git and stat formats hide such hunks, and the other formats hide
+    /// files whose changes are all hidden. External tools aren't supported.
+    pub fn with_ignore_matching_lines(mut self, regex: Regex) -> Self {
+        self.unified_diff_options.ignore_matching_lines = Some(regex);
+        self
+    }
+
+    /// Highlights blocks of removed lines that were added elsewhere in the same
+    /// file, and vice versa. Only the Git format highlights moved lines.
+    pub fn with_color_moved(mut self) -> Self {
+        self.unified_diff_options.color_moved = true;
+        self
+    }
+
+    /// Hides contents of the files matching the `matcher`. The color-words and
+    /// Git formats print `[redacted]` in place of the hunks. External tools
+    /// aren't given the files, which are rendered in the color-words format
+    /// instead.
+    pub fn with_secret_paths(mut self, matcher: Box<dyn Matcher>) -> Self {
+        self.unified_diff_options.secret_matcher = matcher.into();
+        self
+    }
+
+    /// Generates diffs of the files matching the fileset expressions by the
+    /// corresponding external tools. The first matching expression wins. Only
+    /// the Git, color-words, and tool formats are affected, and the remaining
+    /// files and the secret files are rendered in that format.
+    pub fn with_tools_by_path(
+        mut self,
+        tools: impl IntoIterator<Item = (FilesetExpression, ExternalMergeTool)>,
+    ) -> Self {
+        let mut routed = FilesetExpression::none();
+        for (expression, tool) in tools {
+            let matcher = expression.clone().difference(routed.clone()).to_matcher();
+            routed = routed.union(expression);
+            self.tools_by_path.push((matcher, tool));
         }
+        self.unrouted_matcher = FilesetExpression::all().difference(routed).to_matcher();
+        self
+    }
+
+    /// Returns true if any of the diffs may be generated by external tools.
+    pub fn has_external_tools(&self) -> bool {
+        self.formats.iter().any(|format| match format {
+            DiffFormat::Tool(_) => true,
+            DiffFormat::Git { .. } | DiffFormat::ColorWords { .. } => {
+                !self.tools_by_path.is_empty()
+            }
+            _ => false,
+        })
     }
 
     /// Generates diff between `from_tree` and `to_tree`.
@@ -244,17 +412,59 @@ impl<'a> DiffRenderer<'a> {
     ) -> Result<(), DiffRenderError> {
         let repo = self.repo;
         let path_converter = self.path_converter;
+        let copies = if let Some(options) = &self.copy_detection {
+            copy_detection::detect_copies(from_tree, to_tree, matcher, options)?
+        } else {
+            DetectedCopies::default()
+        };
+        let unignored_matcher;
+        let matcher = if let Some(regex) = &self.unified_diff_options.ignore_matching_lines {
+            let ignored_files =
+                find_ignored_files(repo.store(), from_tree.diff_stream(to_tree, matcher), regex)?;
+            unignored_matcher = IntersectionMatcher::new(
+                matcher,
+                DifferenceMatcher::new(EverythingMatcher, FilesMatcher::new(ignored_files)),
+            );
+            &unignored_matcher as &dyn Matcher
+        } else {
+            matcher
+        };
         for format in &self.formats {
+            let unrouted_matcher;
+            let matcher = match format {
+                DiffFormat::Git { .. } | DiffFormat::ColorWords { .. } | DiffFormat::Tool(_)
+                    if !self.tools_by_path.is_empty() =>
+                {
+                    self.show_diff_by_path_tools(ui, formatter, from_tree, to_tree, matcher)?;
+                    // Secret files aren't routed to the tools.
+                    unrouted_matcher = IntersectionMatcher::new(
+                        matcher,
+                        UnionMatcher::new(
+                            self.unrouted_matcher.as_ref(),
+                            self.unified_diff_options.secret_matcher.as_ref(),
+                        ),
+                    );
+                    &unrouted_matcher as &dyn Matcher
+                }
+                _ => matcher,
+            };
             match format {
                 DiffFormat::Summary => {
                     let tree_diff = from_tree.diff_stream(to_tree, matcher);
-                    show_diff_summary(formatter, tree_diff, path_converter)?;
+                    show_diff_summary(formatter, tree_diff, path_converter, &copies)?;
                 }
                 DiffFormat::Stat => {
                     let tree_diff = from_tree.diff_stream(to_tree, matcher);
                     // TODO: In graph log, graph width should be subtracted
                     let width = usize::from(ui.term_width().unwrap_or(80));
-                    show_diff_stat(repo, formatter, tree_diff, path_converter, width)?;
+                    show_diff_stat(
+                        repo,
+                        formatter,
+                        tree_diff,
+                        path_converter,
+                        width,
+                        self.unified_diff_options.ignore_matching_lines.as_ref(),
+                    )?;
                 }
                 DiffFormat::Types => {
                     let tree_diff = from_tree.diff_stream(to_tree, matcher);
@@ -266,28 +476,99 @@ impl<'a> DiffRenderer<'a> {
                 }
                 DiffFormat::Git { context } => {
                     let tree_diff = from_tree.diff_stream(to_tree, matcher);
-                    show_git_diff(repo, formatter, *context, tree_diff)?;
+                    show_git_diff(
+                        repo,
+                        formatter,
+                        *context,
+                        &self.unified_diff_options,
+                        tree_diff,
+                        from_tree,
+                        &copies,
+                    )?;
                 }
                 DiffFormat::ColorWords { context } => {
                     let tree_diff = from_tree.diff_stream(to_tree, matcher);
-                    show_color_words_diff(repo, formatter, *context, tree_diff, path_converter)?;
+                    show_color_words_diff(
+                        repo,
+                        formatter,
+                        *context,
+                        tree_diff,
+                        path_converter,
+                        self.unified_diff_options.secret_matcher.as_ref(),
+                    )?;
                 }
                 DiffFormat::Tool(tool) => {
+                    let non_secret_matcher =
+                        IntersectionMatcher::new(matcher, self.non_secret_matcher());
                     merge_tools::generate_diff(
                         ui,
                         formatter.raw(),
                         from_tree,
                         to_tree,
-                        matcher,
+                        &non_secret_matcher,
                         tool,
                     )
                     .map_err(DiffRenderError::DiffGenerate)?;
+                    self.show_redacted_secret_files(formatter, from_tree, to_tree, matcher)?;
                 }
             }
         }
         Ok(())
     }
 
+    /// Matches files whose contents may be passed to external tools.
+    fn non_secret_matcher(&self) -> impl Matcher + '_ {
+        DifferenceMatcher::new(
+            EverythingMatcher,
+            self.unified_diff_options.secret_matcher.as_ref(),
+        )
+    }
+
+    /// Renders the secret files in the color-words format, which prints
+    /// `[redacted]` in place of the hunks.
+    fn show_redacted_secret_files(
+        &self,
+        formatter: &mut dyn Formatter,
+        from_tree: &MergedTree,
+        to_tree: &MergedTree,
+        matcher: &dyn Matcher,
+    ) -> Result<(), DiffRenderError> {
+        let secret_matcher = self.unified_diff_options.secret_matcher.as_ref();
+        let matcher = IntersectionMatcher::new(matcher, secret_matcher);
+        let tree_diff = from_tree.diff_stream(to_tree, &matcher);
+        show_color_words_diff(
+            self.repo,
+            formatter,
+            DEFAULT_CONTEXT_LINES,
+            tree_diff,
+            self.path_converter,
+            secret_matcher,
+        )
+    }
+
+    fn show_diff_by_path_tools(
+        &self,
+        ui: &Ui,
+        formatter: &mut dyn Formatter,
+        from_tree: &MergedTree,
+        to_tree: &MergedTree,
+        matcher: &dyn Matcher,
+    ) -> Result<(), DiffRenderError> {
+        for (tool_matcher, tool) in &self.tools_by_path {
+            let matcher = IntersectionMatcher::new(
+                IntersectionMatcher::new(matcher, tool_matcher.as_ref()),
+                self.non_secret_matcher(),
+            );
+            // Don't invoke the tool if no files are routed to it.
+            if from_tree.diff(to_tree, &matcher).next().is_none() {
+                continue;
+            }
+            merge_tools::generate_diff(ui, formatter.raw(), from_tree, to_tree, &matcher, tool)
+                .map_err(DiffRenderError::DiffGenerate)?;
+        }
+        Ok(())
+    }
+
     /// Generates diff of the given `commit` compared to its parents.
     pub fn show_patch(
         &self,
@@ -306,6 +587,7 @@ fn show_color_words_diff_hunks(
     left: &[u8],
     right: &[u8],
     num_context_lines: usize,
+    label_conflict_markers: bool,
     formatter: &mut dyn Formatter,
 ) -> io::Result<()> {
     const SKIPPED_CONTEXT_LINE: &str = "    ...\n";
@@ -326,7 +608,7 @@ fn show_color_words_diff_hunks(
                 }
             } else if context.len() > num_context_lines * 2 + 1 {
                 for line in context.drain(..num_context_lines) {
-                    show_color_words_diff_line(formatter, &line)?;
+                    show_color_words_diff_line(formatter, &line, label_conflict_markers)?;
                 }
                 start_skipping_context = true;
             }
@@ -338,10 +620,10 @@ fn show_color_words_diff_hunks(
             }
         } else {
             for line in &context {
-                show_color_words_diff_line(formatter, line)?;
+                show_color_words_diff_line(formatter, line, label_conflict_markers)?;
             }
             context.clear();
-            show_color_words_diff_line(formatter, &diff_line)?;
+            show_color_words_diff_line(formatter, &diff_line, label_conflict_markers)?;
             context_before = false;
             skipped_context = false;
         }
@@ -353,7 +635,7 @@ fn show_color_words_diff_hunks(
             context_before = true;
         }
         for line in &context {
-            show_color_words_diff_line(formatter, line)?;
+            show_color_words_diff_line(formatter, line, label_conflict_markers)?;
         }
         if context_before {
             write!(formatter, "{SKIPPED_CONTEXT_LINE}")?;
@@ -373,6 +655,7 @@ fn show_color_words_diff_hunks(
 fn show_color_words_diff_line(
     formatter: &mut dyn Formatter,
     diff_line: &DiffLine,
+    label_conflict_markers: bool,
 ) -> io::Result<()> {
     if diff_line.has_left_content {
         formatter.with_label("removed", |formatter| {
@@ -398,29 +681,51 @@ fn show_color_words_diff_line(
     } else {
         write!(formatter, "    : ")?;
     }
-    for hunk in &diff_line.hunks {
-        match hunk {
-            DiffHunk::Matching(data) => {
-                formatter.write_all(data)?;
-            }
-            DiffHunk::Different(data) => {
-                let before = data[0];
-                let after = data[1];
-                if !before.is_empty() {
-                    formatter.with_label("removed", |formatter| {
-                        formatter.with_label("token", |formatter| formatter.write_all(before))
-                    })?;
+    let write_hunks = |formatter: &mut dyn Formatter| -> io::Result<()> {
+        for hunk in &diff_line.hunks {
+            match hunk {
+                DiffHunk::Matching(data) => {
+                    formatter.write_all(data)?;
                 }
-                if !after.is_empty() {
-                    formatter.with_label("added", |formatter| {
-                        formatter.with_label("token", |formatter| formatter.write_all(after))
-                    })?;
+                DiffHunk::Different(data) => {
+                    let before = data[0];
+                    let after = data[1];
+                    if !before.is_empty() {
+                        formatter.with_label("removed", |formatter| {
+                            formatter.with_label("token", |formatter| formatter.write_all(before))
+                        })?;
+                    }
+                    if !after.is_empty() {
+                        formatter.with_label("added", |formatter| {
+                            formatter.with_label("token", |formatter| formatter.write_all(after))
+                        })?;
+                    }
                 }
             }
         }
+        Ok(())
+    };
+    if label_conflict_markers && is_conflict_marker_diff_line(diff_line) {
+        formatter.with_label("conflict_marker", write_hunks)
+    } else {
+        write_hunks(formatter)
     }
+}
 
-    Ok(())
+/// Returns true if either side of the `diff_line` is a conflict marker.
+fn is_conflict_marker_diff_line(diff_line: &DiffLine) -> bool {
+    [0, 1].into_iter().any(|side| {
+        let line = diff_line
+            .hunks
+            .iter()
+            .flat_map(|hunk| match hunk {
+                DiffHunk::Matching(data) => *data,
+                DiffHunk::Different(data) => data[side],
+            })
+            .copied()
+            .collect_vec();
+        is_conflict_marker_line(&line)
+    })
 }
 
 struct FileContent {
@@ -520,6 +825,7 @@ pub fn show_color_words_diff(
     num_context_lines: usize,
     tree_diff: TreeDiffStream,
     path_converter: &RepoPathUiConverter,
+    secret_matcher: &dyn Matcher,
 ) -> Result<(), DiffRenderError> {
     formatter.push_label("diff")?;
     let mut diff_stream = materialized_diff_stream(repo.store(), tree_diff);
@@ -540,6 +846,8 @@ pub fn show_color_words_diff(
                 }
                 _ => {}
             }
+            let has_conflict = left_value.is_conflict() || right_value.is_conflict();
+            let is_secret = secret_matcher.matches(&path);
             if left_value.is_absent() {
                 let description = basic_diff_file_type(&right_value);
                 writeln!(
@@ -551,11 +859,14 @@ pub fn show_color_words_diff(
                     writeln!(formatter.labeled("empty"), "    (empty)")?;
                 } else if right_content.is_binary {
                     writeln!(formatter.labeled("binary"), "    (binary)")?;
+                } else if is_secret {
+                    writeln!(formatter.labeled("redacted"), "    [redacted]")?;
                 } else {
                     show_color_words_diff_hunks(
                         &[],
                         &right_content.contents,
                         num_context_lines,
+                        has_conflict,
                         formatter,
                     )?;
                 }
@@ -612,11 +923,16 @@ pub fn show_color_words_diff(
                 writeln!(formatter.labeled("header"), "{description} {ui_path}:")?;
                 if left_content.is_binary || right_content.is_binary {
                     writeln!(formatter.labeled("binary"), "    (binary)")?;
+                } else if is_secret {
+                    if left_content.contents != right_content.contents {
+                        writeln!(formatter.labeled("redacted"), "    [redacted]")?;
+                    }
                 } else {
                     show_color_words_diff_hunks(
                         &left_content.contents,
                         &right_content.contents,
                         num_context_lines,
+                        has_conflict,
                         formatter,
                     )?;
                 }
@@ -631,11 +947,14 @@ pub fn show_color_words_diff(
                     writeln!(formatter.labeled("empty"), "    (empty)")?;
                 } else if left_content.is_binary {
                     writeln!(formatter.labeled("binary"), "    (binary)")?;
+                } else if is_secret {
+                    writeln!(formatter.labeled("redacted"), "    [redacted]")?;
                 } else {
                     show_color_words_diff_hunks(
                         &left_content.contents,
                         &[],
                         num_context_lines,
+                        has_conflict,
                         formatter,
                     )?;
                 }
@@ -722,7 +1041,7 @@ fn git_diff_part(
     })
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 enum DiffLineType {
     Context,
     Removed,
@@ -739,6 +1058,7 @@ fn unified_diff_hunks<'content>(
     left_content: &'content [u8],
     right_content: &'content [u8],
     num_context_lines: usize,
+    ignore_matching_lines: Option<&Regex>,
 ) -> Vec<UnifiedDiffHunk<'content>> {
     let mut hunks = vec![];
     let mut current_hunk = UnifiedDiffHunk {
@@ -812,16 +1132,174 @@ fn unified_diff_hunks<'content>(
     {
         hunks.push(current_hunk);
     }
+    if let Some(regex) = ignore_matching_lines {
+        hunks.retain(|hunk| !is_ignorable_hunk(hunk, regex));
+    }
     hunks
 }
 
+/// Returns true if all added and removed lines of the `hunk` match the
+/// `regex`, in which case the hunk is considered unchanged.
+fn is_ignorable_hunk(hunk: &UnifiedDiffHunk, regex: &Regex) -> bool {
+    hunk.lines
+        .iter()
+        .filter(|(line_type, _)| *line_type != DiffLineType::Context)
+        .all(|(_, content)| regex.is_match(content.strip_suffix(b"\n").unwrap_or(content)))
+}
+
+/// Finds text files whose added and removed lines all match the `regex`.
+/// Changes of the file type or executable bit aren't ignored.
+fn find_ignored_files(
+    store: &Store,
+    tree_diff: TreeDiffStream,
+    regex: &Regex,
+) -> Result<Vec<RepoPathBuf>, DiffRenderError> {
+    let mut ignored_files = vec![];
+    let mut diff_stream = materialized_diff_stream(store, tree_diff);
+    async {
+        while let Some((path, diff)) = diff_stream.next().await {
+            let (left_value, right_value) = diff?;
+            let is_text_change = match (&left_value, &right_value) {
+                (
+                    MaterializedTreeValue::File {
+                        executable: left_executable,
+                        ..
+                    },
+                    MaterializedTreeValue::File {
+                        executable: right_executable,
+                        ..
+                    },
+                ) => left_executable == right_executable,
+                (MaterializedTreeValue::File { .. }, MaterializedTreeValue::Absent)
+                | (MaterializedTreeValue::Absent, MaterializedTreeValue::File { .. }) => true,
+                _ => false,
+            };
+            if !is_text_change {
+                continue;
+            }
+            let left_content = diff_content(&path, left_value)?;
+            let right_content = diff_content(&path, right_value)?;
+            if left_content.is_binary || right_content.is_binary {
+                continue;
+            }
+            let hunks = unified_diff_hunks(
+                &left_content.contents,
+                &right_content.contents,
+                0,
+                Some(regex),
+            );
+            if hunks.is_empty() {
+                ignored_files.push(path);
+            }
+        }
+        Ok::<(), DiffRenderError>(())
+    }
+    .block_on()?;
+    Ok(ignored_files)
+}
+
+/// Minimum number of consecutive lines to be detected as a moved block.
+const MIN_MOVED_BLOCK_LINES: usize = 3;
+
+/// Finds removed and added lines which are part of a moved block. Returns flags
+/// for each line of each hunk.
+///
+/// A line is considered moved if it is part of at least
+/// `MIN_MOVED_BLOCK_LINES` consecutive removed (or added) lines which are also
+/// added (or removed) consecutively elsewhere. Blocks moved with small edits
+/// are therefore detected only partially, excluding the edited lines and
+/// fragments shorter than the threshold.
+fn find_moved_lines(hunks: &[UnifiedDiffHunk]) -> Vec<Vec<bool>> {
+    // Collect runs of consecutive removed and added lines as (hunk index, line
+    // index) positions.
+    let mut runs: Vec<(DiffLineType, Vec<(usize, usize)>)> = vec![];
+    for (hunk_index, hunk) in hunks.iter().enumerate() {
+        let mut last_type = DiffLineType::Context;
+        for (line_index, (line_type, _)) in hunk.lines.iter().enumerate() {
+            if *line_type == DiffLineType::Context {
+                // Context lines terminate the current run.
+            } else if *line_type == last_type {
+                runs.last_mut().unwrap().1.push((hunk_index, line_index));
+            } else {
+                runs.push((*line_type, vec![(hunk_index, line_index)]));
+            }
+            last_type = *line_type;
+        }
+    }
+    let content_at =
+        |(hunk_index, line_index): (usize, usize)| hunks[hunk_index].lines[line_index].1;
+    let windows_of = |line_type: DiffLineType| -> HashSet<Vec<&[u8]>> {
+        runs.iter()
+            .filter(|(run_type, _)| *run_type == line_type)
+            .flat_map(|(_, positions)| positions.windows(MIN_MOVED_BLOCK_LINES))
+            .map(|window| window.iter().map(|&pos| content_at(pos)).collect())
+            .collect()
+    };
+    let removed_windows = windows_of(DiffLineType::Removed);
+    let added_windows = windows_of(DiffLineType::Added);
+
+    let mut moved = hunks
+        .iter()
+        .map(|hunk| vec![false; hunk.lines.len()])
+        .collect_vec();
+    for (run_type, positions) in &runs {
+        let other_windows = match run_type {
+            DiffLineType::Removed => &added_windows,
+            DiffLineType::Added => &removed_windows,
+            DiffLineType::Context => unreachable!(),
+        };
+        for window in positions.windows(MIN_MOVED_BLOCK_LINES) {
+            let key = window.iter().map(|&pos| content_at(pos)).collect_vec();
+            if other_windows.contains(&key) {
+                for &(hunk_index, line_index) in window {
+                    moved[hunk_index][line_index] = true;
+                }
+            }
+        }
+    }
+    moved
+}
+
+/// How to render the hunks of a single file.
+#[derive(Clone, Copy, Debug)]
+struct HunksRenderOptions {
+    /// Whether to label conflict markers in the contents.
+    label_conflict_markers: bool,
+    /// Whether to highlight blocks of lines that were moved.
+    color_moved: bool,
+    /// Whether to print `[redacted]` in place of the hunks.
+    redacted: bool,
+}
+
 fn show_unified_diff_hunks(
     formatter: &mut dyn Formatter,
-    left_content: &[u8],
-    right_content: &[u8],
-    num_context_lines: usize,
+    hunks: Vec<UnifiedDiffHunk>,
+    options: HunksRenderOptions,
 ) -> io::Result<()> {
-    for hunk in unified_diff_hunks(left_content, right_content, num_context_lines) {
+    let HunksRenderOptions {
+        label_conflict_markers,
+        color_moved,
+        redacted,
+    } = options;
+    if redacted {
+        if !hunks.is_empty() {
+            writeln!(formatter.labeled("redacted"), "[redacted]")?;
+        }
+        return Ok(());
+    }
+    let write_content = |formatter: &mut dyn Formatter, content: &[u8]| {
+        if label_conflict_markers && is_conflict_marker_line(content) {
+            formatter.with_label("conflict_marker", |formatter| formatter.write_all(content))
+        } else {
+            formatter.write_all(content)
+        }
+    };
+    let moved_lines = if color_moved {
+        find_moved_lines(&hunks)
+    } else {
+        vec![]
+    };
+    for (hunk_index, hunk) in hunks.into_iter().enumerate() {
         writeln!(
             formatter.labeled("hunk_header"),
             "@@ -{},{} +{},{} @@",
@@ -830,25 +1308,33 @@ fn show_unified_diff_hunks(
             hunk.right_line_range.start,
             hunk.right_line_range.len()
         )?;
-        for (line_type, content) in hunk.lines {
+        for (line_index, (line_type, content)) in hunk.lines.into_iter().enumerate() {
+            let moved = moved_lines
+                .get(hunk_index)
+                .is_some_and(|flags| flags[line_index]);
+            let write_line = |formatter: &mut dyn Formatter, prefix: &str| {
+                if moved {
+                    formatter.with_label("moved", |formatter| {
+                        write!(formatter, "{prefix}")?;
+                        write_content(formatter, content)
+                    })
+                } else {
+                    write!(formatter, "{prefix}")?;
+                    write_content(formatter, content)
+                }
+            };
             match line_type {
                 DiffLineType::Context => {
                     formatter.with_label("context", |formatter| {
                         write!(formatter, " ")?;
-                        formatter.write_all(content)
+                        write_content(formatter, content)
                     })?;
                 }
                 DiffLineType::Removed => {
-                    formatter.with_label("removed", |formatter| {
-                        write!(formatter, "-")?;
-                        formatter.write_all(content)
-                    })?;
+                    formatter.with_label("removed", |formatter| write_line(formatter, "-"))?;
                 }
                 DiffLineType::Added => {
-                    formatter.with_label("added", |formatter| {
-                        write!(formatter, "+")?;
-                        formatter.write_all(content)
-                    })?;
+                    formatter.with_label("added", |formatter| write_line(formatter, "+"))?;
                 }
             }
             if !content.ends_with(b"\n") {
@@ -887,16 +1373,45 @@ pub fn show_git_diff(
     repo: &dyn Repo,
     formatter: &mut dyn Formatter,
     num_context_lines: usize,
+    options: &UnifiedDiffOptions,
     tree_diff: TreeDiffStream,
+    from_tree: &MergedTree,
+    copies: &DetectedCopies,
 ) -> Result<(), DiffRenderError> {
     formatter.push_label("diff")?;
 
+    let secret_matcher = options.secret_matcher.as_ref();
     let mut diff_stream = materialized_diff_stream(repo.store(), tree_diff);
     async {
         while let Some((path, diff)) = diff_stream.next().await {
             let path_string = path.as_internal_file_string();
             let (left_value, right_value) = diff?;
-            if left_value.is_absent() {
+            let is_secret = secret_matcher.matches(&path);
+            if let Some(copy) = copies.for_target(&path) {
+                let is_secret = is_secret || secret_matcher.matches(&copy.source);
+                let source_value = from_tree.path_value(&copy.source)?;
+                let left_value =
+                    materialize_tree_value(repo.store(), &copy.source, source_value).await?;
+                let has_conflict = left_value.is_conflict() || right_value.is_conflict();
+                let left_part = git_diff_part(&copy.source, left_value)?;
+                let right_part = git_diff_part(&path, right_value)?;
+                show_git_copy_header(formatter, copy, &left_part, &right_part)?;
+                show_unified_diff_hunks(
+                    formatter,
+                    unified_diff_hunks(
+                        &left_part.content,
+                        &right_part.content,
+                        num_context_lines,
+                        options.ignore_matching_lines.as_ref(),
+                    ),
+                    HunksRenderOptions {
+                        label_conflict_markers: has_conflict,
+                        color_moved: options.color_moved,
+                        redacted: is_secret,
+                    },
+                )?;
+            } else if left_value.is_absent() {
+                let has_conflict = right_value.is_conflict();
                 let right_part = git_diff_part(&path, right_value)?;
                 formatter.with_label("file_header", |formatter| {
                     writeln!(formatter, "diff --git a/{path_string} b/{path_string}")?;
@@ -905,10 +1420,30 @@ pub fn show_git_diff(
                     writeln!(formatter, "--- /dev/null")?;
                     writeln!(formatter, "+++ b/{path_string}")
                 })?;
-                show_unified_diff_hunks(formatter, &[], &right_part.content, num_context_lines)?;
+                show_unified_diff_hunks(
+                    formatter,
+                    unified_diff_hunks(
+                        &[],
+                        &right_part.content,
+                        num_context_lines,
+                        options.ignore_matching_lines.as_ref(),
+                    ),
+                    HunksRenderOptions {
+                        label_conflict_markers: has_conflict,
+                        color_moved: options.color_moved,
+                        redacted: is_secret,
+                    },
+                )?;
             } else if right_value.is_present() {
+                let has_conflict = left_value.is_conflict() || right_value.is_conflict();
                 let left_part = git_diff_part(&path, left_value)?;
                 let right_part = git_diff_part(&path, right_value)?;
+                let hunks = unified_diff_hunks(
+                    &left_part.content,
+                    &right_part.content,
+                    num_context_lines,
+                    options.ignore_matching_lines.as_ref(),
+                );
                 formatter.with_label("file_header", |formatter| {
                     writeln!(formatter, "diff --git a/{path_string} b/{path_string}")?;
                     if left_part.mode != right_part.mode {
@@ -932,11 +1467,15 @@ pub fn show_git_diff(
                 })?;
                 show_unified_diff_hunks(
                     formatter,
-                    &left_part.content,
-                    &right_part.content,
-                    num_context_lines,
+                    hunks,
+                    HunksRenderOptions {
+                        label_conflict_markers: has_conflict,
+                        color_moved: options.color_moved,
+                        redacted: is_secret,
+                    },
                 )?;
-            } else {
+            } else if !copies.is_renamed_source(&path) {
+                let has_conflict = left_value.is_conflict();
                 let left_part = git_diff_part(&path, left_value)?;
                 formatter.with_label("file_header", |formatter| {
                     writeln!(formatter, "diff --git a/{path_string} b/{path_string}")?;
@@ -945,7 +1484,20 @@ pub fn show_git_diff(
                     writeln!(formatter, "--- a/{path_string}")?;
                     writeln!(formatter, "+++ /dev/null")
                 })?;
-                show_unified_diff_hunks(formatter, &left_part.content, &[], num_context_lines)?;
+                show_unified_diff_hunks(
+                    formatter,
+                    unified_diff_hunks(
+                        &left_part.content,
+                        &[],
+                        num_context_lines,
+                        options.ignore_matching_lines.as_ref(),
+                    ),
+                    HunksRenderOptions {
+                        label_conflict_markers: has_conflict,
+                        color_moved: options.color_moved,
+                        redacted: is_secret,
+                    },
+                )?;
             }
         }
         Ok::<(), DiffRenderError>(())
@@ -955,21 +1507,74 @@ pub fn show_git_diff(
     Ok(())
 }
 
+fn show_git_copy_header(
+    formatter: &mut dyn Formatter,
+    copy: &DetectedCopy,
+    left_part: &GitDiffPart,
+    right_part: &GitDiffPart,
+) -> io::Result<()> {
+    let source_string = copy.source.as_internal_file_string();
+    let target_string = copy.target.as_internal_file_string();
+    let operation = match copy.operation {
+        CopyOperation::Rename => "rename",
+        CopyOperation::Copy => "copy",
+    };
+    formatter.with_label("file_header", |formatter| {
+        writeln!(formatter, "diff --git a/{source_string} b/{target_string}")?;
+        if left_part.mode != right_part.mode {
+            writeln!(formatter, "old mode {}", &left_part.mode)?;
+            writeln!(formatter, "new mode {}", &right_part.mode)?;
+        }
+        writeln!(formatter, "similarity index {}%", copy.similarity)?;
+        writeln!(formatter, "{operation} from {source_string}")?;
+        writeln!(formatter, "{operation} to {target_string}")?;
+        if left_part.hash != right_part.hash {
+            if left_part.mode != right_part.mode {
+                writeln!(formatter, "index {}...{}", &left_part.hash, right_part.hash)?;
+            } else {
+                writeln!(
+                    formatter,
+                    "index {}...{} {}",
+                    &left_part.hash, right_part.hash, left_part.mode
+                )?;
+            }
+        }
+        if left_part.content != right_part.content {
+            writeln!(formatter, "--- a/{source_string}")?;
+            writeln!(formatter, "+++ b/{target_string}")?;
+        }
+        Ok(())
+    })
+}
+
 #[instrument(skip_all)]
 pub fn show_diff_summary(
     formatter: &mut dyn Formatter,
     mut tree_diff: TreeDiffStream,
     path_converter: &RepoPathUiConverter,
+    copies: &DetectedCopies,
 ) -> io::Result<()> {
     formatter.with_label("diff", |formatter| -> io::Result<()> {
         async {
             while let Some((repo_path, diff)) = tree_diff.next().await {
                 let (before, after) = diff.unwrap();
                 let ui_path = path_converter.format_file_path(&repo_path);
-                if before.is_present() && after.is_present() {
+                if let Some(copy) = copies.for_target(&repo_path) {
+                    let source_path = path_converter.format_file_path(&copy.source);
+                    match copy.operation {
+                        CopyOperation::Rename => {
+                            writeln!(formatter.labeled("renamed"), "R {source_path} => {ui_path}")?
+                        }
+                        CopyOperation::Copy => {
+                            writeln!(formatter.labeled("copied"), "C {source_path} => {ui_path}")?
+                        }
+                    }
+                } else if before.is_present() && after.is_present() {
                     writeln!(formatter.labeled("modified"), "M {ui_path}")?;
                 } else if before.is_absent() {
                     writeln!(formatter.labeled("added"), "A {ui_path}")?;
+                } else if copies.is_renamed_source(&repo_path) {
+                    // Shown as the source of the rename target
                 } else {
                     // `R` could be interpreted as "renamed"
                     writeln!(formatter.labeled("removed"), "D {ui_path}")?;
@@ -985,22 +1590,55 @@ struct DiffStat {
     path: String,
     added: usize,
     removed: usize,
+    /// File sizes before and after if either side is binary.
+    binary_sizes: Option<(usize, usize)>,
+    is_conflict: bool,
 }
 
 fn get_diff_stat(
     path: String,
     left_content: &FileContent,
     right_content: &FileContent,
+    ignore_matching_lines: Option<&Regex>,
 ) -> DiffStat {
-    // TODO: this matches git's behavior, which is to count the number of newlines
-    // in the file. but that behavior seems unhelpful; no one really cares how
-    // many `0xa0` characters are in an image.
+    if left_content.is_binary || right_content.is_binary {
+        return DiffStat {
+            path,
+            added: 0,
+            removed: 0,
+            binary_sizes: Some((left_content.contents.len(), right_content.contents.len())),
+            is_conflict: false,
+        };
+    }
+    let mut added = 0;
+    let mut removed = 0;
+    if let Some(regex) = ignore_matching_lines {
+        // Count lines in the same hunks as the Git format shows.
+        let hunks = unified_diff_hunks(
+            &left_content.contents,
+            &right_content.contents,
+            DEFAULT_CONTEXT_LINES,
+            Some(regex),
+        );
+        for (line_type, _) in hunks.iter().flat_map(|hunk| &hunk.lines) {
+            match line_type {
+                DiffLineType::Context => {}
+                DiffLineType::Removed => removed += 1,
+                DiffLineType::Added => added += 1,
+            }
+        }
+        return DiffStat {
+            path,
+            added,
+            removed,
+            binary_sizes: None,
+            is_conflict: false,
+        };
+    }
     let diff = Diff::for_tokenizer(
         &[&left_content.contents, &right_content.contents],
         diff::find_line_ranges,
     );
-    let mut added = 0;
-    let mut removed = 0;
     for hunk in diff.hunks() {
         match hunk {
             DiffHunk::Matching(_) => {}
@@ -1015,6 +1653,8 @@ fn get_diff_stat(
         path,
         added,
         removed,
+        binary_sizes: None,
+        is_conflict: false,
     }
 }
 
@@ -1024,6 +1664,7 @@ pub fn show_diff_stat(
     tree_diff: TreeDiffStream,
     path_converter: &RepoPathUiConverter,
     display_width: usize,
+    ignore_matching_lines: Option<&Regex>,
 ) -> Result<(), DiffRenderError> {
     let mut stats: Vec<DiffStat> = vec![];
     let mut max_path_width = 0;
@@ -1034,10 +1675,13 @@ pub fn show_diff_stat(
         while let Some((repo_path, diff)) = diff_stream.next().await {
             let (left, right) = diff?;
             let path = path_converter.format_file_path(&repo_path);
+            let is_conflict = matches!(right, MaterializedTreeValue::Conflict { .. });
             let left_content = diff_content(&repo_path, left)?;
             let right_content = diff_content(&repo_path, right)?;
             max_path_width = max(max_path_width, path.width());
-            let stat = get_diff_stat(path, &left_content, &right_content);
+            let mut stat =
+                get_diff_stat(path, &left_content, &right_content, ignore_matching_lines);
+            stat.is_conflict = is_conflict;
             max_diffs = max(max_diffs, stat.added + stat.removed);
             stats.push(stat);
         }
@@ -1070,15 +1714,31 @@ pub fn show_diff_stat(
             // replace start of path with ellipsis if the path is too long
             let (path, path_width) = text_util::elide_start(&stat.path, "...", max_path_width);
             let path_pad_width = max_path_width - path_width;
-            write!(
-                formatter,
-                "{path}{:path_pad_width$} | {:>number_padding$}{}",
-                "", // pad to max_path_width
-                stat.added + stat.removed,
-                if bar_added + bar_removed > 0 { " " } else { "" },
-            )?;
-            write!(formatter.labeled("added"), "{}", "+".repeat(bar_added))?;
-            writeln!(formatter.labeled("removed"), "{}", "-".repeat(bar_removed))?;
+            if let Some((left_size, right_size)) = stat.binary_sizes {
+                write!(
+                    formatter,
+                    "{path}{:path_pad_width$} | ",
+                    "", // pad to max_path_width
+                )?;
+                write!(
+                    formatter.labeled("binary"),
+                    "Bin {left_size} -> {right_size} bytes"
+                )?;
+            } else {
+                write!(
+                    formatter,
+                    "{path}{:path_pad_width$} | {:>number_padding$}{}",
+                    "", // pad to max_path_width
+                    stat.added + stat.removed,
+                    if bar_added + bar_removed > 0 { " " } else { "" },
+                )?;
+                write!(formatter.labeled("added"), "{}", "+".repeat(bar_added))?;
+                write!(formatter.labeled("removed"), "{}", "-".repeat(bar_removed))?;
+            }
+            if stat.is_conflict {
+                write!(formatter.labeled("conflict"), " (conflict)")?;
+            }
+            writeln!(formatter)?;
         }
         writeln!(
             formatter.labeled("stat-summary"),
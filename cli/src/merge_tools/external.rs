@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::env;
 use std::io::{self, Write};
+use std::path::Path;
 use std::process::{Command, ExitStatus, Stdio};
 use std::sync::Arc;
 
@@ -103,6 +105,22 @@ impl ExternalMergeTool {
     }
 }
 
+/// Returns true if the program of the `tool` can be found. A program name
+/// without directory components is looked up in the `PATH`.
+pub fn is_external_tool_installed(tool: &ExternalMergeTool) -> bool {
+    let program = Path::new(&tool.program);
+    if program.components().count() > 1 {
+        return program.is_file();
+    }
+    let Some(paths) = env::var_os("PATH") else {
+        return false;
+    };
+    env::split_paths(&paths).any(|dir| {
+        let path = dir.join(program);
+        path.is_file() || (cfg!(windows) && path.with_extension("exe").is_file())
+    })
+}
+
 #[derive(Debug, Error)]
 pub enum ExternalToolError {
     #[error("Error setting up temporary directory")]
@@ -33,11 +33,11 @@ use thiserror::Error;
 use self::builtin::{edit_diff_builtin, edit_merge_builtin, BuiltinToolError};
 use self::diff_working_copies::DiffCheckoutError;
 use self::external::{edit_diff_external, ExternalToolError};
-pub use self::external::{generate_diff, ExternalMergeTool};
+pub use self::external::{generate_diff, is_external_tool_installed, ExternalMergeTool};
 use crate::config::CommandNameAndArgs;
 use crate::ui::Ui;
 
-const BUILTIN_EDITOR_NAME: &str = ":builtin";
+pub const BUILTIN_EDITOR_NAME: &str = ":builtin";
 
 #[derive(Debug, Error)]
 pub enum DiffEditError {
@@ -21,6 +21,7 @@ use jj_lib::extensions_map::ExtensionsMap;
 use jj_lib::object_id::ObjectId;
 use jj_lib::op_store::OperationId;
 use jj_lib::operation::Operation;
+use jj_lib::settings::UserSettings;
 
 use crate::template_builder::{
     self, merge_fn_map, BuildContext, CoreTemplateBuildFnTable, CoreTemplatePropertyKind,
@@ -39,6 +40,7 @@ pub trait OperationTemplateLanguageExtension {
 }
 
 pub struct OperationTemplateLanguage {
+    settings: UserSettings,
     root_op_id: OperationId,
     current_op_id: Option<OperationId>,
     build_fn_table: OperationTemplateBuildFnTable,
@@ -49,6 +51,7 @@ impl OperationTemplateLanguage {
     /// Sets up environment where operation template will be transformed to
     /// evaluation tree.
     pub fn new(
+        settings: &UserSettings,
         root_op_id: &OperationId,
         current_op_id: Option<&OperationId>,
         extensions: &[impl AsRef<dyn OperationTemplateLanguageExtension>],
@@ -64,6 +67,7 @@ impl OperationTemplateLanguage {
         }
 
         OperationTemplateLanguage {
+            settings: settings.clone(),
             root_op_id: root_op_id.clone(),
             current_op_id: current_op_id.cloned(),
             build_fn_table,
@@ -77,6 +81,10 @@ impl TemplateLanguage<'static> for OperationTemplateLanguage {
 
     template_builder::impl_core_wrap_property_fns!('static, OperationTemplatePropertyKind::Core);
 
+    fn settings(&self) -> &UserSettings {
+        &self.settings
+    }
+
     fn build_function(
         &self,
         build_ctx: &BuildContext<Self::Property>,
@@ -248,6 +256,20 @@ fn builtin_operation_methods() -> OperationTemplateBuildMethodFnMap<Operation> {
         let out_property = self_property.map(|op| op.id().clone());
         Ok(L::wrap_operation_id(out_property))
     });
+    map.insert("args", |_language, _build_ctx, self_property, function| {
+        function.expect_no_arguments()?;
+        let out_property =
+            self_property.map(|op| op.metadata().tags.get("args").cloned().unwrap_or_default());
+        Ok(L::wrap_string(out_property))
+    });
+    map.insert("tag", |language, build_ctx, self_property, function| {
+        let [key_node] = function.expect_exact_arguments()?;
+        let key_property =
+            template_builder::expect_plain_text_expression(language, build_ctx, key_node)?;
+        let out_property = (self_property, key_property)
+            .map(|(op, key)| op.metadata().tags.get(&key).cloned().unwrap_or_default());
+        Ok(L::wrap_string(out_property))
+    });
     map.insert("tags", |_language, _build_ctx, self_property, function| {
         function.expect_no_arguments()?;
         let out_property = self_property.map(|op| {
@@ -255,6 +277,7 @@ fn builtin_operation_methods() -> OperationTemplateBuildMethodFnMap<Operation> {
             op.metadata()
                 .tags
                 .iter()
+                .sorted()
                 .map(|(key, value)| format!("{key}: {value}"))
                 .join("\n")
         });
@@ -14,6 +14,8 @@
 
 //! Utility for parsing and evaluating user-provided revset expressions.
 
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -23,9 +25,9 @@ use jj_lib::commit::Commit;
 use jj_lib::id_prefix::IdPrefixContext;
 use jj_lib::repo::Repo;
 use jj_lib::revset::{
-    self, DefaultSymbolResolver, Revset, RevsetAliasesMap, RevsetCommitRef, RevsetEvaluationError,
-    RevsetExpression, RevsetExtensions, RevsetIteratorExt as _, RevsetParseContext,
-    RevsetParseError, RevsetResolutionError, SymbolResolverExtension,
+    self, DefaultSymbolResolver, ResolvedExpression, Revset, RevsetAliasesMap, RevsetCommitRef,
+    RevsetEvaluationError, RevsetExpression, RevsetExtensions, RevsetIteratorExt as _,
+    RevsetParseContext, RevsetParseError, RevsetResolutionError, SymbolResolverExtension,
 };
 use jj_lib::settings::ConfigResultExt as _;
 use thiserror::Error;
@@ -46,11 +48,52 @@ pub enum UserRevsetEvaluationError {
     Evaluation(RevsetEvaluationError),
 }
 
+/// Evaluated revsets keyed by the resolved expression.
+///
+/// Since the evaluation result has to be collected eagerly to be cached, an
+/// expression is cached only when it's evaluated for the second time. The
+/// first evaluation stays lazy so e.g. `jj log -n1` doesn't walk the whole
+/// revset.
+///
+/// The cached results are valid only for the repo they were evaluated
+/// against, so the cache must be discarded when the repo is mutated.
+#[derive(Debug, Default)]
+pub struct RevsetCache {
+    commit_ids: RefCell<HashMap<ResolvedExpression, Option<Vec<CommitId>>>>,
+}
+
+impl RevsetCache {
+    fn evaluate<'a>(
+        &self,
+        repo: &'a dyn Repo,
+        expression: ResolvedExpression,
+    ) -> Result<Box<dyn Revset + 'a>, RevsetEvaluationError> {
+        let cached = self.commit_ids.borrow().get(&expression).cloned();
+        let commit_ids = match cached {
+            Some(Some(commit_ids)) => commit_ids,
+            Some(None) => {
+                let commit_ids = expression.evaluate(repo)?.iter().collect_vec();
+                self.commit_ids
+                    .borrow_mut()
+                    .insert(expression, Some(commit_ids.clone()));
+                commit_ids
+            }
+            None => {
+                let revset = expression.evaluate(repo);
+                self.commit_ids.borrow_mut().insert(expression, None);
+                return revset;
+            }
+        };
+        ResolvedExpression::Commits(commit_ids).evaluate(repo)
+    }
+}
+
 /// Wrapper around `RevsetExpression` to provide convenient methods.
 pub struct RevsetExpressionEvaluator<'repo> {
     repo: &'repo dyn Repo,
     extensions: Arc<RevsetExtensions>,
     id_prefix_context: &'repo IdPrefixContext,
+    cache: Option<&'repo RevsetCache>,
     expression: Rc<RevsetExpression>,
 }
 
@@ -65,10 +108,18 @@ impl<'repo> RevsetExpressionEvaluator<'repo> {
             repo,
             extensions,
             id_prefix_context,
+            cache: None,
             expression,
         }
     }
 
+    /// Reuses the evaluation results stored in the `cache`, which must be
+    /// associated with the `repo`.
+    pub fn with_cache(mut self, cache: &'repo RevsetCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     /// Returns the underlying expression.
     pub fn expression(&self) -> &Rc<RevsetExpression> {
         &self.expression
@@ -79,6 +130,11 @@ impl<'repo> RevsetExpressionEvaluator<'repo> {
         self.expression = self.expression.intersection(other);
     }
 
+    /// Unites the underlying expression with the `other` expression.
+    pub fn union_with(&mut self, other: &Rc<RevsetExpression>) {
+        self.expression = self.expression.union(other);
+    }
+
     /// Evaluates the expression.
     pub fn evaluate(&self) -> Result<Box<dyn Revset + 'repo>, UserRevsetEvaluationError> {
         let symbol_resolver = default_symbol_resolver(
@@ -86,7 +142,15 @@ impl<'repo> RevsetExpressionEvaluator<'repo> {
             self.extensions.symbol_resolvers(),
             self.id_prefix_context,
         );
-        evaluate(self.repo, &symbol_resolver, self.expression.clone())
+        let Some(cache) = self.cache else {
+            return evaluate(self.repo, &symbol_resolver, self.expression.clone());
+        };
+        let resolved = revset::optimize(self.expression.clone())
+            .resolve_user_expression(self.repo, &symbol_resolver)
+            .map_err(UserRevsetEvaluationError::Resolution)?;
+        cache
+            .evaluate(self.repo, resolved)
+            .map_err(UserRevsetEvaluationError::Evaluation)
     }
 
     /// Evaluates the expression to an iterator over commit ids. Entries are
@@ -277,3 +341,60 @@ fn format_multiple_revisions_error(
     };
     cmd_err
 }
+
+#[cfg(test)]
+mod tests {
+    use testutils::{write_random_commit, TestRepo};
+
+    use super::*;
+
+    fn evaluate_all_with_cache(repo: &dyn Repo, cache: &RevsetCache) -> Vec<CommitId> {
+        let extensions: Arc<RevsetExtensions> = RevsetExtensions::default().into();
+        let id_prefix_context = IdPrefixContext::new(extensions.clone());
+        RevsetExpressionEvaluator::new(
+            repo,
+            extensions,
+            &id_prefix_context,
+            RevsetExpression::all(),
+        )
+        .with_cache(cache)
+        .evaluate_to_commit_ids()
+        .unwrap()
+        .collect()
+    }
+
+    #[test]
+    fn test_revset_cache() {
+        let settings = testutils::user_settings();
+        let test_repo = TestRepo::init();
+        let root_commit_id = test_repo.repo.store().root_commit_id().clone();
+        let mut tx = test_repo.repo.start_transaction(&settings);
+        let commit1 = write_random_commit(tx.mut_repo(), &settings);
+        let repo1 = tx.commit("test");
+        let cache = RevsetCache::default();
+        let cached_values = || cache.commit_ids.borrow().values().cloned().collect_vec();
+
+        // The first evaluation isn't cached
+        let ids1 = evaluate_all_with_cache(repo1.as_ref(), &cache);
+        assert_eq!(ids1, [commit1.id().clone(), root_commit_id.clone()]);
+        assert_eq!(cached_values(), [None]);
+
+        // The second evaluation fills the cache, and the next one hits it
+        assert_eq!(evaluate_all_with_cache(repo1.as_ref(), &cache), ids1);
+        assert_eq!(cached_values(), [Some(ids1.clone())]);
+        assert_eq!(evaluate_all_with_cache(repo1.as_ref(), &cache), ids1);
+        assert_eq!(cached_values(), [Some(ids1.clone())]);
+
+        // The expression resolves differently after a transaction, so the
+        // results evaluated against the old repo aren't reused
+        let mut tx = repo1.start_transaction(&settings);
+        let commit2 = write_random_commit(tx.mut_repo(), &settings);
+        let repo2 = tx.commit("test");
+        let ids2 = evaluate_all_with_cache(repo2.as_ref(), &cache);
+        assert_eq!(
+            ids2,
+            [commit2.id().clone(), commit1.id().clone(), root_commit_id]
+        );
+        assert_eq!(cache.commit_ids.borrow().len(), 2);
+    }
+}
@@ -13,11 +13,15 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::env;
+use std::io;
 
 use itertools::Itertools as _;
 use jj_lib::backend::{Signature, Timestamp};
 use jj_lib::dsl_util::AliasExpandError as _;
+use jj_lib::settings::UserSettings;
 
+use crate::formatter::{FormatRecorder, Formatter};
 use crate::template_parser::{
     self, BinaryOp, ExpressionKind, ExpressionNode, FunctionCallNode, TemplateAliasesMap,
     TemplateParseError, TemplateParseErrorKind, TemplateParseResult, UnaryOp,
@@ -49,10 +53,16 @@ pub trait TemplateLanguage<'a> {
     fn wrap_timestamp_range(
         property: impl TemplateProperty<Output = TimestampRange> + 'a,
     ) -> Self::Property;
+    fn wrap_config_value(
+        property: impl TemplateProperty<Output = config::Value> + 'a,
+    ) -> Self::Property;
 
     fn wrap_template(template: Box<dyn Template + 'a>) -> Self::Property;
     fn wrap_list_template(template: Box<dyn ListTemplate + 'a>) -> Self::Property;
 
+    /// Settings which can be looked up by the `config()` function.
+    fn settings(&self) -> &UserSettings;
+
     /// Translates the given global `function` call to a property.
     ///
     /// This should be delegated to
@@ -91,6 +101,7 @@ macro_rules! impl_core_wrap_property_fns {
                 wrap_size_hint($crate::templater::SizeHint) => SizeHint,
                 wrap_timestamp(jj_lib::backend::Timestamp) => Timestamp,
                 wrap_timestamp_range($crate::templater::TimestampRange) => TimestampRange,
+                wrap_config_value(config::Value) => ConfigValue,
             }
         );
         fn wrap_template(
@@ -145,6 +156,7 @@ pub enum CoreTemplatePropertyKind<'a> {
     SizeHint(Box<dyn TemplateProperty<Output = SizeHint> + 'a>),
     Timestamp(Box<dyn TemplateProperty<Output = Timestamp> + 'a>),
     TimestampRange(Box<dyn TemplateProperty<Output = TimestampRange> + 'a>),
+    ConfigValue(Box<dyn TemplateProperty<Output = config::Value> + 'a>),
 
     // Both TemplateProperty and Template can represent a value to be evaluated
     // dynamically, which suggests that `Box<dyn Template + 'a>` could be
@@ -172,6 +184,7 @@ impl<'a> IntoTemplateProperty<'a> for CoreTemplatePropertyKind<'a> {
             CoreTemplatePropertyKind::SizeHint(_) => "SizeHint",
             CoreTemplatePropertyKind::Timestamp(_) => "Timestamp",
             CoreTemplatePropertyKind::TimestampRange(_) => "TimestampRange",
+            CoreTemplatePropertyKind::ConfigValue(_) => "ConfigValue",
             CoreTemplatePropertyKind::Template(_) => "Template",
             CoreTemplatePropertyKind::ListTemplate(_) => "ListTemplate",
         }
@@ -194,6 +207,7 @@ impl<'a> IntoTemplateProperty<'a> for CoreTemplatePropertyKind<'a> {
             CoreTemplatePropertyKind::SizeHint(_) => None,
             CoreTemplatePropertyKind::Timestamp(_) => None,
             CoreTemplatePropertyKind::TimestampRange(_) => None,
+            CoreTemplatePropertyKind::ConfigValue(_) => None,
             // Template types could also be evaluated to boolean, but it's less likely
             // to apply label() or .map() and use the result as conditional. It's also
             // unclear whether ListTemplate should behave as a "list" or a "template".
@@ -233,6 +247,7 @@ impl<'a> IntoTemplateProperty<'a> for CoreTemplatePropertyKind<'a> {
             CoreTemplatePropertyKind::SizeHint(_) => None,
             CoreTemplatePropertyKind::Timestamp(property) => Some(property.into_template()),
             CoreTemplatePropertyKind::TimestampRange(property) => Some(property.into_template()),
+            CoreTemplatePropertyKind::ConfigValue(property) => Some(property.into_template()),
             CoreTemplatePropertyKind::Template(template) => Some(template),
             CoreTemplatePropertyKind::ListTemplate(template) => Some(template.into_template()),
         }
@@ -278,6 +293,7 @@ pub struct CoreTemplateBuildFnTable<'a, L: TemplateLanguage<'a> + ?Sized> {
     pub size_hint_methods: TemplateBuildMethodFnMap<'a, L, SizeHint>,
     pub timestamp_methods: TemplateBuildMethodFnMap<'a, L, Timestamp>,
     pub timestamp_range_methods: TemplateBuildMethodFnMap<'a, L, TimestampRange>,
+    pub config_value_methods: TemplateBuildMethodFnMap<'a, L, config::Value>,
 }
 
 pub fn merge_fn_map<'s, F>(base: &mut HashMap<&'s str, F>, extension: HashMap<&'s str, F>) {
@@ -300,6 +316,7 @@ impl<'a, L: TemplateLanguage<'a> + ?Sized> CoreTemplateBuildFnTable<'a, L> {
             size_hint_methods: builtin_size_hint_methods(),
             timestamp_methods: builtin_timestamp_methods(),
             timestamp_range_methods: builtin_timestamp_range_methods(),
+            config_value_methods: builtin_config_value_methods(),
         }
     }
 
@@ -313,6 +330,7 @@ impl<'a, L: TemplateLanguage<'a> + ?Sized> CoreTemplateBuildFnTable<'a, L> {
             size_hint_methods: HashMap::new(),
             timestamp_methods: HashMap::new(),
             timestamp_range_methods: HashMap::new(),
+            config_value_methods: HashMap::new(),
         }
     }
 
@@ -326,6 +344,7 @@ impl<'a, L: TemplateLanguage<'a> + ?Sized> CoreTemplateBuildFnTable<'a, L> {
             size_hint_methods,
             timestamp_methods,
             timestamp_range_methods,
+            config_value_methods,
         } = extension;
 
         merge_fn_map(&mut self.functions, functions);
@@ -336,6 +355,7 @@ impl<'a, L: TemplateLanguage<'a> + ?Sized> CoreTemplateBuildFnTable<'a, L> {
         merge_fn_map(&mut self.size_hint_methods, size_hint_methods);
         merge_fn_map(&mut self.timestamp_methods, timestamp_methods);
         merge_fn_map(&mut self.timestamp_range_methods, timestamp_range_methods);
+        merge_fn_map(&mut self.config_value_methods, config_value_methods);
     }
 
     /// Translates the function call node `function` by using this symbol table.
@@ -409,6 +429,11 @@ impl<'a, L: TemplateLanguage<'a> + ?Sized> CoreTemplateBuildFnTable<'a, L> {
                 let build = template_parser::lookup_method(type_name, table, function)?;
                 build(language, build_ctx, property, function)
             }
+            CoreTemplatePropertyKind::ConfigValue(property) => {
+                let table = &self.config_value_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(language, build_ctx, property, function)
+            }
             CoreTemplatePropertyKind::Template(_) => {
                 // TODO: migrate to table?
                 Err(TemplateParseError::no_such_method(type_name, function))
@@ -835,6 +860,53 @@ fn builtin_timestamp_range_methods<'a, L: TemplateLanguage<'a> + ?Sized>(
     map
 }
 
+fn builtin_config_value_methods<'a, L: TemplateLanguage<'a> + ?Sized>(
+) -> TemplateBuildMethodFnMap<'a, L, config::Value> {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = TemplateBuildMethodFnMap::<L, config::Value>::new();
+    map.insert(
+        "as_boolean",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|value| Ok(value.into_bool()?));
+            Ok(L::wrap_boolean(out_property))
+        },
+    );
+    map.insert(
+        "as_integer",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|value| Ok(value.into_int()?));
+            Ok(L::wrap_integer(out_property))
+        },
+    );
+    map.insert(
+        "as_string",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|value| Ok(value.into_string()?));
+            Ok(L::wrap_string(out_property))
+        },
+    );
+    map.insert(
+        "as_string_list",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|value| {
+                let list = value
+                    .into_array()?
+                    .into_iter()
+                    .map(|item| item.into_string())
+                    .try_collect()?;
+                Ok(list)
+            });
+            Ok(L::wrap_string_list(out_property))
+        },
+    );
+    map
+}
+
 fn build_list_template_method<'a, L: TemplateLanguage<'a> + ?Sized>(
     language: &L,
     build_ctx: &BuildContext<L::Property>,
@@ -986,6 +1058,53 @@ fn builtin_functions<'a, L: TemplateLanguage<'a> + ?Sized>() -> TemplateBuildFun
         });
         Ok(L::wrap_template(Box::new(template)))
     });
+    map.insert("pad_start", |language, build_ctx, function| {
+        build_padding_function(language, build_ctx, function, text_util::write_padded_start)
+    });
+    map.insert("pad_end", |language, build_ctx, function| {
+        build_padding_function(language, build_ctx, function, text_util::write_padded_end)
+    });
+    map.insert("pad_centered", |language, build_ctx, function| {
+        build_padding_function(
+            language,
+            build_ctx,
+            function,
+            text_util::write_padded_centered,
+        )
+    });
+    map.insert("config", |language, _build_ctx, function| {
+        let [name_node] = function.expect_exact_arguments()?;
+        let name =
+            template_parser::expect_string_literal_with(name_node, |name, _| Ok(name.to_owned()))?;
+        // The settings don't change while rendering, so the value can be
+        // looked up upfront. A missing value is reported when it's evaluated.
+        let value = language
+            .settings()
+            .config()
+            .get::<config::Value>(&name)
+            .map_err(|err| err.to_string());
+        let out_property = Literal(value)
+            .and_then(|value| value.map_err(|message| TemplatePropertyError(message.into())));
+        Ok(L::wrap_config_value(out_property))
+    });
+    map.insert("env", |language, _build_ctx, function| {
+        let [name_node] = function.expect_exact_arguments()?;
+        let name =
+            template_parser::expect_string_literal_with(name_node, |name, _| Ok(name.to_owned()))?;
+        let allowed = language
+            .settings()
+            .config()
+            .get_bool("ui.allow-template-env")
+            .unwrap_or(false);
+        if !allowed {
+            return Err(TemplateParseError::expression(
+                "Access to environment variables is disabled by ui.allow-template-env",
+                function.name_span,
+            ));
+        }
+        let value = env::var(name).unwrap_or_default();
+        Ok(L::wrap_string(Literal(value)))
+    });
     map.insert("label", |language, build_ctx, function| {
         let [label_node, content_node] = function.expect_exact_arguments()?;
         let label_property = expect_plain_text_expression(language, build_ctx, label_node)?;
@@ -996,6 +1115,22 @@ fn builtin_functions<'a, L: TemplateLanguage<'a> + ?Sized>() -> TemplateBuildFun
             content, labels,
         ))))
     });
+    map.insert("label_if", |language, build_ctx, function| {
+        let [condition_node, label_node, content_node] = function.expect_exact_arguments()?;
+        let condition = expect_boolean_expression(language, build_ctx, condition_node)?;
+        let label_property = expect_plain_text_expression(language, build_ctx, label_node)?;
+        let content = expect_template_expression(language, build_ctx, content_node)?;
+        let labels = (condition, label_property).map(|(condition, s)| {
+            if condition {
+                s.split_whitespace().map(ToString::to_string).collect()
+            } else {
+                vec![]
+            }
+        });
+        Ok(L::wrap_template(Box::new(LabelTemplate::new(
+            content, labels,
+        ))))
+    });
     map.insert("if", |language, build_ctx, function| {
         let ([condition_node, true_node], [false_node]) = function.expect_arguments()?;
         let condition = expect_boolean_expression(language, build_ctx, condition_node)?;
@@ -1007,16 +1142,16 @@ fn builtin_functions<'a, L: TemplateLanguage<'a> + ?Sized>() -> TemplateBuildFun
         Ok(L::wrap_template(Box::new(template)))
     });
     map.insert("coalesce", |language, build_ctx, function| {
-        let contents = function
-            .args
+        let ([], content_nodes) = function.expect_some_arguments()?;
+        let contents = content_nodes
             .iter()
             .map(|node| expect_template_expression(language, build_ctx, node))
             .try_collect()?;
         Ok(L::wrap_template(Box::new(CoalesceTemplate(contents))))
     });
     map.insert("concat", |language, build_ctx, function| {
-        let contents = function
-            .args
+        let ([], content_nodes) = function.expect_some_arguments()?;
+        let contents = content_nodes
             .iter()
             .map(|node| expect_template_expression(language, build_ctx, node))
             .try_collect()?;
@@ -1052,6 +1187,23 @@ fn builtin_functions<'a, L: TemplateLanguage<'a> + ?Sized>() -> TemplateBuildFun
     map
 }
 
+fn build_padding_function<'a, L: TemplateLanguage<'a> + ?Sized>(
+    language: &L,
+    build_ctx: &BuildContext<L::Property>,
+    function: &FunctionCallNode,
+    write_padded: fn(&mut dyn Formatter, &FormatRecorder, usize) -> io::Result<()>,
+) -> TemplateParseResult<L::Property> {
+    let [width_node, content_node] = function.expect_exact_arguments()?;
+    let width = expect_usize_expression(language, build_ctx, width_node)?;
+    let content = expect_template_expression(language, build_ctx, content_node)?;
+    let template =
+        ReformatTemplate::new(content, move |formatter, recorded| match width.extract() {
+            Ok(width) => write_padded(formatter.as_mut(), recorded, width),
+            Err(err) => formatter.handle_error(err),
+        });
+    Ok(L::wrap_template(Box::new(template)))
+}
+
 /// Builds intermediate expression tree from AST nodes.
 pub fn build_expression<'a, L: TemplateLanguage<'a> + ?Sized>(
     language: &L,
@@ -1261,8 +1413,13 @@ mod tests {
 
     impl TestTemplateEnv {
         fn new() -> Self {
+            Self::with_config(config::Config::default())
+        }
+
+        fn with_config(config: config::Config) -> Self {
+            let settings = UserSettings::from_config(config);
             TestTemplateEnv {
-                language: L::new(),
+                language: L::new(&settings),
                 aliases_map: TemplateAliasesMap::new(),
                 color_rules: Vec::new(),
             }
@@ -1988,6 +2145,33 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn test_pad_function() {
+        let mut env = TestTemplateEnv::new();
+        env.add_keyword("bad_int", || L::wrap_integer(new_error_property("Bad")));
+        env.add_color("red", crossterm::style::Color::Red);
+
+        insta::assert_snapshot!(env.render_ok(r#""{" ++ pad_start(5, "foo") ++ "}""#), @"{  foo}");
+        insta::assert_snapshot!(env.render_ok(r#""{" ++ pad_end(5, "foo") ++ "}""#), @"{foo  }");
+        insta::assert_snapshot!(env.render_ok(r#""{" ++ pad_centered(8, "foo") ++ "}""#), @"{  foo   }");
+
+        // Content wider than width is returned unchanged
+        insta::assert_snapshot!(
+            env.render_ok(r#""{" ++ pad_centered(2, "foo") ++ "}""#), @"{foo}");
+        insta::assert_snapshot!(
+            env.render_ok(r#""{" ++ pad_centered(-1, "foo") ++ "}""#),
+            @"{<Error: out of range integral type conversion attempted>}");
+
+        // Padding isn't labeled
+        insta::assert_snapshot!(
+            env.render_ok(r#""{" ++ pad_centered(5, label("red", "foo")) ++ "}""#),
+            @"{ [38;5;9mfoo[39m }");
+
+        // Error in width
+        insta::assert_snapshot!(
+            env.render_ok(r#""{" ++ pad_start(bad_int, "foo") ++ "}""#), @"{<Error: Bad>}");
+    }
+
     #[test]
     fn test_fill_function() {
         let mut env = TestTemplateEnv::new();
@@ -2148,6 +2332,118 @@ mod tests {
             @"[38;5;1mtext[39m");
     }
 
+    #[test]
+    fn test_label_if_function() {
+        let mut env = TestTemplateEnv::new();
+        env.add_keyword("empty", || L::wrap_boolean(Literal(true)));
+        env.add_keyword("bad_bool", || L::wrap_boolean(new_error_property("Bad")));
+        env.add_color("error", crossterm::style::Color::DarkRed);
+        env.add_color("warning", crossterm::style::Color::DarkYellow);
+
+        insta::assert_snapshot!(
+            env.render_ok(r#"label_if(empty, "error", "text")"#),
+            @"[38;5;1mtext[39m");
+        insta::assert_snapshot!(
+            env.render_ok(r#"label_if(!empty, "error", "text")"#),
+            @"text");
+
+        // Evaluated label
+        insta::assert_snapshot!(
+            env.render_ok(r#"label_if(empty, if(empty, "error", "warning"), "text")"#),
+            @"[38;5;1mtext[39m");
+
+        // Error in condition
+        insta::assert_snapshot!(
+            env.render_ok(r#"label_if(bad_bool, "error", "text")"#),
+            @"[38;5;1m<Error: Bad>[39m");
+
+        insta::assert_snapshot!(env.parse_err(r#"label_if(true, "error")"#), @r###"
+         --> 1:10
+          |
+        1 | label_if(true, "error")
+          |          ^-----------^
+          |
+          = Function "label_if": Expected 3 arguments
+        "###);
+        insta::assert_snapshot!(env.parse_err(r#"label_if(label("foo", "bar"), "error", "text")"#), @r###"
+         --> 1:10
+          |
+        1 | label_if(label("foo", "bar"), "error", "text")
+          |          ^-----------------^
+          |
+          = Expected expression of type "Boolean", but actual type is "Template"
+        "###);
+    }
+
+    #[test]
+    fn test_config_function() {
+        let config = config::Config::builder()
+            .set_override("test.string", "foo")
+            .unwrap()
+            .set_override("test.integer", 42)
+            .unwrap()
+            .set_override("test.boolean", true)
+            .unwrap()
+            .set_override("test.list", vec!["a", "b"])
+            .unwrap()
+            .build()
+            .unwrap();
+        let env = TestTemplateEnv::with_config(config);
+
+        insta::assert_snapshot!(env.render_ok(r#"config("test.string")"#), @r###""foo""###);
+        insta::assert_snapshot!(env.render_ok(r#"config("test.integer")"#), @r###"
+        42
+        "###);
+        insta::assert_snapshot!(env.render_ok(r#"config("test.list")"#), @r###"["a", "b"]"###);
+
+        insta::assert_snapshot!(
+            env.render_ok(r#"config("test.string").as_string().upper()"#), @r###"
+        FOO
+        "###);
+        insta::assert_snapshot!(
+            env.render_ok(r#"config("test.integer").as_integer()"#), @r###"
+        42
+        "###);
+        insta::assert_snapshot!(
+            env.render_ok(r#"if(config("test.boolean").as_boolean(), "yes", "no")"#), @r###"
+        yes
+        "###);
+        insta::assert_snapshot!(
+            env.render_ok(r#"config("test.list").as_string_list().join("|")"#), @r###"
+        a|b
+        "###);
+
+        // Missing or mistyped values are reported when evaluated
+        insta::assert_snapshot!(
+            env.render_ok(r#"config("test.missing")"#),
+            @r###"<Error: configuration property "test.missing" not found>"###);
+        insta::assert_snapshot!(
+            env.render_ok(r#"config("test.string").as_integer()"#),
+            @r###"<Error: invalid type: string "foo", expected an integer>"###);
+        insta::assert_snapshot!(
+            env.render_ok(r#"config("test.list").as_string()"#),
+            @r###"
+        <Error: invalid type: sequence, expected a string>
+        "###);
+
+        insta::assert_snapshot!(env.parse_err(r#"config("test." ++ "string")"#), @r###"
+         --> 1:8
+          |
+        1 | config("test." ++ "string")
+          |        ^-----------------^
+          |
+          = Expected string literal
+        "###);
+        insta::assert_snapshot!(env.parse_err(r#"config("test.string").foo()"#), @r###"
+         --> 1:23
+          |
+        1 | config("test.string").foo()
+          |                       ^-^
+          |
+          = Method "foo" doesn't exist for type "ConfigValue"
+        "###);
+    }
+
     #[test]
     fn test_coalesce_function() {
         let mut env = TestTemplateEnv::new();
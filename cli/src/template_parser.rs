@@ -19,7 +19,7 @@ use itertools::Itertools as _;
 use jj_lib::dsl_util::{
     self, collect_similar, AliasDeclaration, AliasDeclarationParser, AliasDefinitionParser,
     AliasExpandError, AliasExpandableExpression, AliasId, AliasesMap, ExpressionFolder,
-    FoldableExpression, InvalidArguments, StringLiteralParser,
+    FoldableExpression, InvalidArguments, KeywordArgument, StringLiteralParser,
 };
 use once_cell::sync::Lazy;
 use pest::iterators::{Pair, Pairs};
@@ -58,6 +58,8 @@ impl Rule {
             Rule::prefix_ops => None,
             Rule::infix_ops => None,
             Rule::function => None,
+            Rule::keyword_argument => None,
+            Rule::argument => None,
             Rule::function_arguments => None,
             Rule::lambda => None,
             Rule::formal_parameters => None,
@@ -66,6 +68,8 @@ impl Rule {
             Rule::expression => None,
             Rule::template => None,
             Rule::program => None,
+            Rule::alias_formal_parameter => None,
+            Rule::alias_formal_parameters => None,
             Rule::function_alias_declaration => None,
             Rule::alias_declaration => None,
         }
@@ -401,6 +405,94 @@ fn parse_formal_parameters(params_pair: Pair<Rule>) -> TemplateParseResult<Vec<&
     }
 }
 
+/// Parses alias parameters into names and default values of the trailing
+/// parameters.
+fn parse_alias_formal_parameters(
+    params_pair: Pair<Rule>,
+) -> TemplateParseResult<(Vec<String>, Vec<String>)> {
+    assert_eq!(params_pair.as_rule(), Rule::alias_formal_parameters);
+    let params_span = params_pair.as_span();
+    let mut params = Vec::new();
+    let mut default_nodes = Vec::new();
+    for pair in params_pair.into_inner() {
+        let span = pair.as_span();
+        let mut inner = pair.into_inner();
+        let name = parse_identifier_name(inner.next().unwrap())?;
+        if let Some(value_pair) = inner.next() {
+            default_nodes.push(parse_template_node(value_pair)?);
+        } else if !default_nodes.is_empty() {
+            return Err(TemplateParseError::expression(
+                "Parameter without default value follows parameter with default value",
+                span,
+            ));
+        }
+        params.push(name);
+    }
+    if !params.iter().all_unique() {
+        return Err(TemplateParseError::with_span(
+            TemplateParseErrorKind::RedefinedFunctionParameter,
+            params_span,
+        ));
+    }
+    // Default values are resolved in a fresh scope, where the parameter names
+    // would silently be looked up as keywords.
+    for node in &default_nodes {
+        if let Some((name, span)) = find_identifier(node, &params) {
+            return Err(TemplateParseError::expression(
+                format!(r#"Default value cannot refer to parameter "{name}""#),
+                span,
+            ));
+        }
+    }
+    let params = params.into_iter().map(|s| s.to_owned()).collect();
+    let defaults = default_nodes
+        .iter()
+        .map(|node| node.span.as_str().to_owned())
+        .collect();
+    Ok((params, defaults))
+}
+
+/// Looks up the first occurrence of the given `names` in expression tree.
+/// Names shadowed by lambda parameters are ignored.
+fn find_identifier<'i>(
+    node: &ExpressionNode<'i>,
+    names: &[&str],
+) -> Option<(&'i str, pest::Span<'i>)> {
+    match &node.kind {
+        ExpressionKind::Identifier(name) => names.contains(name).then_some((*name, node.span)),
+        ExpressionKind::Boolean(_) | ExpressionKind::Integer(_) | ExpressionKind::String(_) => None,
+        ExpressionKind::Unary(_, arg) => find_identifier(arg, names),
+        ExpressionKind::Binary(_, lhs, rhs) => {
+            find_identifier(lhs, names).or_else(|| find_identifier(rhs, names))
+        }
+        ExpressionKind::Concat(nodes) => nodes.iter().find_map(|n| find_identifier(n, names)),
+        ExpressionKind::FunctionCall(function) => find_identifier_in_args(function, names),
+        ExpressionKind::MethodCall(method) => find_identifier(&method.object, names)
+            .or_else(|| find_identifier_in_args(&method.function, names)),
+        ExpressionKind::Lambda(lambda) => {
+            let names = names
+                .iter()
+                .copied()
+                .filter(|name| !lambda.params.contains(name))
+                .collect_vec();
+            find_identifier(&lambda.body, &names)
+        }
+        ExpressionKind::AliasExpanded(_, subst) => find_identifier(subst, names),
+    }
+}
+
+fn find_identifier_in_args<'i>(
+    function: &FunctionCallNode<'i>,
+    names: &[&str],
+) -> Option<(&'i str, pest::Span<'i>)> {
+    let keyword_values = function.keyword_args.iter().map(|arg| &arg.value);
+    function
+        .args
+        .iter()
+        .chain(keyword_values)
+        .find_map(|node| find_identifier(node, names))
+}
+
 fn parse_function_call_node(pair: Pair<Rule>) -> TemplateParseResult<FunctionCallNode> {
     assert_eq!(pair.as_rule(), Rule::function);
     let mut inner = pair.into_inner();
@@ -410,15 +502,41 @@ fn parse_function_call_node(pair: Pair<Rule>) -> TemplateParseResult<FunctionCal
     let args_span = args_pair.as_span();
     assert_eq!(args_pair.as_rule(), Rule::function_arguments);
     let name = parse_identifier_name(name_pair)?;
-    let args = args_pair
-        .into_inner()
-        .map(parse_template_node)
-        .try_collect()?;
+    let mut args = Vec::new();
+    let mut keyword_args = Vec::new();
+    for pair in args_pair.into_inner() {
+        let span = pair.as_span();
+        match pair.as_rule() {
+            Rule::template => {
+                if !keyword_args.is_empty() {
+                    return Err(InvalidArguments {
+                        name,
+                        message: "Positional argument follows keyword argument".to_owned(),
+                        span,
+                    }
+                    .into());
+                }
+                args.push(parse_template_node(pair)?);
+            }
+            Rule::keyword_argument => {
+                let mut inner = pair.into_inner();
+                let name_pair = inner.next().unwrap();
+                let value_pair = inner.next().unwrap();
+                let arg = KeywordArgument {
+                    name: parse_identifier_name(name_pair.clone())?,
+                    name_span: name_pair.as_span(),
+                    value: parse_template_node(value_pair)?,
+                };
+                keyword_args.push(arg);
+            }
+            r => panic!("unexpected argument rule {r:?}"),
+        }
+    }
     Ok(FunctionCallNode {
         name,
         name_span,
         args,
-        keyword_args: vec![], // unsupported
+        keyword_args,
         args_span,
     })
 }
@@ -573,11 +691,16 @@ impl AliasDeclarationParser for TemplateAliasParser {
                 let name_pair = inner.next().unwrap();
                 let params_pair = inner.next().unwrap();
                 let name = parse_identifier_name(name_pair)?.to_owned();
-                let params = parse_formal_parameters(params_pair)?
-                    .into_iter()
-                    .map(|s| s.to_owned())
-                    .collect();
-                Ok(AliasDeclaration::Function(name, params))
+                let (params, defaults) = parse_alias_formal_parameters(params_pair)?;
+                if defaults.is_empty() {
+                    Ok(AliasDeclaration::Function(name, params))
+                } else {
+                    Ok(AliasDeclaration::FunctionWithDefaults {
+                        name,
+                        params,
+                        defaults,
+                    })
+                }
             }
             r => panic!("unexpected alias declaration rule {r:?}"),
         }
@@ -591,6 +714,10 @@ impl AliasDefinitionParser for TemplateAliasParser {
     fn parse_definition<'i>(&self, source: &'i str) -> Result<ExpressionNode<'i>, Self::Error> {
         parse_template(source)
     }
+
+    fn allows_keyword_arguments(&self) -> bool {
+        true
+    }
 }
 
 /// Parses text into AST nodes, and expands aliases.
@@ -696,7 +823,6 @@ pub fn lookup_method<'a, V>(
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
-    use jj_lib::dsl_util::KeywordArgument;
 
     use super::*;
 
@@ -884,6 +1010,17 @@ mod tests {
         assert!(parse_template("false()").is_err());
         // Function arguments can be any expression
         assert!(parse_template("f(false)").is_ok());
+
+        // Keyword arguments
+        assert!(parse_template("f(a, b=c)").is_ok());
+        assert!(parse_template("f(b = c ++ d,)").is_ok());
+        assert_eq!(
+            parse_template("f(b=c, a)").unwrap_err().kind,
+            TemplateParseErrorKind::InvalidArguments {
+                name: "f".to_owned(),
+                message: "Positional argument follows keyword argument".to_owned(),
+            }
+        );
     }
 
     #[test]
@@ -1082,6 +1219,50 @@ mod tests {
         assert!(aliases_map.insert("m(a,,b)", r#"""#).is_err());
     }
 
+    #[test]
+    fn test_parse_alias_decl_default_value() {
+        let mut aliases_map = TemplateAliasesMap::new();
+        aliases_map
+            .insert(r#"func(a, sep = "/", b=x ++ y)"#, "a")
+            .unwrap();
+        let (_, params, _) = aliases_map.get_function("func", 3).unwrap();
+        assert_eq!(params, ["a", "sep", "b"]);
+        assert!(aliases_map.get_function("func", 1).is_some());
+        assert!(aliases_map.get_function("func", 0).is_none());
+
+        // Default value must be a valid template
+        assert!(aliases_map.insert("f(a=)", r#"""#).is_err());
+        assert!(aliases_map.insert("f(a=b ++)", r#"""#).is_err());
+
+        // Parameter with default value must be trailing
+        assert_matches!(
+            aliases_map.insert("f(a=b, c)", r#"""#).unwrap_err().kind,
+            TemplateParseErrorKind::Expression(_)
+        );
+        assert_eq!(
+            aliases_map.insert("f(a=b, a=c)", r#"""#).unwrap_err().kind,
+            TemplateParseErrorKind::RedefinedFunctionParameter
+        );
+
+        // Default value cannot refer to parameters
+        assert_eq!(
+            aliases_map.insert("f(a, b=a)", r#"""#).unwrap_err().kind,
+            TemplateParseErrorKind::Expression(
+                r#"Default value cannot refer to parameter "a""#.to_owned()
+            )
+        );
+        assert_eq!(
+            aliases_map
+                .insert("f(a=x.f(b), b=y)", r#"""#)
+                .unwrap_err()
+                .kind,
+            TemplateParseErrorKind::Expression(
+                r#"Default value cannot refer to parameter "b""#.to_owned()
+            )
+        );
+        assert!(aliases_map.insert("f(a, b=x.map(|a| a))", r#"""#).is_ok());
+    }
+
     #[test]
     fn test_expand_symbol_alias() {
         assert_eq!(
@@ -1239,6 +1420,87 @@ mod tests {
             TemplateParseErrorKind::InvalidArguments { .. }
         );
 
+        // Keyword arguments are bound by parameter name.
+        assert_eq!(
+            with_aliases([("F(x, y)", "x ++ y")]).parse_normalized("F(y=b, x=a)"),
+            parse_normalized("a ++ b"),
+        );
+        assert_eq!(
+            with_aliases([("F(x, y)", "x ++ y")]).parse_normalized("F(a, y=b)"),
+            parse_normalized("a ++ b"),
+        );
+        assert_eq!(
+            with_aliases([("F(x)", "x")])
+                .parse("F(y=a)")
+                .unwrap_err()
+                .kind,
+            TemplateParseErrorKind::InvalidArguments {
+                name: "F".to_owned(),
+                message: r#"Unexpected keyword argument "y""#.to_owned(),
+            }
+        );
+        assert_eq!(
+            with_aliases([("F(x, y)", "x ++ y")])
+                .parse("F(a, x=b)")
+                .unwrap_err()
+                .kind,
+            TemplateParseErrorKind::InvalidArguments {
+                name: "F".to_owned(),
+                message: r#"Got multiple values for keyword "x""#.to_owned(),
+            }
+        );
+        assert_eq!(
+            with_aliases([("F(x, y=c)", "x ++ y")])
+                .parse("F(y=b)")
+                .unwrap_err()
+                .kind,
+            TemplateParseErrorKind::InvalidArguments {
+                name: "F".to_owned(),
+                message: r#"Missing argument for parameter "x""#.to_owned(),
+            }
+        );
+
+        // Missing parameters are filled with default values.
+        assert_eq!(
+            with_aliases([(r#"F(x, sep="/")"#, "x ++ sep")]).parse_normalized("F(a)"),
+            parse_normalized(r#"a ++ "/""#),
+        );
+        assert_eq!(
+            with_aliases([(r#"F(x, sep="/")"#, "x ++ sep")]).parse_normalized("F(a, b)"),
+            parse_normalized("a ++ b"),
+        );
+        assert_eq!(
+            with_aliases([("F(x=a, y=b)", "x ++ y")]).parse_normalized("F(y=c)"),
+            parse_normalized("a ++ c"),
+        );
+        // Exact arity match takes precedence.
+        assert_eq!(
+            with_aliases([("F(x, y=b)", "x ++ y"), ("F(x)", "x")]).parse_normalized("F(a)"),
+            parse_normalized("a"),
+        );
+        // Default value is resolved in a fresh scope, but aliases are expanded.
+        assert_eq!(
+            with_aliases([("F(x, y=z ++ A)", "y"), ("A", "w")]).parse_normalized("F(a)"),
+            parse_normalized("z ++ w"),
+        );
+        assert_eq!(
+            with_aliases([("F(x, y=F(b))", "y")])
+                .parse("F(a)")
+                .unwrap_err()
+                .kind,
+            TemplateParseErrorKind::BadAliasExpansion("F(x, y)".to_owned()),
+        );
+        assert_eq!(
+            with_aliases([("F(x, y=b)", "x ++ y")])
+                .parse("F()")
+                .unwrap_err()
+                .kind,
+            TemplateParseErrorKind::InvalidArguments {
+                name: "F".to_owned(),
+                message: "Expected 1 to 2 arguments".to_owned(),
+            }
+        );
+
         // Infinite recursion, where the top-level error isn't of RecursiveAlias kind.
         assert_eq!(
             with_aliases([("F(x)", "G(x)"), ("G(x)", "H(x)"), ("H(x)", "F(x)")])
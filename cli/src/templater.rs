@@ -18,6 +18,7 @@ use std::{error, fmt, io, iter};
 
 use jj_lib::backend::{Signature, Timestamp};
 
+use crate::config::to_toml_value;
 use crate::formatter::{FormatRecorder, Formatter, LabeledWriter, PlainTextFormatter};
 use crate::time_util;
 
@@ -74,6 +75,15 @@ impl Template for Signature {
     }
 }
 
+impl Template for config::Value {
+    fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
+        match to_toml_value(self) {
+            Ok(value) => write!(formatter, "{value}"),
+            Err(err) => formatter.handle_error(err.into()),
+        }
+    }
+}
+
 // In template language, an integer value is represented as i64. However, we use
 // usize here because it's more convenient to guarantee that the lower value is
 // bounded to 0.
@@ -664,6 +674,15 @@ impl<'a, C: Clone> TemplateRenderer<'a, C> {
             format_labeled(&mut wrapper, &self.template, &self.labels)
         })
     }
+
+    /// Like `format()`, but fails on property evaluation error instead of
+    /// rendering the error inline.
+    pub fn try_format(&self, context: &C, formatter: &mut dyn Formatter) -> io::Result<()> {
+        let mut wrapper = TemplateFormatter::new(formatter, propagate_property_error);
+        self.placeholder.with_value(context.clone(), || {
+            format_labeled(&mut wrapper, &self.template, &self.labels)
+        })
+    }
 }
 
 /// Wrapper to pass around `Formatter` and error handler.
@@ -15,7 +15,7 @@
 use std::borrow::Cow;
 use std::{cmp, io};
 
-use unicode_width::UnicodeWidthChar as _;
+use unicode_width::{UnicodeWidthChar as _, UnicodeWidthStr as _};
 
 use crate::formatter::{FormatRecorder, Formatter};
 
@@ -103,6 +103,63 @@ fn trim_start_zero_width_chars(text: &str) -> &str {
     text.trim_start_matches(|c: char| c.width().unwrap_or(0) == 0)
 }
 
+/// Writes text padded with spaces on the left so that it occupies at least
+/// `min_width` columns. The text is written unchanged if it's wider than that.
+pub fn write_padded_start(
+    formatter: &mut dyn Formatter,
+    recorded_content: &FormatRecorder,
+    min_width: usize,
+) -> io::Result<()> {
+    let padding = padding_width(recorded_content, min_width);
+    write_padded(formatter, recorded_content, padding, 0)
+}
+
+/// Writes text padded with spaces on the right so that it occupies at least
+/// `min_width` columns. The text is written unchanged if it's wider than that.
+pub fn write_padded_end(
+    formatter: &mut dyn Formatter,
+    recorded_content: &FormatRecorder,
+    min_width: usize,
+) -> io::Result<()> {
+    let padding = padding_width(recorded_content, min_width);
+    write_padded(formatter, recorded_content, 0, padding)
+}
+
+/// Writes text centered within `min_width` columns. If the padding can't be
+/// split evenly, the extra space goes to the right. The text is written
+/// unchanged if it's wider than `min_width`.
+pub fn write_padded_centered(
+    formatter: &mut dyn Formatter,
+    recorded_content: &FormatRecorder,
+    min_width: usize,
+) -> io::Result<()> {
+    let padding = padding_width(recorded_content, min_width);
+    write_padded(
+        formatter,
+        recorded_content,
+        padding / 2,
+        padding - padding / 2,
+    )
+}
+
+fn padding_width(recorded_content: &FormatRecorder, min_width: usize) -> usize {
+    let text = String::from_utf8_lossy(recorded_content.data());
+    min_width.saturating_sub(text.width())
+}
+
+fn write_padded(
+    formatter: &mut dyn Formatter,
+    recorded_content: &FormatRecorder,
+    left_width: usize,
+    right_width: usize,
+) -> io::Result<()> {
+    // The padding isn't labeled by the content labels.
+    write!(formatter, "{:left_width$}", "")?;
+    recorded_content.replay(formatter)?;
+    write!(formatter, "{:right_width$}", "")?;
+    Ok(())
+}
+
 /// Indents each line by the given prefix preserving labels.
 pub fn write_indented(
     formatter: &mut dyn Formatter,
@@ -492,6 +549,49 @@ mod tests {
         assert_eq!(lines[4].as_ptr(), text[14..].as_ptr());
     }
 
+    #[test]
+    fn test_write_padded() {
+        let mut recorder = FormatRecorder::new();
+        for (label, word) in [("red", "foo"), ("cyan", "bar")] {
+            recorder.push_label(label).unwrap();
+            write!(recorder, "{word}").unwrap();
+            recorder.pop_label().unwrap();
+        }
+        fn bracketed(
+            write_padded: fn(&mut dyn Formatter, &FormatRecorder, usize) -> io::Result<()>,
+            recorder: &FormatRecorder,
+            width: usize,
+        ) -> impl FnOnce(&mut dyn Formatter) -> io::Result<()> + '_ {
+            move |formatter| {
+                write!(formatter, "[")?;
+                write_padded(formatter, recorder, width)?;
+                write!(formatter, "]")
+            }
+        }
+
+        insta::assert_snapshot!(
+            format_colored(bracketed(write_padded_start, &recorder, 10)),
+            @"[    [38;5;1mfoo[39m[38;5;6mbar[39m]");
+        insta::assert_snapshot!(
+            format_colored(bracketed(write_padded_end, &recorder, 10)),
+            @"[[38;5;1mfoo[39m[38;5;6mbar[39m    ]");
+        insta::assert_snapshot!(
+            format_colored(bracketed(write_padded_centered, &recorder, 10)),
+            @"[  [38;5;1mfoo[39m[38;5;6mbar[39m  ]");
+
+        // Wider content is written unchanged
+        insta::assert_snapshot!(
+            format_plain_text(bracketed(write_padded_centered, &recorder, 5)),
+            @"[foobar]");
+
+        // East Asian characters (char.width() == 2)
+        let mut recorder = FormatRecorder::new();
+        write!(recorder, "一二").unwrap();
+        insta::assert_snapshot!(
+            format_plain_text(bracketed(write_padded_centered, &recorder, 7)),
+            @"[ 一二  ]");
+    }
+
     #[test]
     fn test_write_wrapped() {
         // Split single label chunk
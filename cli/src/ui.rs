@@ -233,6 +233,9 @@ fn prepare_formatter_factory(
         ColorChoice::Auto => (terminal, false),
     };
     if color {
+        // crossterm strips styles if NO_COLOR is set, which would defeat an
+        // explicit request for color from the config or command line.
+        crossterm::style::force_color_output(true);
         FormatterFactory::color(config, debug)
     } else if terminal {
         // Sanitize ANSI escape codes if we're printing to a terminal. Doesn't
@@ -0,0 +1,33 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::process::ExitCode;
+
+use jj_cli::cli_util::CliRunner;
+
+/// A `jj` binary with a dispatch hook, useful for testing
+///
+/// The hook reports the command it observed to stderr as
+/// `dispatch-hook: <command> <ok|error> <elapsed>`, where `<elapsed>` is
+/// `elapsed` if a duration was passed to the hook.
+fn main() -> ExitCode {
+    CliRunner::init()
+        .add_dispatch_hook(Box::new(|name, elapsed, result| {
+            let status = if result.is_ok() { "ok" } else { "error" };
+            // The duration isn't deterministic, but it should be measured.
+            let elapsed = if elapsed.is_zero() { "" } else { "elapsed" };
+            eprintln!("dispatch-hook: {name} {status} {elapsed}");
+        }))
+        .run()
+}
@@ -83,7 +83,17 @@ impl Default for TestEnvironment {
 
 impl TestEnvironment {
     pub fn jj_cmd(&self, current_dir: &Path, args: &[&str]) -> assert_cmd::Command {
-        let mut cmd = assert_cmd::Command::cargo_bin("jj").unwrap();
+        self.jj_cmd_with_bin("jj", current_dir, args)
+    }
+
+    /// Like `jj_cmd()`, but runs the named binary built with `CliRunner`.
+    pub fn jj_cmd_with_bin(
+        &self,
+        bin_name: &str,
+        current_dir: &Path,
+        args: &[&str],
+    ) -> assert_cmd::Command {
+        let mut cmd = assert_cmd::Command::cargo_bin(bin_name).unwrap();
         cmd.current_dir(current_dir);
         cmd.args(args);
         cmd.env_clear();
@@ -23,9 +23,12 @@ mod test_debug_command;
 mod test_describe_command;
 mod test_diff_command;
 mod test_diffedit_command;
+mod test_dispatch_hook;
 mod test_duplicate_command;
 mod test_edit_command;
 mod test_file_chmod_command;
+mod test_file_list_command;
+mod test_file_move_command;
 mod test_file_print_command;
 mod test_fix_command;
 mod test_generate_md_cli_help;
@@ -39,6 +42,7 @@ mod test_git_remotes;
 mod test_git_submodule;
 mod test_gitignores;
 mod test_global_opts;
+mod test_help_command;
 mod test_immutable_commits;
 mod test_init_command;
 mod test_interdiff_command;
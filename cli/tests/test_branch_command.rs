@@ -57,6 +57,32 @@ fn test_branch_multiple_names() {
     "###);
 }
 
+#[test]
+fn test_branch_set_multiple_names_atomic() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "baz"]);
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "foo", "bar"]);
+
+    // If any of the branches can't be updated, none of them should be.
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["branch", "set", "-r@-", "foo", "baz", "bar", "foo"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Refusing to move branch backwards or sideways: foo, bar
+    Hint: Use --allow-backwards to allow it.
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    @  bar foo 4db490c88528
+    ◉  baz 230dd059e1b0
+    ◉   000000000000
+    "###);
+}
+
 #[test]
 fn test_branch_at_root() {
     let test_env = TestEnvironment::default();
@@ -1741,6 +1767,125 @@ fn test_branch_list_conflicted() {
     "###);
 }
 
+#[test]
+fn test_branch_list_contains() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.auto-local-branch = true");
+
+    // Initialize remote refs
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "remote"]);
+    let remote_path = test_env.env_root().join("remote");
+    test_env.jj_cmd_ok(&remote_path, &["new", "root()", "-m", "fix"]);
+    test_env.jj_cmd_ok(&remote_path, &["branch", "create", "fix"]);
+    test_env.jj_cmd_ok(&remote_path, &["new", "-m", "feature"]);
+    test_env.jj_cmd_ok(&remote_path, &["branch", "create", "feature"]);
+    test_env.jj_cmd_ok(&remote_path, &["new", "root()", "-m", "unrelated"]);
+    test_env.jj_cmd_ok(&remote_path, &["branch", "create", "unrelated"]);
+    test_env.jj_cmd_ok(&remote_path, &["new"]);
+    test_env.jj_cmd_ok(&remote_path, &["git", "export"]);
+
+    // Initialize local refs
+    let mut remote_git_path = remote_path;
+    remote_git_path.extend([".jj", "repo", "store", "git"]);
+    test_env.jj_cmd_ok(
+        test_env.env_root(),
+        &["git", "clone", remote_git_path.to_str().unwrap(), "local"],
+    );
+    let local_path = test_env.env_root().join("local");
+    // Only the remote target of "feature" contains the fix
+    test_env.jj_cmd_ok(
+        &local_path,
+        &[
+            "branch",
+            "set",
+            "--allow-backwards",
+            "feature",
+            "-r",
+            "unrelated",
+        ],
+    );
+
+    let template = r#"name ++ if(remote, "@" ++ remote) ++ "\n""#;
+    let list = |args: &[&str]| {
+        test_env.jj_cmd_success(
+            &local_path,
+            &[&["branch", "list", "-T", template], args].concat(),
+        )
+    };
+    insta::assert_snapshot!(list(&["--contains", "fix"]), @r###"
+    feature
+    feature@origin
+    fix
+    "###);
+    insta::assert_snapshot!(list(&["--contains", "unrelated"]), @r###"
+    feature
+    feature@origin
+    unrelated
+    "###);
+    insta::assert_snapshot!(list(&["--contains", "fix", "glob:fe*"]), @r###"
+    feature
+    feature@origin
+    "###);
+}
+
+#[test]
+fn test_branch_list_sort() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "a"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "zzz"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "b"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "aaa"]);
+    // Rewriting "a" updates its committer date, but not its author date
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["describe", "-r", "description(a)", "-m", "a2"],
+    );
+    // A conflicted branch is sorted by the newest of its targets
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "c"]);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["branch", "create", "mmm", "-r", "description(b)"],
+    );
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "branch",
+            "create",
+            "mmm",
+            "-r",
+            "description(c)",
+            "--at-op=@-",
+        ],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["status"]);
+
+    let template = r#"name ++ "\n""#;
+    let list = |sort: &str| {
+        test_env.jj_cmd_success(
+            &repo_path,
+            &["branch", "list", "-T", template, &format!("--sort={sort}")],
+        )
+    };
+    insta::assert_snapshot!(list("name"), @r###"
+    aaa
+    mmm
+    zzz
+    "###);
+    insta::assert_snapshot!(list("committer-date"), @r###"
+    mmm
+    zzz
+    aaa
+    "###);
+    insta::assert_snapshot!(list("author-date"), @r###"
+    mmm
+    aaa
+    zzz
+    "###);
+}
+
 fn get_log_output(test_env: &TestEnvironment, cwd: &Path) -> String {
     let template = r#"branches ++ " " ++ commit_id.short()"#;
     test_env.jj_cmd_success(cwd, &["log", "-T", template])
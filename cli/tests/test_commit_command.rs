@@ -31,6 +31,39 @@ fn test_commit_with_description_from_cli() {
     "###);
 }
 
+#[test]
+fn test_commit_allow_empty_description() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+
+    // The editor isn't opened
+    let edit_script = test_env.set_up_fake_editor();
+    std::fs::write(edit_script, "fail").unwrap();
+    std::fs::write(workspace_path.join("file1"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&workspace_path, &["commit", "--allow-empty-description"]);
+    test_env.jj_cmd_ok(&workspace_path, &["describe", "-m=second"]);
+    test_env.jj_cmd_ok(&workspace_path, &["commit", "--allow-empty-description"]);
+    insta::assert_snapshot!(get_log_output(&test_env, &workspace_path), @r###"
+    @  40ceb525d254
+    ◉  de2b7d077ae3 second
+    ◉  d41b1cbaebd5
+    ◉  000000000000
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(
+        &workspace_path,
+        &["commit", "--allow-empty-description", "-m=third"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--allow-empty-description' cannot be used with '--message <MESSAGE>'
+
+    Usage: jj commit --allow-empty-description [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+}
+
 #[test]
 fn test_commit_with_editor() {
     let mut test_env = TestEnvironment::default();
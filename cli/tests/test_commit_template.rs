@@ -870,3 +870,91 @@ fn test_log_contained_in() {
     Hint: Did you mean "main"?
     "###);
 }
+
+#[test]
+fn test_log_diff_summary_and_stats() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "a\nb\n").unwrap();
+    std::fs::write(repo_path.join("file2"), "a\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m=first"]);
+    std::fs::write(repo_path.join("file1"), "a\nc\n").unwrap();
+    std::fs::remove_file(repo_path.join("file2")).unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m=second"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m=third", "root()"]);
+    std::fs::write(repo_path.join("file3"), "x\n").unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "new",
+            "-m=merge",
+            "description(second)",
+            "description(third)",
+        ],
+    );
+
+    let template = r#"description.first_line() ++ "\n" ++ self.diff_summary()"#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮  (diff against the first parent)
+    │ │  A file3
+    │ ◉  third
+    │ │  A file3
+    ◉ │  second
+    │ │  M file1
+    │ │  D file2
+    ◉ │  first
+    ├─╯  A file1
+    │    A file2
+    ◉
+    "###);
+
+    let template = r#"description.first_line() ++ "\n" ++ self.diff_stats(40)"#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮  (diff against the first parent)
+    │ │  file3 | 1 +
+    │ │  1 file changed, 1 insertion(+), 0 deletions(-)
+    │ ◉  third
+    │ │  file3 | 1 +
+    │ │  1 file changed, 1 insertion(+), 0 deletions(-)
+    ◉ │  second
+    │ │  file1 | 2 +-
+    │ │  file2 | 1 -
+    │ │  2 files changed, 1 insertion(+), 2 deletions(-)
+    ◉ │  first
+    ├─╯  file1 | 2 ++
+    │    file2 | 1 +
+    │    2 files changed, 3 insertions(+), 0 deletions(-)
+    ◉
+    "###);
+}
+
+#[test]
+fn test_log_generation() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m=a"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m=b"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m=c"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "description(b)", "@", "-m=merge"]);
+
+    let template = r#"description.first_line() ++ " " ++ generation ++ "\n""#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge 4
+    ├─╮
+    │ ◉  c 1
+    ◉ │  b 3
+    ◉ │  a 2
+    ◉ │   1
+    ├─╯
+    ◉   0
+    "###);
+}
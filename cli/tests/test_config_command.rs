@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use indoc::indoc;
 use insta::assert_snapshot;
 use itertools::Itertools;
 use regex::Regex;
@@ -417,6 +418,77 @@ fn test_config_layer_workspace() {
     insta::assert_snapshot!(stdout, @r###"
     ui.editor = "main-repo"
     "###);
+
+    // Workspace
+    std::fs::write(
+        secondary_path.join(".jj/config.toml"),
+        format!("{config_key} = {value:?}\n", value = "secondary-workspace"),
+    )
+    .unwrap();
+    let stdout = test_env.jj_cmd_success(&main_path, &["config", "list", config_key]);
+    insta::assert_snapshot!(stdout, @r###"
+    ui.editor = "main-repo"
+    "###);
+    let stdout = test_env.jj_cmd_success(&secondary_path, &["config", "list", config_key]);
+    insta::assert_snapshot!(stdout, @r###"
+    ui.editor = "secondary-workspace"
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &secondary_path,
+        &["config", "list", "--include-overridden", config_key],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    # ui.editor = "main-repo"
+    ui.editor = "secondary-workspace"
+    "###);
+}
+
+#[test]
+fn test_config_layer_workspace_log_revset() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "main"]);
+    let main_path = test_env.env_root().join("main");
+    let secondary_path = test_env.env_root().join("secondary");
+    test_env.jj_cmd_ok(&main_path, &["describe", "-m", "main"]);
+    test_env.jj_cmd_ok(
+        &main_path,
+        &["workspace", "add", "--name", "second", "../secondary"],
+    );
+    test_env.jj_cmd_ok(&secondary_path, &["describe", "-m", "second"]);
+
+    // Workspace config takes precedence over repo and user configs
+    test_env.add_config(r#"revsets.log = "none()""#);
+    test_env.jj_cmd_ok(
+        &main_path,
+        &[
+            "config",
+            "set",
+            "--repo",
+            "revsets.log",
+            "description(main)",
+        ],
+    );
+    test_env.jj_cmd_ok(
+        &secondary_path,
+        &["config", "set", "--workspace", "revsets.log", "all()"],
+    );
+    let stdout = test_env.jj_cmd_success(&main_path, &["log", "-T", "description"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  main
+    │
+    ~
+    "###);
+    let stdout = test_env.jj_cmd_success(&secondary_path, &["log", "-T", "description"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  second
+    │ ◉  main
+    ├─╯
+    ◉
+    "###);
+    let stdout = test_env.jj_cmd_success(&secondary_path, &["config", "path", "--workspace"]);
+    insta::assert_snapshot!(stdout.replace('\\', "/"), @r###"
+    $TEST_ENV/secondary/.jj/config.toml
+    "###);
 }
 
 #[test]
@@ -425,11 +497,11 @@ fn test_config_set_bad_opts() {
     let stderr = test_env.jj_cmd_cli_error(test_env.env_root(), &["config", "set"]);
     insta::assert_snapshot!(stderr, @r###"
     error: the following required arguments were not provided:
-      <--user|--repo>
+      <--user|--repo|--workspace>
       <NAME>
       <VALUE>
 
-    Usage: jj config set <--user|--repo> <NAME> <VALUE>
+    Usage: jj config set <--user|--repo|--workspace> <NAME> <VALUE>
 
     For more information, try '--help'.
     "###);
@@ -512,6 +584,75 @@ fn test_config_set_for_repo() {
     "###);
 }
 
+#[test]
+fn test_config_migrate() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let repo_config_path = repo_path.join(".jj/repo/config.toml");
+    std::fs::write(
+        &repo_config_path,
+        indoc! {r#"
+            # The diff format
+            diff.format = "git"
+
+            [ui]
+            # The default revset
+            default-revset = "@"
+            color = "never"
+
+            [alias]
+            l = ["log"]
+
+            [revsets]
+            # Already in the new form
+            log = "all()"
+        "#},
+    )
+    .unwrap();
+
+    // Dry run doesn't modify the file
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["config", "migrate", "--dry-run"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Would rename alias to aliases in $TEST_ENV/repo/.jj/repo/config.toml
+    Would rename diff.format to ui.diff.format in $TEST_ENV/repo/.jj/repo/config.toml
+    Warning: Not renaming ui.default-revset in $TEST_ENV/repo/.jj/repo/config.toml because revsets.log is already set
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["config", "migrate", "--repo"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Renamed alias to aliases in $TEST_ENV/repo/.jj/repo/config.toml
+    Renamed diff.format to ui.diff.format in $TEST_ENV/repo/.jj/repo/config.toml
+    Warning: Not renaming ui.default-revset in $TEST_ENV/repo/.jj/repo/config.toml because revsets.log is already set
+    "###);
+    insta::assert_snapshot!(std::fs::read_to_string(&repo_config_path).unwrap(), @r###"
+    [ui]
+    # The default revset
+    default-revset = "@"
+    color = "never"
+
+    [ui.diff]
+    # The diff format
+    format = "git"
+
+    [aliases]
+    l = ["log"]
+
+    [revsets]
+    # Already in the new form
+    log = "all()"
+    "###);
+
+    // Nothing left to rename
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["config", "migrate", "--repo"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Not renaming ui.default-revset in $TEST_ENV/repo/.jj/repo/config.toml because revsets.log is already set
+    No deprecated config options to rename.
+    "###);
+}
+
 #[test]
 fn test_config_set_toml_types() {
     let mut test_env = TestEnvironment::default();
@@ -606,9 +747,9 @@ fn test_config_edit_missing_opt() {
     let stderr = test_env.jj_cmd_cli_error(test_env.env_root(), &["config", "edit"]);
     insta::assert_snapshot!(stderr, @r###"
     error: the following required arguments were not provided:
-      <--user|--repo>
+      <--user|--repo|--workspace>
 
-    Usage: jj config edit <--user|--repo>
+    Usage: jj config edit <--user|--repo|--workspace>
 
     For more information, try '--help'.
     "###);
@@ -647,6 +788,31 @@ fn test_config_edit_repo() {
     test_env.jj_cmd_ok(&repo_path, &["config", "edit", "--repo"]);
 }
 
+#[test]
+fn test_config_edit_workspace() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "main"]);
+    let main_path = test_env.env_root().join("main");
+    let secondary_path = test_env.env_root().join("secondary");
+    test_env.jj_cmd_ok(
+        &main_path,
+        &["workspace", "add", "--name", "second", "../secondary"],
+    );
+    let edit_script = test_env.set_up_fake_editor();
+
+    // The file is created before the editor starts
+    let config_path = secondary_path.join(".jj/config.toml");
+    assert!(!config_path.exists());
+    std::fs::write(
+        edit_script,
+        format!("expectpath\n{}", config_path.to_str().unwrap()),
+    )
+    .unwrap();
+    test_env.jj_cmd_ok(&secondary_path, &["config", "edit", "--workspace"]);
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), "");
+    assert!(!main_path.join(".jj/config.toml").exists());
+}
+
 #[test]
 fn test_config_path() {
     let test_env = TestEnvironment::default();
@@ -667,6 +833,40 @@ fn test_config_path() {
     );
 }
 
+#[test]
+fn test_config_path_value_of() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["config", "path", "--repo", "--value-of", "ui.editor"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    $TEST_ENV/repo/.jj/repo/config.toml
+    "###);
+
+    // The user config directory can't be written by `jj config set`
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["config", "path", "--user", "--value-of", "ui.editor"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Can't set config in path $TEST_ENV/config (dirs not supported)
+    "###);
+
+    let user_config_path = test_env.config_path().join("config.toml");
+    test_env.set_config_path(user_config_path);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["config", "path", "--user", "--value-of", "ui.editor"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    $TEST_ENV/config/config.toml
+    "###);
+}
+
 #[test]
 fn test_config_edit_repo_outside_repo() {
     let test_env = TestEnvironment::default();
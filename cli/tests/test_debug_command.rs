@@ -47,6 +47,87 @@ fn test_debug_fileset() {
     "###);
 }
 
+#[test]
+fn test_debug_fileset_matched_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+    std::fs::create_dir_all(workspace_path.join("dir/sub")).unwrap();
+    std::fs::create_dir_all(workspace_path.join("other")).unwrap();
+    std::fs::write(workspace_path.join(".gitignore"), "*.log\n").unwrap();
+    std::fs::write(workspace_path.join("dir/.gitignore"), "sub/\n").unwrap();
+    std::fs::write(workspace_path.join("dir/a.txt"), "").unwrap();
+    std::fs::write(workspace_path.join("dir/b.log"), "").unwrap();
+    std::fs::write(workspace_path.join("dir/c.rs"), "").unwrap();
+    std::fs::write(workspace_path.join("dir/sub/d.txt"), "").unwrap();
+    std::fs::write(workspace_path.join("other/e.txt"), "").unwrap();
+
+    let get_matched_paths = |args: &[&str]| {
+        let stdout =
+            test_env.jj_cmd_success(&workspace_path, &[&["debug", "fileset"], args].concat());
+        let (_, paths) = stdout.split_once("-- Matched paths:\n").unwrap();
+        paths.to_owned()
+    };
+    assert_snapshot!(
+        get_matched_paths(&["~glob:'**/*.rs'", "--matched-paths", "dir"]), @r###"
+    dir/.gitignore
+    dir/a.txt
+    "###);
+    assert_snapshot!(
+        get_matched_paths(&["~glob:'**/*.rs'", "--matched-paths", "dir", "--include-ignored"]),
+        @r###"
+    dir/.gitignore
+    dir/a.txt
+    dir/b.log
+    dir/sub/d.txt
+    "###);
+    assert_snapshot!(get_matched_paths(&["all()", "--matched-paths", "."]), @r###"
+    .gitignore
+    dir/.gitignore
+    dir/a.txt
+    dir/c.rs
+    other/e.txt
+    "###);
+}
+
+#[test]
+fn test_debug_gitignore() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+    std::fs::create_dir_all(workspace_path.join("dir/build")).unwrap();
+    std::fs::write(workspace_path.join(".gitignore"), "*.log\n").unwrap();
+    std::fs::write(workspace_path.join("dir/.gitignore"), "!keep.log\nbuild\n").unwrap();
+
+    let debug_gitignore =
+        |path: &str| test_env.jj_cmd_success(&workspace_path, &["debug", "gitignore", path]);
+    assert_snapshot!(debug_gitignore("foo.log"), @r###"
+    foo.log: ignored by .gitignore: *.log
+    "###);
+    assert_snapshot!(debug_gitignore("dir/keep.log"), @r###"
+    dir/keep.log: not ignored by dir/.gitignore: !keep.log
+    "###);
+    assert_snapshot!(debug_gitignore("dir/build"), @r###"
+    dir/build: ignored by dir/.gitignore: build
+    "###);
+    assert_snapshot!(debug_gitignore("dir/build/foo"), @r###"
+    dir/build/foo: ignored by dir/.gitignore: build
+    "###);
+    assert_snapshot!(debug_gitignore("dir/foo"), @r###"
+    dir/foo: not ignored (no matching rule)
+    "###);
+
+    // Patterns from .git/info/exclude are included
+    std::fs::write(
+        workspace_path.join(".jj/repo/store/git/info/exclude"),
+        "foo\n",
+    )
+    .unwrap();
+    assert_snapshot!(debug_gitignore("dir/foo"), @r###"
+    dir/foo: ignored by .jj/repo/store/git/info/exclude: foo
+    "###);
+}
+
 #[test]
 fn test_debug_revset() {
     let test_env = TestEnvironment::default();
@@ -82,6 +163,120 @@ fn test_debug_revset() {
         0000000000000000000000000000000000000000
         "###);
     });
+
+    let stdout = test_env.jj_cmd_success(
+        &workspace_path,
+        &["debug", "revset", "::@", "--benchmark", "--iterations=3"],
+    );
+    insta::with_settings!({filters => vec![
+        (r"(?m)(^    .*\n)+", "    ..\n"),
+        (r"\d+\.\d+ms", "[TIME]"),
+    ]}, {
+        assert_snapshot!(stdout, @r###"
+        -- Parsed:
+        Ancestors {
+            ..
+        }
+
+        -- Optimized:
+        Ancestors {
+            ..
+        }
+
+        -- Resolved:
+        Ancestors {
+            ..
+        }
+
+        -- Evaluated:
+        RevsetImpl {
+            ..
+        }
+
+        -- Benchmark:
+        Iterations: 3
+        Commits: 2
+        Min: [TIME]
+        Median: [TIME]
+        Max: [TIME]
+        "###);
+    });
+
+    let stderr = test_env.jj_cmd_cli_error(
+        &workspace_path,
+        &["debug", "revset", "root()", "--benchmark", "--iterations=0"],
+    );
+    assert_snapshot!(stderr, @r###"
+    error: invalid value '0' for '--iterations <ITERATIONS>': 0 is not in 1..=4294967295
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_debug_template_type_check() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_path,
+        &["debug", "template", "--type-check", r#"description ++ "x""#],
+    );
+    assert_snapshot!(stdout, @"");
+    assert_snapshot!(stderr, @r###"
+    Template type-checks OK
+    "###);
+
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_path,
+        &[
+            "debug",
+            "template",
+            "--type-check",
+            "descripton ++ author ++ commit_id.foo()",
+        ],
+    );
+    assert_snapshot!(stderr, @r###"
+    Error: Failed to parse template: Keyword "descripton" doesn't exist
+    Caused by:  --> 1:1
+      |
+    1 | descripton ++ author ++ commit_id.foo()
+      | ^--------^
+      |
+      = Keyword "descripton" doesn't exist
+    Hint: Did you mean "description", "description_placeholder"?
+    Error: Failed to parse template: Method "foo" doesn't exist for type "CommitOrChangeId"
+    Caused by:  --> 1:35
+      |
+    1 | descripton ++ author ++ commit_id.foo()
+      |                                   ^-^
+      |
+      = Method "foo" doesn't exist for type "CommitOrChangeId"
+    Error: Template has 2 type error(s)
+    "###);
+
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_path,
+        &[
+            "debug",
+            "template",
+            "--type-check",
+            "--max-errors=1",
+            "descripton ++ commit_id.foo()",
+        ],
+    );
+    assert_snapshot!(stderr, @r###"
+    Error: Failed to parse template: Keyword "descripton" doesn't exist
+    Caused by:  --> 1:1
+      |
+    1 | descripton ++ commit_id.foo()
+      | ^--------^
+      |
+      = Keyword "descripton" doesn't exist
+    Hint: Did you mean "description", "description_placeholder"?
+    Error: Template has 2 type error(s)
+    "###);
 }
 
 #[test]
@@ -147,6 +342,52 @@ fn test_debug_reindex() {
     );
 }
 
+#[test]
+fn test_debug_copy_detection() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+
+    std::fs::write(workspace_path.join("moved"), "1\n2\n3\n").unwrap();
+    std::fs::write(workspace_path.join("edited"), "a\nb\nc\nd\n").unwrap();
+    std::fs::write(workspace_path.join("rewritten"), "x\ny\nz\nw\n").unwrap();
+    test_env.jj_cmd_ok(&workspace_path, &["new"]);
+    std::fs::remove_file(workspace_path.join("moved")).unwrap();
+    std::fs::write(workspace_path.join("moved2"), "1\n2\n3\n").unwrap();
+    std::fs::remove_file(workspace_path.join("edited")).unwrap();
+    std::fs::write(workspace_path.join("edited2"), "a\nb\nc\nD\n").unwrap();
+    std::fs::remove_file(workspace_path.join("rewritten")).unwrap();
+    std::fs::write(workspace_path.join("rewritten2"), "x\nY\nZ\nW\n").unwrap();
+
+    let stdout = test_env.jj_cmd_success(&workspace_path, &["debug", "copy-detection"]);
+    assert_snapshot!(stdout, @r###"
+    edited -> edited2
+    moved -> moved2
+    "###);
+    let stdout = test_env.jj_cmd_success(&workspace_path, &["debug", "copy-detection", "--scores"]);
+    assert_snapshot!(stdout, @r###"
+    R  75% edited -> edited2
+    R 100% moved -> moved2
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &workspace_path,
+        &["debug", "copy-detection", "--scores", "--threshold=30"],
+    );
+    assert_snapshot!(stdout, @r###"
+    R  75% edited -> edited2
+    R 100% moved -> moved2
+    -  25% rewritten -> rewritten2 (below threshold)
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &workspace_path,
+        &["debug", "copy-detection", "--scores", "--threshold=80"],
+    );
+    assert_snapshot!(stdout, @r###"
+    -  75% edited -> edited2 (below threshold)
+    R 100% moved -> moved2
+    "###);
+}
+
 #[test]
 fn test_debug_tree() {
     let test_env = TestEnvironment::default();
@@ -228,6 +469,45 @@ fn test_debug_tree() {
     );
 }
 
+#[test]
+fn test_debug_snapshot_stats() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+
+    std::fs::write(workspace_path.join("file1"), "a\n").unwrap();
+    std::fs::write(workspace_path.join("file2"), "b\n").unwrap();
+    let stdout = test_env.jj_cmd_success(&workspace_path, &["debug", "snapshot", "--stats"]);
+    assert_snapshot!(stdout, @r###"
+    Files written: 2 unseen, 0 already seen
+    "###);
+
+    // A copy of a known file is deduplicated.
+    std::fs::write(workspace_path.join("file3"), "b\n").unwrap();
+    std::fs::write(workspace_path.join("file4"), "c\n").unwrap();
+    let stdout = test_env.jj_cmd_success(&workspace_path, &["debug", "snapshot", "--stats"]);
+    assert_snapshot!(stdout, @r###"
+    Files written: 1 unseen, 1 already seen
+    "###);
+}
+
+#[test]
+fn test_debug_snapshot_compare_watchman() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+
+    // Watchman must be configured
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_path,
+        &["debug", "snapshot", "--compare-watchman"],
+    );
+    assert_snapshot!(stderr, @r###"
+    Error: Watchman is not configured
+    Hint: Set `core.fsmonitor = "watchman"` to enable it.
+    "###);
+}
+
 #[test]
 fn test_debug_operation_id() {
     let test_env = TestEnvironment::default();
@@ -241,6 +521,22 @@ fn test_debug_operation_id() {
     );
 }
 
+#[test]
+fn test_debug_local_working_copy_watchman_status() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+    let stdout = test_env.jj_cmd_success(
+        &workspace_path,
+        &["debug", "local-working-copy", "--watchman-status"],
+    );
+    assert_snapshot!(stdout, @r###"
+    Current operation: OperationId("b51416386f2685fd5493f2b20e8eec3c24a1776d9e1a7cb5ed7e30d2d9c88c0c1e1fe71b0b7358cba60de42533d1228ed9878f2f89817d892c803395ccf9fe92")
+    Current tree: Merge(Resolved(TreeId("4b825dc642cb6eb9a060e54bf8d69288fbee4904")))
+    Watchman: disabled
+    "###);
+}
+
 fn filter_index_stats(text: &str) -> String {
     let regex = Regex::new(r"    Name: [0-9a-z]+").unwrap();
     regex.replace_all(text, "    Name: [hash]").to_string()
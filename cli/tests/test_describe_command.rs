@@ -247,6 +247,115 @@ fn test_multiple_message_args() {
     "###);
 }
 
+#[test]
+fn test_describe_batch() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let edit_script = test_env.set_up_fake_editor();
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m1"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m2"]);
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m3"]);
+    let get_commit_hash = |revision: &str| {
+        test_env.jj_cmd_success(
+            &repo_path,
+            &[
+                "log",
+                "--no-graph",
+                "-T",
+                "commit_id.short()",
+                "-r",
+                revision,
+            ],
+        )
+    };
+    let hash1 = get_commit_hash("description(1)");
+    let hash2 = get_commit_hash("description(2)");
+
+    // The descriptions are edited in a single editor, parents first
+    std::fs::write(
+        &edit_script,
+        [
+            "dump editor0",
+            &format!(
+                "write\nJJ: describe {hash1} -------\nfirst\n\nJJ: describe {hash2}\nsecond\n"
+            ),
+        ]
+        .join("\0"),
+    )
+    .unwrap();
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["describe", "--batch", "-r", "::@- ~ root()"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Updated 2 commits
+    Rebased 1 descendant commits
+    Working copy now at: zsuskuln 901ef7d4 (empty) 3
+    Parent commit      : kkmpptxz 0d8f23b8 second
+    "###);
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor0")).unwrap(), @r###"
+    JJ: Enter or edit commit descriptions after the `JJ: describe` lines.
+    JJ: If a section is deleted, the description of the commit is left unchanged.
+
+    JJ: describe 8b64ddff700d -------
+    1
+
+    JJ: describe df30174a800d -------
+    2
+
+    JJ: This commit contains the following changes:
+    JJ:     A file1
+
+    JJ: Lines starting with "JJ: " (like this one) will be removed.
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", "description"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  3
+    ◉  second
+    ◉  first
+    ◉
+    "###);
+
+    // The description of a deleted section is left unchanged
+    let hash2 = get_commit_hash("description(second)");
+    std::fs::write(
+        &edit_script,
+        format!("write\nJJ: describe {hash2} -------\nsecond again\n"),
+    )
+    .unwrap();
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["describe", "--batch", "-r", "::@- ~ root()"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: The descriptions of these commits were not found in the edited text and were left unchanged: d645def979e5
+    Updated 1 commits
+    Rebased 1 descendant commits
+    Working copy now at: zsuskuln ab47350a (empty) 3
+    Parent commit      : kkmpptxz f168daa7 second again
+    "###);
+
+    // Unknown commits are rejected
+    std::fs::write(
+        &edit_script,
+        "write\nJJ: describe 000000000000 -------\nfoo\n",
+    )
+    .unwrap();
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["describe", "--batch", "-r", "@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Found unexpected commit 000000000000
+    "###);
+
+    // Text before the first header is rejected
+    std::fs::write(&edit_script, "write\nfoo\n").unwrap();
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["describe", "--batch", "-r", "@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Found the following line without a commit header: "foo"
+    "###);
+}
+
 #[test]
 fn test_describe_default_description() {
     let mut test_env = TestEnvironment::default();
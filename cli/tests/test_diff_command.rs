@@ -365,6 +365,116 @@ fn test_diff_name_only() {
     "###);
 }
 
+#[test]
+fn test_diff_copies() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("renamed"), "a\nb\nc\nd\n").unwrap();
+    std::fs::write(repo_path.join("moved"), "1\n2\n3\n").unwrap();
+    std::fs::write(repo_path.join("source"), "x\ny\nz\n").unwrap();
+    std::fs::write(repo_path.join("unrelated"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::remove_file(repo_path.join("renamed")).unwrap();
+    std::fs::write(repo_path.join("renamed2"), "a\nb\nc\nD\n").unwrap();
+    std::fs::remove_file(repo_path.join("moved")).unwrap();
+    std::fs::write(repo_path.join("moved2"), "1\n2\n3\n").unwrap();
+    std::fs::write(repo_path.join("copied"), "x\ny\nz\n").unwrap();
+    std::fs::remove_file(repo_path.join("unrelated")).unwrap();
+    std::fs::write(repo_path.join("added"), "bar\n").unwrap();
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--summary"]);
+    insta::assert_snapshot!(stdout, @r###"
+    A added
+    A copied
+    D moved
+    A moved2
+    D renamed
+    A renamed2
+    D unrelated
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--summary", "--copies"]);
+    insta::assert_snapshot!(stdout, @r###"
+    A added
+    A copied
+    R moved => moved2
+    R renamed => renamed2
+    D unrelated
+    "###);
+    let stdout =
+        test_env.jj_cmd_success(&repo_path, &["diff", "--summary", "--find-copies-harder"]);
+    insta::assert_snapshot!(stdout, @r###"
+    A added
+    C source => copied
+    R moved => moved2
+    R renamed => renamed2
+    D unrelated
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git", "--copies"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/added b/added
+    new file mode 100644
+    index 0000000000..5716ca5987
+    --- /dev/null
+    +++ b/added
+    @@ -1,0 +1,1 @@
+    +bar
+    diff --git a/copied b/copied
+    new file mode 100644
+    index 0000000000..04ec35a6dc
+    --- /dev/null
+    +++ b/copied
+    @@ -1,0 +1,3 @@
+    +x
+    +y
+    +z
+    diff --git a/moved b/moved2
+    similarity index 100%
+    rename from moved
+    rename to moved2
+    diff --git a/renamed b/renamed2
+    similarity index 75%
+    rename from renamed
+    rename to renamed2
+    index d68dd4031d...5790697ef6 100644
+    --- a/renamed
+    +++ b/renamed2
+    @@ -1,4 +1,4 @@
+     a
+     b
+     c
+    -d
+    +D
+    diff --git a/unrelated b/unrelated
+    deleted file mode 100644
+    index 257cc5642c..0000000000
+    --- a/unrelated
+    +++ /dev/null
+    @@ -1,1 +1,0 @@
+    -foo
+    "###);
+
+    // Only identical files are compared if there are too many candidates
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "diff",
+            "--summary",
+            "--copies",
+            "--config-toml=ui.diff.copy-candidate-limit=1",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    A added
+    A copied
+    R moved => moved2
+    D renamed
+    A renamed2
+    D unrelated
+    "###);
+}
+
 #[test]
 fn test_diff_bad_args() {
     let test_env = TestEnvironment::default();
@@ -857,6 +967,240 @@ fn test_diff_skipped_context_nondefault() {
     "###);
 }
 
+#[test]
+fn test_diff_ignore_matching_lines() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(
+        repo_path.join("file1"),
+        "# a\nfoo\n1\n2\n3\n4\n5\n6\n7\nbar\n# b\n",
+    )
+    .unwrap();
+    std::fs::write(repo_path.join("file2"), "# a\nfoo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    // The first hunk only changes comments, but the second hunk doesn't
+    std::fs::write(
+        repo_path.join("file1"),
+        "# A\nfoo\n1\n2\n3\n4\n5\n6\n7\nBAR\n# B\n",
+    )
+    .unwrap();
+    // All changes are comments
+    std::fs::write(repo_path.join("file2"), "# A\nfoo\n").unwrap();
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file1 b/file1
+    index e6c5ba7f00...955734b575 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,4 +1,4 @@
+    -# a
+    +# A
+     foo
+     1
+     2
+    @@ -7,5 +7,5 @@
+     5
+     6
+     7
+    -bar
+    -# b
+    +BAR
+    +# B
+    diff --git a/file2 b/file2
+    index e1ee9bc731...5d453b8e14 100644
+    --- a/file2
+    +++ b/file2
+    @@ -1,2 +1,2 @@
+    -# a
+    +# A
+     foo
+    "###);
+    let stdout =
+        test_env.jj_cmd_success(&repo_path, &["diff", "--git", "--ignore-matching-lines=^#"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file1 b/file1
+    index e6c5ba7f00...955734b575 100644
+    --- a/file1
+    +++ b/file1
+    @@ -7,5 +7,5 @@
+     5
+     6
+     7
+    -bar
+    -# b
+    +BAR
+    +# B
+    "###);
+
+    // Other formats hide files whose changes are all ignored
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--summary", "--ignore-matching-lines=^#"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    M file1
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--stat", "--ignore-matching-lines=^#"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    file1 | 4 ++--
+    1 file changed, 2 insertions(+), 2 deletions(-)
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--color-words", "--ignore-matching-lines=^#"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    Modified regular file file1:
+       1    1: # aA
+       2    2: foo
+       3    3: 1
+       4    4: 2
+        ...
+       7    7: 5
+       8    8: 6
+       9    9: 7
+      10   10: barBAR
+      11   11: # bB
+    "###);
+
+    // Added and removed files whose lines all match are hidden
+    std::fs::write(repo_path.join("file3"), "# added\n").unwrap();
+    std::fs::remove_file(repo_path.join("file2")).unwrap();
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--summary", "--ignore-matching-lines=^#|^foo"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    M file1
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--git", "-r@-", "--ignore-matching-lines=^#|^foo"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file1 b/file1
+    new file mode 100644
+    index 0000000000..e6c5ba7f00
+    --- /dev/null
+    +++ b/file1
+    @@ -1,0 +1,11 @@
+    +# a
+    +foo
+    +1
+    +2
+    +3
+    +4
+    +5
+    +6
+    +7
+    +bar
+    +# b
+    "###);
+
+    // External diff tools can't ignore lines
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &[
+            "diff",
+            "--tool=fake-diff-editor",
+            "--ignore-matching-lines=^#",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: --ignore-matching-lines can't be used with external diff tools
+    Hint: Use a builtin format such as `--git`.
+    "###);
+
+    // Invalid regex
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["diff", "--ignore-matching-lines=("]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value '(' for '--ignore-matching-lines <REGEX>': regex parse error:
+        (
+        ^
+    error: unclosed group
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_diff_color_moved() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(
+        repo_path.join("file1"),
+        "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n",
+    )
+    .unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    // "a".."d" are moved to the end, "e" is removed, and "g" is modified in
+    // place
+    std::fs::write(repo_path.join("file1"), "f\nG\nh\ni\nj\nk\nl\na\nb\nc\nd\n").unwrap();
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--git", "--color-moved", "--color=always"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    [1mdiff --git a/file1 b/file1[0m
+    [1mindex 2f0b8ee0f0...87d2945ce3 100644[0m
+    [1m--- a/file1[0m
+    [1m+++ b/file1[0m
+    [38;5;6m@@ -1,12 +1,11 @@[39m
+    [38;5;5m-a[39m
+    [38;5;5m-b[39m
+    [38;5;5m-c[39m
+    [38;5;5m-d[39m
+    [38;5;1m-e[39m
+     f
+    [38;5;1m-g[39m
+    [38;5;2m+G[39m
+     h
+     i
+     j
+     k
+     l
+    [38;5;6m+a[39m
+    [38;5;6m+b[39m
+    [38;5;6m+c[39m
+    [38;5;6m+d[39m
+    "###);
+    // Not highlighted by default
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git", "--color=always"]);
+    insta::assert_snapshot!(stdout, @r###"
+    [1mdiff --git a/file1 b/file1[0m
+    [1mindex 2f0b8ee0f0...87d2945ce3 100644[0m
+    [1m--- a/file1[0m
+    [1m+++ b/file1[0m
+    [38;5;6m@@ -1,12 +1,11 @@[39m
+    [38;5;1m-a[39m
+    [38;5;1m-b[39m
+    [38;5;1m-c[39m
+    [38;5;1m-d[39m
+    [38;5;1m-e[39m
+     f
+    [38;5;1m-g[39m
+    [38;5;2m+G[39m
+     h
+     i
+     j
+     k
+     l
+    [38;5;2m+a[39m
+    [38;5;2m+b[39m
+    [38;5;2m+c[39m
+    [38;5;2m+d[39m
+    "###);
+}
+
 #[test]
 fn test_diff_external_tool() {
     let mut test_env = TestEnvironment::default();
@@ -983,6 +1327,126 @@ fn test_diff_external_tool() {
     "###);
 }
 
+#[test]
+fn test_diff_external_tool_candidates() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("file1"), "bar\n").unwrap();
+
+    let edit_script = test_env.set_up_fake_diff_editor();
+    std::fs::write(
+        &edit_script,
+        "print-files-before\0print --\0print-files-after",
+    )
+    .unwrap();
+
+    // The first installed tool is picked
+    let config = r#"--config-toml=ui.diff-tool-candidates=["nonexistent", "fake-diff-editor"]"#;
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["diff", config]), @r###"
+    file1
+    --
+    file1
+    "###);
+
+    // Falls back to the internal format
+    let config =
+        r#"--config-toml=ui.diff-tool-candidates=["nonexistent", ":builtin", "fake-diff-editor"]"#;
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["diff", config]), @r###"
+    Modified regular file file1:
+       1    1: foobar
+    "###);
+    let config = r#"--config-toml=ui.diff-tool-candidates=["nonexistent"]"#;
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["diff", config]), @r###"
+    Modified regular file file1:
+       1    1: foobar
+    "###);
+
+    // Explicit --tool overrides the candidates
+    let config = r#"--config-toml=ui.diff-tool-candidates=["fake-diff-editor"]"#;
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["diff", config, "--tool=nonexistent"]);
+    insta::assert_snapshot!(strip_last_line(&stderr), @r###"
+    Error: Failed to generate diff
+    Caused by:
+    1: Error executing 'nonexistent' (run with --debug to see the exact invocation)
+    "###);
+}
+
+#[test]
+fn test_diff_external_tool_by_path() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    std::fs::write(repo_path.join("image.png"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("file1"), "bar\n").unwrap();
+    std::fs::write(repo_path.join("image.png"), "bar\n").unwrap();
+    std::fs::write(repo_path.join("file2.png"), "bar\n").unwrap();
+
+    let edit_script = test_env.set_up_fake_diff_editor();
+    std::fs::write(
+        &edit_script,
+        "print-files-before\0print --\0print-files-after",
+    )
+    .unwrap();
+    let command = escaped_fake_diff_editor_path();
+    test_env.add_config(&format!(
+        r#"
+        merge-tools.reversed.program = "{command}"
+        merge-tools.reversed.diff-args = ["$right", "$left"]
+        ui.diff-tool-by-path = [
+          {{ files = "image.png", tool = "reversed" }},
+          {{ files = 'glob:"*.png"', tool = "fake-diff-editor" }},
+        ]
+        "#
+    ));
+
+    // Each tool is invoked for the matching files, and the other files are
+    // rendered in the default format
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff"]);
+    insta::assert_snapshot!(stdout, @r###"
+    image.png
+    --
+    image.png
+    --
+    file2.png
+    Modified regular file file1:
+       1    1: foobar
+    "###);
+
+    // Tools aren't invoked if no files are routed to them
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "file1"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Modified regular file file1:
+       1    1: foobar
+    "###);
+
+    // Short formats aren't affected
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--summary"]);
+    insta::assert_snapshot!(stdout, @r###"
+    M file1
+    A file2.png
+    M image.png
+    "###);
+
+    // An explicitly specified format overrides the rules
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git", "image.png"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/image.png b/image.png
+    index 257cc5642c...5716ca5987 100644
+    --- a/image.png
+    +++ b/image.png
+    @@ -1,1 +1,1 @@
+    -foo
+    +bar
+    "###);
+}
+
 #[cfg(unix)]
 #[test]
 fn test_diff_external_tool_symlink() {
@@ -1198,10 +1662,138 @@ fn test_diff_binary() {
 
     let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--stat"]);
     insta::assert_snapshot!(stdout, @r###"
-    file1.png | 3 ---
-    file2.png | 5 ++---
-    file3.png | 3 +++
-    file4.png | 1 +
-    4 files changed, 6 insertions(+), 6 deletions(-)
+    file1.png | Bin 16 -> 0 bytes
+    file2.png | Bin 16 -> 8 bytes
+    file3.png | Bin 0 -> 12 bytes
+    file4.png | Bin 0 -> 3 bytes
+    4 files changed, 0 insertions(+), 0 deletions(-)
+    "###);
+}
+
+#[test]
+fn test_diff_secret_paths() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(r#"secrets.paths = ['glob:"*.key"', "dir/secret"]"#);
+
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("deleted.key"), "foo\n").unwrap();
+    std::fs::write(repo_path.join("modified.key"), "foo\n").unwrap();
+    std::fs::write(repo_path.join("file"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::remove_file(repo_path.join("deleted.key")).unwrap();
+    std::fs::write(repo_path.join("modified.key"), "bar\n").unwrap();
+    std::fs::write(repo_path.join("dir").join("secret"), "bar\n").unwrap();
+    std::fs::write(repo_path.join("file"), "bar\n").unwrap();
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Removed regular file deleted.key:
+        [redacted]
+    Added regular file dir/secret:
+        [redacted]
+    Modified regular file file:
+       1    1: foobar
+    Modified regular file modified.key:
+        [redacted]
+    "###);
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/deleted.key b/deleted.key
+    deleted file mode 100644
+    index 257cc5642c..0000000000
+    --- a/deleted.key
+    +++ /dev/null
+    [redacted]
+    diff --git a/dir/secret b/dir/secret
+    new file mode 100644
+    index 0000000000..5716ca5987
+    --- /dev/null
+    +++ b/dir/secret
+    [redacted]
+    diff --git a/file b/file
+    index 257cc5642c...5716ca5987 100644
+    --- a/file
+    +++ b/file
+    @@ -1,1 +1,1 @@
+    -foo
+    +bar
+    diff --git a/modified.key b/modified.key
+    index 257cc5642c...5716ca5987 100644
+    --- a/modified.key
+    +++ b/modified.key
+    [redacted]
+    "###);
+
+    // Paths are relative to the workspace root
+    let stdout = test_env.jj_cmd_success(&repo_path.join("dir"), &["diff", "--git", "secret"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/dir/secret b/dir/secret
+    new file mode 100644
+    index 0000000000..5716ca5987
+    --- /dev/null
+    +++ b/dir/secret
+    [redacted]
+    "###);
+
+    // External tools aren't given the secret files, which are redacted instead
+    let edit_script = test_env.set_up_fake_diff_editor();
+    std::fs::write(
+        &edit_script,
+        "print-files-before\0print --\0print-files-after",
+    )
+    .unwrap();
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--tool=fake-diff-editor"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file
+    --
+    file
+    Removed regular file deleted.key:
+        [redacted]
+    Added regular file dir/secret:
+        [redacted]
+    Modified regular file modified.key:
+        [redacted]
+    "###);
+
+    // Secret files routed to tools by path are redacted in the default format
+    let tools_by_path = r#"--config-toml=
+        [[ui.diff-tool-by-path]]
+        files = 'glob:"*.key"'
+        tool = "fake-diff-editor"
+        [[ui.diff-tool-by-path]]
+        files = "file"
+        tool = "fake-diff-editor"
+    "#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", tools_by_path]);
+    insta::assert_snapshot!(stdout, @r###"
+    file
+    --
+    file
+    Removed regular file deleted.key:
+        [redacted]
+    Added regular file dir/secret:
+        [redacted]
+    Modified regular file modified.key:
+        [redacted]
+    "###);
+
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["diff", "--config-toml=secrets.paths = ['bad:x']"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Config error: Invalid `secrets.paths`
+    Caused by:
+    1:  --> 1:1
+      |
+    1 | bad:x
+      | ^---^
+      |
+      = Invalid file pattern
+    2: Invalid file pattern kind "bad:"
+    For help, see https://github.com/martinvonz/jj/blob/main/docs/config.md.
     "###);
 }
@@ -0,0 +1,65 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::{get_stderr_string, TestEnvironment};
+
+#[test]
+fn test_dispatch_hook_success() {
+    let test_env = TestEnvironment::default();
+    let assert = test_env
+        .jj_cmd_with_bin(
+            "fake-dispatch-hook",
+            test_env.env_root(),
+            &["git", "init", "repo"],
+        )
+        .assert()
+        .success();
+    insta::assert_snapshot!(get_stderr_string(&assert), @r###"
+    Initialized repo in "repo"
+    dispatch-hook: git init ok elapsed
+    "###);
+}
+
+#[test]
+fn test_dispatch_hook_error() {
+    let test_env = TestEnvironment::default();
+    let assert = test_env
+        .jj_cmd_with_bin(
+            "fake-dispatch-hook",
+            test_env.env_root(),
+            &["config", "get", "no-such.key"],
+        )
+        .assert()
+        .code(1);
+    insta::assert_snapshot!(get_stderr_string(&assert), @r###"
+    dispatch-hook: config get error elapsed
+    Config error: configuration property "no-such.key" not found
+    For help, see https://github.com/martinvonz/jj/blob/main/docs/config.md.
+    "###);
+}
+
+#[test]
+fn test_dispatch_hook_not_called_on_parse_error() {
+    let test_env = TestEnvironment::default();
+    let assert = test_env
+        .jj_cmd_with_bin(
+            "fake-dispatch-hook",
+            test_env.env_root(),
+            &["log", "--no-such-flag"],
+        )
+        .assert()
+        .code(2);
+    let stderr = get_stderr_string(&assert);
+    assert!(!stderr.contains("dispatch-hook:"), "{stderr}");
+}
@@ -246,3 +246,99 @@ fn test_chmod_file_dir_deletion_conflicts() {
     >>>>>>> Conflict 1 of 1 ends
     "###);
 }
+
+#[test]
+fn test_chmod_multiple_revisions() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[], &[("file", "a\n")]);
+    create_commit(&test_env, &repo_path, "b", &["a"], &[("file", "b\n")]);
+    create_commit(&test_env, &repo_path, "c", &["b"], &[("file", "c\n")]);
+
+    // Both selected revisions are updated, and the descendant is rebased
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["file", "chmod", "+x", "file", "-r=a|b"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 descendant commits
+    Working copy now at: royxmykx ee88380c c | c
+    Parent commit      : zsuskuln c973274f b | b
+    Added 0 files, modified 1 files, removed 0 files
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["debug", "tree", "-r=a"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file: Ok(Resolved(Some(File { id: FileId("78981922613b2afb6025042ff6bd878ac1994e85"), executable: true })))
+    "###);
+    // The executable bit is propagated to the rebased descendant
+    let stdout = test_env.jj_cmd_success(&repo_path, &["debug", "tree", "-r=c"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file: Ok(Resolved(Some(File { id: FileId("f2ad6c76f0115a6ba5b00456a849810e7ec0af20"), executable: true })))
+    "###);
+
+    // `-x` clears the executable bit
+    test_env.jj_cmd_ok(&repo_path, &["file", "chmod", "-x", "file", "-r=b"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["debug", "tree", "-r=b"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file: Ok(Resolved(Some(File { id: FileId("61780798228d17af2d34fce4cfbdf35556832472"), executable: false })))
+    "###);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_chmod_skips_symlink() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "content\n").unwrap();
+    std::os::unix::fs::symlink("file", repo_path.join("link")).unwrap();
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["file", "chmod", "x", "file", "link"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Skipping 'link', which is neither a file nor a conflict
+    Working copy now at: qpvuntsm 98dea45b (no description set)
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["debug", "tree"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file: Ok(Resolved(Some(File { id: FileId("d95f3ad14dee633a758d2e331151e950dd13e4ed"), executable: true })))
+    link: Ok(Resolved(Some(Symlink(SymlinkId("1a010b1c0f081b2e8901d55307a15c29ff30af0e")))))
+    "###);
+}
+
+#[test]
+fn test_chmod_mode_and_flags() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "content\n").unwrap();
+
+    // Flag with attached value before the mode
+    test_env.jj_cmd_ok(&repo_path, &["file", "chmod", "-r=@", "x", "file"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["debug", "tree"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file: Ok(Resolved(Some(File { id: FileId("d95f3ad14dee633a758d2e331151e950dd13e4ed"), executable: true })))
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["file", "chmod", "-x", "file", "-r=@"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["debug", "tree"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file: Ok(Resolved(Some(File { id: FileId("d95f3ad14dee633a758d2e331151e950dd13e4ed"), executable: false })))
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["file", "chmod", "foo", "file"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value 'foo' for '[MODE]'
+      [possible values: n, x]
+
+    For more information, try '--help'.
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["file", "chmod", "-x"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: a value is required for '-x <PATHS>...' but none was supplied
+
+    For more information, try '--help'.
+    "###);
+}
@@ -0,0 +1,125 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_file_list_ignored() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::create_dir_all(repo_path.join("src")).unwrap();
+    std::fs::create_dir_all(repo_path.join("vendor")).unwrap();
+    std::fs::create_dir_all(repo_path.join("docs")).unwrap();
+    std::fs::write(repo_path.join("src/main.rs"), "").unwrap();
+    std::fs::write(repo_path.join("vendor/lib.log"), "").unwrap();
+    std::fs::write(repo_path.join("docs/index.md"), "").unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "sparse", "set", "--clear", "--add", "src", "--add", "vendor",
+        ],
+    );
+    // vendor/lib.log was tracked before it got ignored
+    std::fs::write(repo_path.join("src/.gitignore"), "*.log\ntarget/\n").unwrap();
+    std::fs::write(repo_path.join("vendor/.gitignore"), "*.log\n").unwrap();
+    std::fs::create_dir_all(repo_path.join("src/target/debug")).unwrap();
+    std::fs::write(repo_path.join("src/target/debug/out"), "").unwrap();
+    std::fs::write(repo_path.join("src/new.log"), "").unwrap();
+    std::fs::write(repo_path.join("vendor/new.log"), "").unwrap();
+    // docs/ was removed from disk by the sparse change
+    std::fs::create_dir_all(repo_path.join("docs")).unwrap();
+    std::fs::write(repo_path.join("docs/notes.md"), "").unwrap();
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    docs/index.md
+    src/.gitignore
+    src/main.rs
+    vendor/.gitignore
+    vendor/lib.log
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "--ignored"]);
+    insta::assert_snapshot!(stdout.replace('\\', "/"), @r###"
+    Ignored files:
+    src/new.log
+    src/target/
+    vendor/new.log
+    Tracked files matching ignore rules:
+    vendor/lib.log
+    Untracked files outside sparse patterns:
+    docs/notes.md
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "--ignored", "src"]);
+    insta::assert_snapshot!(stdout.replace('\\', "/"), @r###"
+    Ignored files:
+    src/new.log
+    src/target/
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["file", "list", "--ignored", "-r@-"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--ignored' cannot be used with '--revision <REVISION>'
+
+    Usage: jj file list --ignored [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_file_list_modified_in() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("file1"), "a\n").unwrap();
+    std::fs::write(repo_path.join("dir/file2"), "a\n").unwrap();
+    std::fs::write(repo_path.join("file3"), "a\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "first"]);
+    std::fs::write(repo_path.join("file3"), "b\n").unwrap();
+    std::fs::remove_file(repo_path.join("file1")).unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "second"]);
+    std::fs::write(repo_path.join("file3"), "c\n").unwrap();
+    std::fs::write(repo_path.join("file4"), "c\n").unwrap();
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "--modified-in", "@"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file3
+    file4
+    "###);
+
+    // Paths changed by multiple revisions are listed once, sorted
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["file", "list", "--modified-in", "@", "--modified-in", "@-"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    file1
+    file3
+    file4
+    "###);
+
+    // Deleted paths are listed too, and paths can be restricted
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["file", "list", "--modified-in", "all()", "dir", "file1"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    dir/file2
+    file1
+    "###);
+}
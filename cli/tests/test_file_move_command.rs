@@ -0,0 +1,109 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_file_move() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("dir").join("file1"), "1\n").unwrap();
+    std::fs::write(repo_path.join("dir").join("file2"), "2\n").unwrap();
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    std::fs::write(repo_path.join("other"), "b\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+
+    // Rename a file in the parent revision
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["file", "move", "file", "renamed", "-r=@-"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 descendant commits
+    Working copy now at: rlvkpnrz b90d01f1 (empty) (no description set)
+    Parent commit      : qpvuntsm 9e362a37 (no description set)
+    Added 1 files, modified 0 files, removed 1 files
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    dir/file1
+    dir/file2
+    other
+    renamed
+    "###);
+
+    // Move a directory
+    test_env.jj_cmd_ok(&repo_path, &["file", "move", "dir", "sub/dir", "-r=@-"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    other
+    renamed
+    sub/dir/file1
+    sub/dir/file2
+    "###);
+
+    // Destination already exists
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["file", "move", "renamed", "other"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Destination 'other' already exists in commit b8cae3310b75
+    Hint: Use --force to overwrite it.
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["file", "move", "renamed", "other", "--force"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "show", "other"]);
+    insta::assert_snapshot!(stdout, @r###"
+    a
+    "###);
+
+    // Destination parent is a file
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["file", "move", "sub", "other/sub"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Destination parent 'other' is a file in commit e31947bbeefb
+    Hint: Use --force to replace it with a directory.
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    other
+    sub/dir/file1
+    sub/dir/file2
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["file", "move", "sub", "other/sub", "--force"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    other/sub/dir/file1
+    other/sub/dir/file2
+    "###);
+
+    // Destination is a directory, which isn't overwritten even with --force
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["file", "move", "other/sub/dir/file1", "other/sub", "--force"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Destination 'other/sub' is a directory in commit c844eb77e5f9
+    "###);
+
+    // Source doesn't exist
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["file", "move", "nonexistent", "new"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: No such path 'nonexistent' in commit c844eb77e5f9
+    "###);
+
+    // Can't move a directory into itself
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["file", "move", "sub", "sub/dir2"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Cannot move 'sub' into itself
+    "###);
+}
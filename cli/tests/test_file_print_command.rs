@@ -92,6 +92,108 @@ fn test_print() {
     c
     >>>>>>> Conflict 1 of 1 ends
     "###);
+
+    // Conflict markers are labeled
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "show", "--color=debug", "file1"]);
+    insta::assert_snapshot!(stdout, @r###"
+    <<conflict_marker::<<<<<<< Conflict 1 of 1>>
+    <<conflict_marker::%%%%%%% Changes from base to side #1>>
+    <<::-b>>
+    <<::+a>>
+    <<conflict_marker::+++++++ Contents of side #2>>
+    <<::c>>
+    <<conflict_marker::>>>>>>> Conflict 1 of 1 ends>>
+    "###);
+
+    // Conflict markers are labeled in diffs too
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--color=debug", "--git"]);
+    insta::assert_snapshot!(stdout, @r###"
+    [1m<<diff file_header::diff --git a/>><<diff file_header::file1>><<diff file_header:: b/>><<diff file_header::file1>><<diff file_header::>>[0m
+    [1m<<diff file_header::index >><<diff file_header::7898192261>><<diff file_header::...>><<diff file_header::0000000000>><<diff file_header:: >><<diff file_header::100644>><<diff file_header::>>[0m
+    [1m<<diff file_header::--- a/>><<diff file_header::file1>><<diff file_header::>>[0m
+    [1m<<diff file_header::+++ b/>><<diff file_header::file1>><<diff file_header::>>[0m
+    [38;5;6m<<diff hunk_header::@@ ->><<diff hunk_header::1>><<diff hunk_header::,>><<diff hunk_header::1>><<diff hunk_header:: +>><<diff hunk_header::1>><<diff hunk_header::,>><<diff hunk_header::7>><<diff hunk_header:: @@>>[39m
+    [38;5;1m<<diff removed::->><<diff removed::a>>[39m
+    [38;5;2m<<diff added::+>><<diff added conflict_marker::<<<<<<< Conflict 1 of 1>>[39m
+    [38;5;2m<<diff added::+>><<diff added conflict_marker::%%%%%%% Changes from base to side #1>>[39m
+    [38;5;2m<<diff added::+>><<diff added::-b>>[39m
+    [38;5;2m<<diff added::+>><<diff added::+a>>[39m
+    [38;5;2m<<diff added::+>><<diff added conflict_marker::+++++++ Contents of side #2>>[39m
+    [38;5;2m<<diff added::+>><<diff added::c>>[39m
+    [38;5;2m<<diff added::+>><<diff added conflict_marker::>>>>>>> Conflict 1 of 1 ends>>[39m
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--color=debug"]);
+    insta::assert_snapshot!(stdout, @r###"
+    [38;5;3m<<diff header::Created conflict in>><<diff header:: >><<diff header::file1>><<diff header:::>>[39m
+    <<diff::     >>[38;5;2m<<diff added line_number:: >><<diff added line_number:: >><<diff added line_number:: >><<diff added line_number::1>>[39m<<diff::: >>[4m[38;5;2m<<diff conflict_marker added token::<<<<<<< Conflict 1 of 1>>[24m[39m
+    <<diff::     >>[38;5;2m<<diff added line_number:: >><<diff added line_number:: >><<diff added line_number:: >><<diff added line_number::2>>[39m<<diff::: >>[4m[38;5;2m<<diff conflict_marker added token::%%%%%%% Changes from base to side #1>>[24m[39m
+    <<diff::     >>[38;5;2m<<diff added line_number:: >><<diff added line_number:: >><<diff added line_number:: >><<diff added line_number::3>>[39m<<diff::: >>[4m[38;5;2m<<diff added token::-b>>[24m[39m
+    [38;5;1m<<diff removed line_number:: >><<diff removed line_number:: >><<diff removed line_number:: >><<diff removed line_number::1>>[39m<<diff:: >>[38;5;2m<<diff added line_number:: >><<diff added line_number:: >><<diff added line_number:: >><<diff added line_number::4>>[39m<<diff::: >>[4m[38;5;2m<<diff added token::+>>[24m[39m<<diff::a>>
+    <<diff::     >>[38;5;2m<<diff added line_number:: >><<diff added line_number:: >><<diff added line_number:: >><<diff added line_number::5>>[39m<<diff::: >>[4m[38;5;2m<<diff conflict_marker added token::+++++++ Contents of side #2>>[24m[39m
+    <<diff::     >>[38;5;2m<<diff added line_number:: >><<diff added line_number:: >><<diff added line_number:: >><<diff added line_number::6>>[39m<<diff::: >>[4m[38;5;2m<<diff added token::c>>[24m[39m
+    <<diff::     >>[38;5;2m<<diff added line_number:: >><<diff added line_number:: >><<diff added line_number:: >><<diff added line_number::7>>[39m<<diff::: >>[4m[38;5;2m<<diff conflict_marker added token::>>>>>>> Conflict 1 of 1 ends>>[24m[39m
+    "###);
+}
+
+#[test]
+fn test_print_multiple_revisions() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "a\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "first"]);
+    std::fs::write(repo_path.join("file1"), "b\n").unwrap();
+    std::fs::write(repo_path.join("file2"), "c\n").unwrap();
+
+    // Files are printed per revision in the specified order
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["file", "show", "file1", "-r", "@", "-r", "@-"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    Revision rlvkpnrz c3d29abd (no description set):
+    b
+    Revision qpvuntsm df9a1eb8 first:
+    a
+    "###);
+
+    // A path absent in one revision is reported as missing
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["file", "show", "file2", "-r", "@-", "-r", "@"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    Revision qpvuntsm df9a1eb8 first:
+    Revision rlvkpnrz c3d29abd (no description set):
+    c
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: No such path in revision df9a1eb8e9f8: file2
+    "###);
+
+    // Unmatched paths are reported if they don't exist in any revision
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "file",
+            "show",
+            "file2",
+            "nonexistent",
+            "-r",
+            "@-",
+            "-r",
+            "@",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    Revision qpvuntsm df9a1eb8 first:
+    Revision rlvkpnrz c3d29abd (no description set):
+    c
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: No matching entries for paths: nonexistent
+    "###);
 }
 
 #[cfg(unix)]
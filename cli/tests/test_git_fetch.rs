@@ -207,6 +207,59 @@ fn test_git_fetch_all_remotes() {
     "###);
 }
 
+#[test]
+fn test_git_fetch_remotes_by_pattern() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.auto-local-branch = true");
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    add_git_remote(&test_env, &repo_path, "rem1");
+    add_git_remote(&test_env, &repo_path, "rem2");
+    add_git_remote(&test_env, &repo_path, "other");
+
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch", "--remote", "glob:rem*"]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1: qxosxrvv 6a211027 message
+      @rem1: qxosxrvv 6a211027 message
+    rem2: yszkquru 2497a8a0 message
+      @rem2: yszkquru 2497a8a0 message
+    "###);
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["git", "fetch", "--remote", "glob:foo*"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: No matching remotes for pattern: foo*
+    "###);
+}
+
+#[test]
+fn test_git_fetch_multiple_remotes_some_failing() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.auto-local-branch = true");
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    add_git_remote(&test_env, &repo_path, "rem1");
+    add_git_remote(&test_env, &repo_path, "rem2");
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["git", "remote", "add", "broken", "../nonexistent"],
+    );
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["git", "fetch", "--all-remotes"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Failed to fetch from remote broken: unsupported URL protocol
+    branch: rem1@rem1 [new] tracked
+    branch: rem2@rem2 [new] tracked
+    Error: Failed to fetch from remote(s): broken
+    "###);
+    // The other remotes should have been fetched
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1: qxosxrvv 6a211027 message
+      @rem1: qxosxrvv 6a211027 message
+    rem2: yszkquru 2497a8a0 message
+      @rem2: yszkquru 2497a8a0 message
+    "###);
+}
+
 #[test]
 fn test_git_fetch_multiple_remotes_from_config() {
     let test_env = TestEnvironment::default();
@@ -928,7 +981,9 @@ fn test_fetch_undo_what() {
         &["op", "restore", "--what", "repo", &base_operation_id],
     );
     insta::assert_snapshot!(stdout, @"");
-    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Kept the remote-tracking branches from the current operation.
+    "###);
     insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
     b (deleted)
       @origin: vpupmnsl hidden c7d4bdcb descr_for_b
@@ -955,7 +1010,9 @@ fn test_fetch_undo_what() {
         ],
     );
     insta::assert_snapshot!(stdout, @"");
-    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Kept the repo state and local branches from the current operation.
+    "###);
     insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
     newbranch: qpvuntsm 230dd059 (empty) (no description set)
     "###);
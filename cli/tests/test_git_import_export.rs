@@ -127,6 +127,159 @@ fn test_git_export_undo() {
     "###);
 }
 
+#[test]
+fn test_git_export_selected_branches() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let git_repo = git2::Repository::open(repo_path.join(".jj/repo/store/git")).unwrap();
+
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "a1", "a2", "b"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["git", "export", "--branch=glob:a*"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @"");
+    insta::assert_debug_snapshot!(get_git_repo_refs(&git_repo), @r###"
+    [
+        (
+            "refs/heads/a1",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+        (
+            "refs/heads/a2",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+    ]
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    a1: qpvuntsm 230dd059 (empty) (no description set)
+      @git: qpvuntsm 230dd059 (empty) (no description set)
+    a2: qpvuntsm 230dd059 (empty) (no description set)
+      @git: qpvuntsm 230dd059 (empty) (no description set)
+    b: qpvuntsm 230dd059 (empty) (no description set)
+    "###);
+
+    // Excluded branches are kept in Git unless --prune is specified
+    test_env.jj_cmd_ok(&repo_path, &["git", "export", "--all"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "export", "--branch=b"]);
+    insta::assert_debug_snapshot!(get_git_repo_refs(&git_repo), @r###"
+    [
+        (
+            "refs/heads/a1",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+        (
+            "refs/heads/a2",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+        (
+            "refs/heads/b",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+    ]
+    "###);
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["git", "export", "--branch=b", "--prune"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @"");
+    insta::assert_debug_snapshot!(get_git_repo_refs(&git_repo), @r###"
+    [
+        (
+            "refs/heads/b",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+    ]
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    a1: qpvuntsm 230dd059 (empty) (no description set)
+    a2: qpvuntsm 230dd059 (empty) (no description set)
+    b: qpvuntsm 230dd059 (empty) (no description set)
+      @git: qpvuntsm 230dd059 (empty) (no description set)
+    "###);
+
+    // --prune requires --branch
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["git", "export", "--prune"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the following required arguments were not provided:
+      --branch <BRANCH>
+
+    Usage: jj git export --branch <BRANCH> --prune
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_git_export_selected_branches_colocated() {
+    let test_env = TestEnvironment::default();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "--colocate", "repo"]);
+    let git_repo = git2::Repository::open(&repo_path).unwrap();
+
+    // All branches are exported automatically
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "a", "b"]);
+    insta::assert_debug_snapshot!(get_git_repo_refs(&git_repo), @r###"
+    [
+        (
+            "refs/heads/a",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+        (
+            "refs/heads/b",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+    ]
+    "###);
+
+    // The excluded branch isn't exported again by the same command
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["git", "export", "--branch=a", "--prune"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @"");
+    insta::assert_debug_snapshot!(get_git_repo_refs(&git_repo), @r###"
+    [
+        (
+            "refs/heads/a",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+    ]
+    "###);
+
+    // Nor by the automatic import
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    a: qpvuntsm 230dd059 (empty) (no description set)
+      @git: qpvuntsm 230dd059 (empty) (no description set)
+    b: qpvuntsm 230dd059 (empty) (no description set)
+    "###);
+    insta::assert_debug_snapshot!(get_git_repo_refs(&git_repo), @r###"
+    [
+        (
+            "refs/heads/a",
+            CommitId(
+                "230dd059e1b059aefc0da06a2e5a7dbf22362f22",
+            ),
+        ),
+    ]
+    "###);
+}
+
 #[test]
 fn test_git_import_undo() {
     let test_env = TestEnvironment::default();
@@ -252,6 +405,91 @@ fn test_git_import_move_export_with_default_undo() {
     "###);
 }
 
+#[test]
+fn test_git_import_skip_missing_objects() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let git_repo_path = repo_path.join(".jj/repo/store/git");
+    let git_repo = git2::Repository::open(&git_repo_path).unwrap();
+
+    // Create "good" branch, and "bad" branch whose parent commit is missing
+    let signature =
+        git2::Signature::new("Some One", "some.one@example.com", &git2::Time::new(0, 0)).unwrap();
+    let tree_id = git_repo.treebuilder(None).unwrap().write().unwrap();
+    let tree = git_repo.find_tree(tree_id).unwrap();
+    git_repo
+        .commit(
+            Some("refs/heads/good"),
+            &signature,
+            &signature,
+            "good",
+            &tree,
+            &[],
+        )
+        .unwrap();
+    let missing_id = git_repo
+        .commit(None, &signature, &signature, "missing", &tree, &[])
+        .unwrap();
+    let missing_commit = git_repo.find_commit(missing_id).unwrap();
+    git_repo
+        .commit(
+            Some("refs/heads/bad"),
+            &signature,
+            &signature,
+            "bad",
+            &tree,
+            &[&missing_commit],
+        )
+        .unwrap();
+    drop(missing_commit);
+    let missing_hex = missing_id.to_string();
+    std::fs::remove_file(
+        git_repo_path
+            .join("objects")
+            .join(&missing_hex[..2])
+            .join(&missing_hex[2..]),
+    )
+    .unwrap();
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["git", "import"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Failed to import refs from underlying Git repo
+    Caused by:
+    1: Ancestor of Git ref bad is missing
+    2: Object 70d81ea5756e746e8b8196e70d9a9be0ecb6310f of type commit not found
+    3: An object with id 70d81ea5756e746e8b8196e70d9a9be0ecb6310f could not be found
+    Hint: Is this Git repository a shallow or partial clone (cloned with the --depth or --filter argument)?
+    jj currently does not support shallow/partial clones. To use jj with this repository, try
+    unshallowing the repository (https://stackoverflow.com/q/6802145) or re-cloning with the full
+    repository contents.
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @"");
+
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["git", "import", "--skip-missing-objects"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    branch: good [new] tracked
+    Warning: Skipped importing refs with missing objects: bad
+    Hint: The skipped refs will be imported again by the next `jj git import`.
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    good: lpzzuynm 378d8311 (empty) good
+      @git: lpzzuynm 378d8311 (empty) good
+    "###);
+
+    // The skipped ref is retried by the next import
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["git", "import", "--skip-missing-objects"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Skipped importing refs with missing objects: bad
+    Hint: The skipped refs will be imported again by the next `jj git import`.
+    Nothing changed.
+    "###);
+}
+
 fn get_branch_output(test_env: &TestEnvironment, repo_path: &Path) -> String {
     test_env.jj_cmd_success(repo_path, &["branch", "list", "--all-remotes"])
 }
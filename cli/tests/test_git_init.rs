@@ -94,6 +94,38 @@ fn test_git_init_internal() {
     assert_eq!(read_git_target(&workspace_root), "git");
 }
 
+#[test]
+fn test_git_init_working_copy_backend() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(
+        test_env.env_root(),
+        &[
+            "git",
+            "init",
+            "repo",
+            "--config-toml=working-copy.backend='local'",
+        ],
+    );
+    let workspace_root = test_env.env_root().join("repo");
+    let type_path = workspace_root.join(".jj").join("working_copy").join("type");
+    assert_eq!(std::fs::read_to_string(type_path).unwrap(), "local");
+
+    // Unknown backend
+    let stderr = test_env.jj_cmd_failure(
+        test_env.env_root(),
+        &[
+            "git",
+            "init",
+            "repo2",
+            "--config-toml=working-copy.backend='unknown'",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Unknown working-copy backend 'unknown'. Registered backends: local
+    "###);
+    assert!(!test_env.env_root().join("repo2").join(".jj").exists());
+}
+
 #[test_case(false; "full")]
 #[test_case(true; "bare")]
 fn test_git_init_external(bare: bool) {
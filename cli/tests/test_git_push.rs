@@ -679,6 +679,108 @@ fn test_git_push_changes() {
     "###);
 }
 
+#[test]
+fn test_git_push_named() {
+    let (test_env, workspace_root) = set_up();
+    test_env.jj_cmd_ok(&workspace_root, &["describe", "-m", "foo"]);
+    std::fs::write(workspace_root.join("file"), "contents").unwrap();
+    test_env.jj_cmd_ok(&workspace_root, &["new", "-m", "bar"]);
+    std::fs::write(workspace_root.join("file"), "modified").unwrap();
+
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--named", "ci-1=@"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin:
+      Add branch ci-1 to cf1a53a8800a
+    "###);
+    // No local branch is created, and the remote branch isn't tracked
+    let stdout = test_env.jj_cmd_success(&workspace_root, &["branch", "list", "--all"]);
+    insta::assert_snapshot!(stdout, @r###"
+    branch1: xtvrqkyv d13ecdbd (empty) description 1
+      @origin: xtvrqkyv d13ecdbd (empty) description 1
+    branch2: rlzusymt 8476341e (empty) description 2
+      @origin: rlzusymt 8476341e (empty) description 2
+    ci-1@origin: yostqsxw cf1a53a8 bar
+    "###);
+
+    // Pushing the same revision again is a no-op
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--named", "ci-1=@"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch ci-1@origin already matches the specified revision
+    Nothing changed.
+    "###);
+
+    // The remote branch can be moved
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--named", "ci-1=@-"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin:
+      Move backward branch ci-1 from cf1a53a8800a to a050abf4ff07
+    "###);
+
+    // Subsequent pushes don't delete the named branch
+    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--all"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    "###);
+
+    // A tracked remote branch whose local branch was deleted becomes
+    // non-tracking, so it won't be deleted by `--deleted`
+    test_env.jj_cmd_ok(&workspace_root, &["branch", "delete", "branch2"]);
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--named", "branch2=@"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin:
+      Move sideways branch branch2 from 8476341eb395 to cf1a53a8800a
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--deleted"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    "###);
+    let stdout = test_env.jj_cmd_success(&workspace_root, &["branch", "list", "--all"]);
+    insta::assert_snapshot!(stdout, @r###"
+    branch1: xtvrqkyv d13ecdbd (empty) description 1
+      @origin: xtvrqkyv d13ecdbd (empty) description 1
+    branch2@origin: yostqsxw cf1a53a8 bar
+    ci-1@origin: yqosqzyt a050abf4 foo
+    "###);
+
+    // The revision must resolve to exactly one commit
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--named", "ci-2=@|@-"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Revset "@|@-" resolved to more than one revision
+    Hint: The revset "@|@-" resolved to these revisions:
+      yostqsxw cf1a53a8 branch2@origin | bar
+      yqosqzyt a050abf4 ci-1@origin | foo
+    "###);
+    let stderr =
+        test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--named", "ci-2=none()"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Revset "none()" didn't resolve to any revisions
+    "###);
+
+    // Malformed argument
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--named", "=@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Invalid named push target: =@
+    Hint: Specify the target as NAME=REVISION, e.g. `--named ci-1=@-`.
+    "###);
+
+    // Local branch of the same name exists
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--named", "branch1=@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Branch branch1 already exists locally
+    Hint: Use `jj git push --branch branch1` to push the local branch.
+    "###);
+}
+
 #[test]
 fn test_git_push_revisions() {
     let (test_env, workspace_root) = set_up();
@@ -795,6 +897,35 @@ fn test_git_push_existing_long_branch() {
     "###);
 }
 
+#[test]
+fn test_git_push_changes_divergent() {
+    let (test_env, workspace_root) = set_up();
+    test_env.jj_cmd_ok(&workspace_root, &["describe", "-m", "foo"]);
+    test_env.jj_cmd_ok(&workspace_root, &["describe", "-m", "bar", "--at-op", "@-"]);
+    // Resolve the concurrent operations
+    test_env.jj_cmd_ok(&workspace_root, &["status"]);
+
+    // A divergent change can't be pushed by its change ID
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--change", "yqosqzyt"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Revset "yqosqzyt" resolved to more than one revision
+    Hint: The revset "yqosqzyt" resolved to these revisions:
+      yqosqzyt?? c1bc570b (empty) bar
+      yqosqzyt?? 7283b790 (empty) foo
+    Hint: Some of these commits have the same change id. Abandon one of them with `jj abandon -r <REVISION>`.
+    "###);
+
+    // Pushing one of the commits by its commit ID is fine
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--change", "c1bc570b"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Creating branch push-19b790168e73f7a73a98deae21e807c0 for revision yqosqzytrlsw
+    Branch changes to push to origin:
+      Add branch push-19b790168e73f7a73a98deae21e807c0 to c1bc570b4f5f
+    "###);
+}
+
 #[test]
 fn test_git_push_unsnapshotted_change() {
     let (test_env, workspace_root) = set_up();
@@ -1134,6 +1265,45 @@ fn test_git_push_deleted_untracked() {
     "###);
 }
 
+#[test]
+fn test_git_push_deleted_with_remote() {
+    let (test_env, workspace_root) = set_up();
+
+    // Deleting a branch with --remote tracks the remote branch so the deletion
+    // will be pushed.
+    test_env.jj_cmd_ok(&workspace_root, &["branch", "untrack", "branch1@origin"]);
+    test_env.jj_cmd_ok(&workspace_root, &["branch", "create", "branch3"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &["branch", "delete", "branch1", "branch3", "--remote=origin"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch branch3 doesn't exist on remote origin, deleting it locally only.
+    Deleted 2 branches.
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &workspace_root), @r###"
+    branch1 (deleted)
+      @origin: xtvrqkyv d13ecdbd (empty) description 1
+    branch2: rlzusymt 8476341e (empty) description 2
+      @origin: rlzusymt 8476341e (empty) description 2
+    "###);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--deleted"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin:
+      Delete branch branch1 from d13ecdbda2a2
+    "###);
+
+    // The git-tracking remote can't be specified
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_root,
+        &["branch", "delete", "branch2", "--remote=git"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Cannot delete branches from remote: git
+    "###);
+}
+
 #[test]
 fn test_git_push_tracked_vs_all() {
     let (test_env, workspace_root) = set_up();
@@ -56,6 +56,27 @@ fn test_version() {
     );
 }
 
+#[test]
+fn test_version_build_info() {
+    let test_env = TestEnvironment::default();
+
+    let stdout = test_env.jj_cmd_success(test_env.env_root(), &["version", "--build-info"]);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(
+        lines.len(),
+        4,
+        "`jj version --build-info` output: {stdout:?}"
+    );
+    assert!(lines[0].starts_with("jj "));
+    assert!(lines[1].starts_with("commit: "));
+    assert!(lines[2].starts_with("build date: "));
+    assert!(lines[3].starts_with("features: "));
+
+    // The default output is unchanged
+    let stdout = test_env.jj_cmd_success(test_env.env_root(), &["version"]);
+    assert_eq!(stdout.lines().count(), 1);
+}
+
 #[test]
 fn test_no_subcommand() {
     let test_env = TestEnvironment::default();
@@ -411,8 +432,38 @@ fn test_color_config() {
     ◉  0000000000000000000000000000000000000000
     "###);
 
+    // --no-color overrides --config-toml 'ui.color=...'.
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "--no-color",
+            "--config-toml",
+            "ui.color=\"always\"",
+            "log",
+            "-T",
+            "commit_id",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @  230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    ◉  0000000000000000000000000000000000000000
+    "###);
+
+    // --no-color can't be combined with --color
+    let stderr = test_env.jj_cmd_cli_error(
+        &repo_path,
+        &["--no-color", "--color=always", "log", "-T", "commit_id"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--no-color' cannot be used with '--color <WHEN>'
+
+    Usage: jj --no-color <COMMAND>
+
+    For more information, try '--help'.
+    "###);
+
     // Test that NO_COLOR does NOT override the request for color in the config file
-    test_env.add_env_var("NO_COLOR", "");
+    test_env.add_env_var("NO_COLOR", "1");
     let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", "commit_id"]);
     insta::assert_snapshot!(stdout, @r###"
     @  [38;5;4m230dd059e1b059aefc0da06a2e5a7dbf22362f22[39m
@@ -432,6 +483,107 @@ fn test_color_config() {
     "###);
 }
 
+#[test]
+fn test_color_env_vars() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "contents\n").unwrap();
+
+    // Every command should pick up the same color choice.
+    let check_commands = |test_env: &TestEnvironment, extra_args: &[&str]| {
+        let commands: [&[&str]; 4] = [
+            &["log", "-T", "commit_id"],
+            &["op", "log"],
+            &["diff", "--git"],
+            &["show"],
+        ];
+        let mut summary = String::new();
+        for command in commands {
+            let args = [command, extra_args].concat();
+            let stdout = test_env.jj_cmd_success(&repo_path, &args);
+            // Check for an actual color code, not just a bare reset sequence
+            let kind = if stdout.contains("\x1b[38;5;") {
+                "color"
+            } else {
+                "plain"
+            };
+            summary.push_str(&format!("{}: {kind}\n", command.join(" ")));
+        }
+        summary
+    };
+
+    // Output isn't a terminal
+    insta::assert_snapshot!(check_commands(&test_env, &[]), @r###"
+    log -T commit_id: plain
+    op log: plain
+    diff --git: plain
+    show: plain
+    "###);
+
+    // CLICOLOR_FORCE=0 and empty values are ignored
+    test_env.add_env_var("CLICOLOR_FORCE", "0");
+    insta::assert_snapshot!(check_commands(&test_env, &[]), @r###"
+    log -T commit_id: plain
+    op log: plain
+    diff --git: plain
+    show: plain
+    "###);
+    test_env.add_env_var("CLICOLOR_FORCE", "");
+    insta::assert_snapshot!(check_commands(&test_env, &[]), @r###"
+    log -T commit_id: plain
+    op log: plain
+    diff --git: plain
+    show: plain
+    "###);
+
+    // CLICOLOR_FORCE enables color, but the command-line argument wins
+    test_env.add_env_var("CLICOLOR_FORCE", "1");
+    insta::assert_snapshot!(check_commands(&test_env, &[]), @r###"
+    log -T commit_id: color
+    op log: color
+    diff --git: color
+    show: color
+    "###);
+    insta::assert_snapshot!(check_commands(&test_env, &["--color=never"]), @r###"
+    log -T commit_id: plain
+    op log: plain
+    diff --git: plain
+    show: plain
+    "###);
+
+    // CLICOLOR_FORCE takes precedence over NO_COLOR
+    test_env.add_env_var("NO_COLOR", "1");
+    insta::assert_snapshot!(check_commands(&test_env, &[]), @r###"
+    log -T commit_id: color
+    op log: color
+    diff --git: color
+    show: color
+    "###);
+
+    // NO_COLOR is overridden by config and command-line arguments
+    test_env.add_env_var("CLICOLOR_FORCE", "");
+    insta::assert_snapshot!(check_commands(&test_env, &[]), @r###"
+    log -T commit_id: plain
+    op log: plain
+    diff --git: plain
+    show: plain
+    "###);
+    insta::assert_snapshot!(check_commands(&test_env, &["--color=always"]), @r###"
+    log -T commit_id: color
+    op log: color
+    diff --git: color
+    show: color
+    "###);
+    test_env.add_config("ui.color = 'always'");
+    insta::assert_snapshot!(check_commands(&test_env, &[]), @r###"
+    log -T commit_id: color
+    op log: color
+    diff --git: color
+    show: color
+    "###);
+}
+
 #[test]
 fn test_color_ui_messages() {
     let test_env = TestEnvironment::default();
@@ -473,7 +625,7 @@ fn test_color_ui_messages() {
     );
     insta::assert_snapshot!(stdout, @r###"
     [38;5;4m167f90e7600a50f85c4f909b53eaf546faa82879[39m
-    [1m[39m<[38;5;1mError: [39mNo Commit available>[0m  [38;5;8m(elided revisions)[39m
+    [1m[39m<[38;5;1mError: [39mNo Commit available>[0m  [38;5;8m(1 elided revision)[39m
     [38;5;4m0000000000000000000000000000000000000000[39m
     "###);
 
@@ -603,7 +755,9 @@ fn test_help() {
           --ignore-immutable             Allow rewriting immutable commits
           --at-operation <AT_OPERATION>  Operation to load the repo at [default: @] [aliases: at-op]
           --debug                        Enable debug logging
+          --op-tag <KEY=VALUE>           Attach a custom tag to the operation (can be repeated)
           --color <WHEN>                 When to colorize output (always, never, debug, auto)
+          --no-color                     Disable colorized output (same as `--color=never`)
           --quiet                        Silence non-primary command output
           --no-pager                     Disable the pager
           --config-toml <TOML>           Additional configuration options (can be repeated)
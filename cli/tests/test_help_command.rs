@@ -0,0 +1,73 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_help() {
+    let test_env = TestEnvironment::default();
+
+    let help_cmd = test_env.jj_cmd_success(test_env.env_root(), &["help"]);
+    let help_flag = test_env.jj_cmd_success(test_env.env_root(), &["--help"]);
+    assert_eq!(help_cmd, help_flag);
+
+    // Help of a subcommand
+    let help_cmd = test_env.jj_cmd_success(test_env.env_root(), &["help", "branch", "list"]);
+    let help_flag = test_env.jj_cmd_success(test_env.env_root(), &["branch", "list", "--help"]);
+    assert_eq!(help_cmd, help_flag);
+
+    // Help of a nonexistent subcommand
+    let stderr = test_env.jj_cmd_cli_error(test_env.env_root(), &["help", "nonexistent"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: unrecognized subcommand 'nonexistent'
+
+      tip: a similar subcommand exists: 'next'
+
+    Usage: jj [OPTIONS] <COMMAND>
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_help_keyword() {
+    let test_env = TestEnvironment::default();
+
+    // It should show help for a certain keyword if the `--keyword` flag is present
+    let help_cmd = test_env.jj_cmd_success(test_env.env_root(), &["help", "--keyword", "revsets"]);
+    assert!(help_cmd.starts_with("# Revsets\n"));
+
+    // It should be able to show help for the short flag
+    let help_cmd_short = test_env.jj_cmd_success(test_env.env_root(), &["help", "-k", "revsets"]);
+    assert_eq!(help_cmd, help_cmd_short);
+
+    // Unknown keyword
+    let stderr = test_env.jj_cmd_cli_error(test_env.env_root(), &["help", "-k", "nonexistent"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value 'nonexistent' for '--keyword <KEYWORD>'
+      [possible values: revsets, tutorial]
+
+    For more information, try '--help'.
+    "###);
+
+    // Keyword and subcommand can't be combined
+    let stderr = test_env.jj_cmd_cli_error(test_env.env_root(), &["help", "-k", "revsets", "log"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--keyword <KEYWORD>' cannot be used with '[COMMAND]...'
+
+    Usage: jj help --keyword <KEYWORD> [COMMAND]...
+
+    For more information, try '--help'.
+    "###);
+}
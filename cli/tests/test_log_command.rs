@@ -263,6 +263,33 @@ fn test_log_with_or_without_diff() {
     +bar
     "###);
 
+    // `--no-color` disables the colors even if configured
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-T",
+            "description",
+            "-r",
+            "@",
+            "--no-graph",
+            "-p",
+            "--git",
+            "--no-color",
+            "--config-toml=ui.color='always'",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    a new commit
+    diff --git a/file1 b/file1
+    index 257cc5642c...3bd1f0e297 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,1 +1,2 @@
+     foo
+    +bar
+    "###);
+
     // `--color-words` implies `-p`, with or without graph
     let stdout = test_env.jj_cmd_success(
         &repo_path,
@@ -294,6 +321,34 @@ fn test_log_with_or_without_diff() {
     "###);
 }
 
+#[test]
+fn test_log_stat_binary_and_conflict() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    std::fs::write(repo_path.join("image"), b"\0\x01\x02").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "a"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "b"]);
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["rebase", "-r", "@", "-d", "description(a)"]);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["log", "-T", "description", "-r", "all() ~ root()", "--stat"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @  b
+    │  file | 7 ++++++- (conflict)
+    │  1 file changed, 6 insertions(+), 1 deletion(-)
+    ◉  a
+    │  file  | 1 +
+    ~  image | Bin 0 -> 3 bytes
+       2 files changed, 1 insertion(+), 0 deletions(-)
+    "###);
+}
+
 #[test]
 fn test_log_null_terminate_multiline_descriptions() {
     let test_env = TestEnvironment::default();
@@ -1058,6 +1113,65 @@ fn test_default_revset() {
     "###);
 }
 
+#[test]
+fn test_log_include_working_copy_parents() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "parent"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "child"]);
+    test_env.add_config(r#"revsets.log = "@""#);
+    let template = r#"description ++ "\n""#;
+
+    insta::assert_snapshot!(
+        test_env.jj_cmd_success(&repo_path, &["log", "-T", template]), @r###"
+    @  child
+    │
+    ~
+    "###);
+    insta::assert_snapshot!(
+        test_env.jj_cmd_success(
+            &repo_path,
+            &["log", "-T", template, "--include-working-copy-parents"],
+        ), @r###"
+    @  child
+    │
+    ◉  parent
+    │
+    ~
+    "###);
+
+    // Enabled by config, the parents are added to the default revset
+    test_env.add_config("ui.log-include-working-copy-parents = true");
+    insta::assert_snapshot!(
+        test_env.jj_cmd_success(&repo_path, &["log", "-T", template]), @r###"
+    @  child
+    │
+    ◉  parent
+    │
+    ~
+    "###);
+
+    // but not to the explicitly specified revisions
+    insta::assert_snapshot!(
+        test_env.jj_cmd_success(&repo_path, &["log", "-T", template, "-r@"]), @r###"
+    @  child
+    │
+    ~
+    "###);
+    insta::assert_snapshot!(
+        test_env.jj_cmd_success(
+            &repo_path,
+            &["log", "-T", template, "-r@", "--include-working-copy-parents"],
+        ), @r###"
+    @  child
+    │
+    ◉  parent
+    │
+    ~
+    "###);
+}
+
 #[test]
 fn test_default_revset_per_repo() {
     let test_env = TestEnvironment::default();
@@ -1448,28 +1562,115 @@ fn test_elided() {
     ├─╮
     │ ◉  side branch 2
     │ │
-    │ ◌  (elided revisions)
+    │ ◌  (1 elided revision)
     ◉ │  main branch 2
     │ │
-    ◌ │  (elided revisions)
+    ◌ │  (1 elided revision)
     ├─╯
     ◉  initial
     │
     ~
     "###);
 
+    // Elide multiple revisions in a row
+    insta::assert_snapshot!(get_log("@ | description(initial)"), @r###"
+    @  merge
+    │
+    ◌  (4 elided revisions)
+    ◉  initial
+    │
+    ~
+    "###);
+
     // Elide shared commits. To keep the implementation simple, it still gets
     // rendered as two synthetic nodes.
     insta::assert_snapshot!(get_log("@-- | root()"), @r###"
     ◉  side branch 1
     │
-    ◌  (elided revisions)
+    ◌  (1 elided revision)
     │ ◉  main branch 1
     │ │
-    │ ◌  (elided revisions)
+    │ ◌  (1 elided revision)
+    ├─╯
+    ◉
+    "###);
+}
+
+#[test]
+fn test_log_summary_only() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "initial"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "main 1"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "main 2"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "main 3"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "main 4"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "description(initial)", "-m", "side 1"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "side 2"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "description(initial)", "-m", "wip"]);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["log", "-T", r#"description ++ "\n""#, "--summary-only"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @  wip
+    │
+    ◌  (1 elided revision)
+    │ ◉  side 2
+    │ │
+    │ ◌  (2 elided revisions)
+    ├─╯
+    │ ◉  main 4
+    │ │
+    │ ◌  (1 elided revision)
+    │ ◉  main 2
+    │ │
+    │ ◌  (2 elided revisions)
+    ├─╯
+    ◉
+    "###);
+
+    // Synthetic nodes are shown even if disabled in the config
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-T",
+            r#"description ++ "\n""#,
+            "--topology",
+            "--config-toml=ui.log-synthetic-elided-nodes=false",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @  wip
+    │
+    ◌  (1 elided revision)
+    │ ◉  side 2
+    │ │
+    │ ◌  (2 elided revisions)
+    ├─╯
+    │ ◉  main 4
+    │ │
+    │ ◌  (1 elided revision)
+    │ ◉  main 2
+    │ │
+    │ ◌  (2 elided revisions)
     ├─╯
     ◉
     "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["log", "--summary-only", "-r", "@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--summary-only' cannot be used with '--revisions <REVISIONS>'
+
+    Usage: jj log --summary-only [PATHS]...
+
+    For more information, try '--help'.
+    "###);
 }
 
 #[test]
@@ -1508,10 +1709,10 @@ fn test_log_with_custom_symbols() {
     ├─╮
     │ ┝  side branch 2
     │ │
-    │ 🮀  (elided revisions)
+    │ 🮀  (1 elided revision)
     ┝ │  main branch 2
     │ │
-    🮀 │  (elided revisions)
+    🮀 │  (1 elided revision)
     ├─╯
     ┝  initial
     │
@@ -1531,13 +1732,64 @@ fn test_log_with_custom_symbols() {
     |\
     | *  side branch 2
     | |
-    | :  (elided revisions)
+    | :  (1 elided revision)
     * |  main branch 2
     | |
-    : |  (elided revisions)
+    : |  (1 elided revision)
     |/
     *  initial
     |
     ^
     "###);
 }
+
+#[test]
+fn test_log_json() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["describe", "-m", "first \"quoted\"\n\ttab \u{1}ctl ünicode"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "a", "b"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "second"]);
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "--json", "-r", "::@"]);
+    insta::assert_snapshot!(stdout, @r###"
+    {"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:10.000+07:00"},"branches":[],"change_id":"zsuskulnrvyrovkzqrwmxqlsskqntxvp","commit_id":"5a1d28b92d1caa1db3865aa6c9291b4fd9270532","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:10.000+07:00"},"description":"second\n","parents":["78f74344b9ef50e562bc1c97c8249d45b1cf52ec"]}
+    {"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08.000+07:00"},"branches":["a","b"],"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","commit_id":"78f74344b9ef50e562bc1c97c8249d45b1cf52ec","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08.000+07:00"},"description":"first \"quoted\"\n\ttab \u0001ctl ünicode\n","parents":["0000000000000000000000000000000000000000"]}
+    {"author":{"email":"","name":"","timestamp":"1970-01-01T00:00:00.000+00:00"},"branches":[],"change_id":"zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz","commit_id":"0000000000000000000000000000000000000000","committer":{"email":"","name":"","timestamp":"1970-01-01T00:00:00.000+00:00"},"description":"","parents":[]}
+    "###);
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "--json", "--reversed", "-n1"]);
+    insta::assert_snapshot!(stdout, @r###"
+    {"author":{"email":"","name":"","timestamp":"1970-01-01T00:00:00.000+00:00"},"branches":[],"change_id":"zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz","commit_id":"0000000000000000000000000000000000000000","committer":{"email":"","name":"","timestamp":"1970-01-01T00:00:00.000+00:00"},"description":"","parents":[]}
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["log", "--json", "-T", "commit_id"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--json' cannot be used with '--template <TEMPLATE>'
+
+    Usage: jj log --json [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["log", "--json", "--stat"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--json' cannot be used with '--stat'
+
+    Usage: jj log --json [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["log", "--json", "--tool=foo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--json' cannot be used with '--tool <TOOL>'
+
+    Usage: jj log --json [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+}
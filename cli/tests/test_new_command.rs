@@ -152,6 +152,63 @@ fn test_new_merge() {
     "###);
 }
 
+#[test]
+fn test_new_merge_revset() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m=a"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m=b"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m=c"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m=d"]);
+
+    // Only the heads of the revset become parents
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["new", "--merge", "~root()"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Working copy now at: royxmykx 5a665d09 (empty) (no description set)
+    Parent commit      : mzvwutvl 203a1532 (empty) d
+    Parent commit      : zsuskuln 3b2085f0 (empty) c
+    Parent commit      : kkmpptxz 017c7f68 (empty) b
+    "###);
+    insta::assert_snapshot!(get_short_log_output(&test_env, &repo_path), @r###"
+    @      root
+    ├─┬─╮
+    │ │ ◉  b
+    │ │ ◉  a
+    │ ◉ │  c
+    │ ├─╯
+    ◉ │  d
+    ├─╯
+    ◉  root
+    "###);
+
+    // A single head creates a non-merge commit
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["new", "--merge", "description(a)::"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Hint: Revset "description(a)::" resolved to a single revision, so the new commit won't be a merge
+    Working copy now at: yostqsxw 0b6fe96d (empty) (no description set)
+    Parent commit      : kkmpptxz 017c7f68 (empty) b
+    "###);
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["new", "--merge", "none()"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Revset "none()" didn't resolve to any revisions
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["new", "--merge", "@", "@-"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--merge <REVSET>' cannot be used with '[REVISIONS]...'
+
+    Usage: jj new --merge <REVSET> [REVISIONS]...
+
+    For more information, try '--help'.
+    "###);
+}
+
 #[test]
 fn test_new_insert_after() {
     let test_env = TestEnvironment::default();
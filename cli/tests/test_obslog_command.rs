@@ -263,6 +263,43 @@ fn test_obslog_squash() {
     "###);
 }
 
+#[test]
+fn test_obslog_squash_no_graph() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "first"]);
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "second"]);
+    std::fs::write(repo_path.join("file1"), "foo\nbar\n").unwrap();
+
+    let edit_script = test_env.set_up_fake_editor();
+    std::fs::write(edit_script, "write\nsquashed").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["squash"]);
+
+    let template = r#"
+    separate(" ",
+      commit_id.short(),
+      committer.timestamp(),
+      "<-",
+      predecessors.map(|c| c.commit_id().short()),
+    ) ++ "\n"
+    "#;
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["obslog", "--no-graph", "-r", "@-", "-T", template],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    68647e349c7c 2001-02-03 04:05:10.000 +07:00 <- 766420db930c 46acd22ad249
+    766420db930c 2001-02-03 04:05:09.000 +07:00 <- fa15625b4a98
+    fa15625b4a98 2001-02-03 04:05:08.000 +07:00 <- 230dd059e1b0
+    230dd059e1b0 2001-02-03 04:05:07.000 +07:00 <-
+    46acd22ad249 2001-02-03 04:05:10.000 +07:00 <- cba41deb0fb3
+    cba41deb0fb3 2001-02-03 04:05:09.000 +07:00 <-
+    "###);
+}
+
 #[test]
 fn test_obslog_with_no_template() {
     let test_env = TestEnvironment::default();
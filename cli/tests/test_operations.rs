@@ -165,6 +165,109 @@ fn test_op_log_limit() {
     "###);
 }
 
+#[test]
+fn test_op_log_at_op() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "description 0"]);
+
+    // Later operations are excluded, and the given operation isn't marked as
+    // the current one
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-Tdescription", "--at-op=@-"]);
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  add workspace 'default'
+    ◉  initialize repo
+    ◉
+    "###);
+}
+
+#[test]
+fn test_op_description_template() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "describe",
+            "@-",
+            "-mfoo",
+            "--config-toml",
+            r#"operation.description-template = 'description ++ " (rebased " ++ num_rebased ++ ")"'"#,
+        ],
+    );
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-n1", "-Tdescription"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22 (rebased 1)
+    "###);
+
+    // Invalid template falls back to the default description
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "describe",
+            "-mbar",
+            "--config-toml",
+            r#"operation.description-template = "unknown""#,
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Failed to parse `operation.description-template`: Keyword "unknown" doesn't exist
+    Hint: Using the default operation description.
+    Working copy now at: rlvkpnrz 31f49ddf (empty) bar
+    Parent commit      : qpvuntsm 2a49fc67 (empty) foo
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-n1", "-Tdescription"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  describe commit 319cc42df77e6354e1f9cee1edd1c49b48e2d19c
+    "###);
+
+    // Evaluation error falls back to the default description
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "describe",
+            "-mbaz",
+            "--config-toml",
+            r#"operation.description-template = 'config("no-such.key")'"#,
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Failed to render `operation.description-template`: configuration property "no-such.key" not found
+    Hint: Using the default operation description.
+    Working copy now at: rlvkpnrz a6e0ff32 (empty) baz
+    Parent commit      : qpvuntsm 2a49fc67 (empty) foo
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-n1", "-Tdescription"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  describe commit 31f49ddfbb804e9c8553d487cc880cd8929a8f94
+    "###);
+
+    // So does an empty description
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "describe",
+            "-mqux",
+            "--config-toml",
+            r#"operation.description-template = '""'"#,
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: `operation.description-template` rendered an empty description
+    Hint: Using the default operation description.
+    Working copy now at: rlvkpnrz 6599e10b (empty) qux
+    Parent commit      : qpvuntsm 2a49fc67 (empty) foo
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-n1", "-Tdescription"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  describe commit a6e0ff3258500ec291420335041d5fa22886924f
+    "###);
+}
+
 #[test]
 fn test_op_log_no_graph() {
     let test_env = TestEnvironment::default();
@@ -250,6 +353,70 @@ fn test_op_log_template() {
     "###);
 }
 
+#[test]
+fn test_op_log_custom_tags() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "describe",
+            "-m=description 0",
+            "--op-tag=ticket=ABC-123",
+            "--op-tag",
+            "ci.job=build 42",
+        ],
+    );
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-n1", "-Ttags"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  args: jj describe '-m=description 0' '--op-tag=ticket=ABC-123' --op-tag 'ci.job=build 42'
+    │  ci.job: build 42
+    │  ticket: ABC-123
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "-n1",
+            "--no-graph",
+            r#"-T=separate("|", args, self.tag("ticket"), self.tag("unknown")) ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    jj describe '-m=description 0' '--op-tag=ticket=ABC-123' --op-tag 'ci.job=build 42'|ABC-123
+    "###);
+
+    // Invalid or reserved keys
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["new", "--op-tag=foo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value 'foo' for '--op-tag <KEY=VALUE>': expected KEY=VALUE
+
+    For more information, try '--help'.
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["new", "--op-tag=a b=c"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value 'a b=c' for '--op-tag <KEY=VALUE>': invalid tag key: "a b"
+
+    For more information, try '--help'.
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["new", "--op-tag=args=foo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value 'args=foo' for '--op-tag <KEY=VALUE>': tag key is reserved: "args"
+
+    For more information, try '--help'.
+    "###);
+
+    // Total size is limited
+    let large_tag = format!("--op-tag=large={}", "x".repeat(5000));
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["new", &large_tag]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Operation tags are too large (5005 bytes; the limit is 4096 bytes)
+    "###);
+}
+
 #[test]
 fn test_op_log_builtin_templates() {
     let test_env = TestEnvironment::default();
@@ -507,6 +674,123 @@ fn test_op_abandon_without_updating_working_copy() {
     "###);
 }
 
+#[test]
+fn test_op_abandon_older_than() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "commit 1"]);
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "commit 2"]);
+
+    // Test operations are timestamped in 2001, so none of them are that old.
+    let (_stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["op", "abandon", "--older-than=10000w"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    "###);
+
+    // Abandon all but the current operation.
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "abandon", "--older-than=1d"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Abandoned 3 operations and reparented 1 descendant operations.
+    "###);
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["op", "log"]), @r###"
+    @  8545e0137524 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    │  commit 81a4ef3dd421f3184289df1c58bd3a16ea1e3d8e
+    │  args: jj commit -m 'commit 2'
+    ◉  000000000000 root()
+    "###);
+
+    // The stale working-copy operation and its ancestors are kept.
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "commit 3"]);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["describe", "-m", "commit 4", "--ignore-working-copy"],
+    );
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "abandon", "--older-than=1d"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    "###);
+
+    // Once the working copy is updated, its ancestors can be abandoned.
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "abandon", "--older-than=1d"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Abandoned 3 operations and reparented 1 descendant operations.
+    "###);
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["op", "log"]), @r###"
+    @  9bb877139257 test-username@host.example.com 2001-02-03 04:05:16.000 +07:00 - 2001-02-03 04:05:16.000 +07:00
+    │  new empty commit
+    │  args: jj new
+    ◉  000000000000 root()
+    "###);
+
+    // With non-linear history, ancestors of the working-copy operation can't
+    // be abandoned through a sibling operation.
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "commit 5"]);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "branch",
+            "create",
+            "sibling",
+            "--at-op=@-",
+            "--ignore-working-copy",
+        ],
+    );
+    // Merge the divergent operations, leaving the working copy stale.
+    test_env.jj_cmd_ok(&repo_path, &["log", "--ignore-working-copy"]);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "abandon", "--older-than=1d"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    "###);
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["op", "log", "--ignore-working-copy"]), @r###"
+    @    195d61a3ef50 test-username@host.example.com 2001-02-03 04:05:21.000 +07:00 - 2001-02-03 04:05:21.000 +07:00
+    ├─╮  resolve concurrent operations
+    │ │  args: jj log --ignore-working-copy
+    ◉ │  f29fb5be6320 test-username@host.example.com 2001-02-03 04:05:19.000 +07:00 - 2001-02-03 04:05:19.000 +07:00
+    │ │  describe commit 1173132c7457307ea258237c79d276ae066ec6c5
+    │ │  args: jj describe -m 'commit 5'
+    │ ◉  fb6f2e90c1d5 test-username@host.example.com 2001-02-03 04:05:20.000 +07:00 - 2001-02-03 04:05:20.000 +07:00
+    ├─╯  create branch sibling pointing to commit 1173132c7457307ea258237c79d276ae066ec6c5
+    │    args: jj branch create sibling '--at-op=@-' --ignore-working-copy
+    ◉  9bb877139257 test-username@host.example.com 2001-02-03 04:05:16.000 +07:00 - 2001-02-03 04:05:16.000 +07:00
+    │  new empty commit
+    │  args: jj new
+    ◉  000000000000 root()
+    "###);
+    insta::assert_snapshot!(
+        test_env.jj_cmd_success(&repo_path, &["debug", "local-working-copy", "--ignore-working-copy"]), @r###"
+    Current operation: OperationId("f29fb5be632022c13a78d44c890a812d61d8b8f2179d0507e01da97d37574cd67f2c235e601c386a2291289c0b90dbcb9a6d42709ffbfda0df47f68c5b066496")
+    Current tree: Merge(Resolved(TreeId("4b825dc642cb6eb9a060e54bf8d69288fbee4904")))
+    "###);
+
+    // Both sides of the merge can be abandoned once the working copy is
+    // updated.
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "abandon", "--older-than=1d"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Abandoned 4 operations and reparented 1 descendant operations.
+    "###);
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["op", "log"]), @r###"
+    @  83598cbed422 test-username@host.example.com 2001-02-03 04:05:25.000 +07:00 - 2001-02-03 04:05:25.000 +07:00
+    │  new empty commit
+    │  args: jj new
+    ◉  000000000000 root()
+    "###);
+
+    // Operation argument and --older-than are exclusive.
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["op", "abandon", "@-", "--older-than=1d"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '[OPERATION]' cannot be used with '--older-than <DURATION>'
+
+    Usage: jj operation abandon <OPERATION>
+
+    For more information, try '--help'.
+    "###);
+}
+
 fn get_log_output(test_env: &TestEnvironment, repo_path: &Path, op_id: &str) -> String {
     test_env.jj_cmd_success(
         repo_path,
@@ -2278,6 +2278,140 @@ fn test_rebase_skip_empty() {
     "###);
 }
 
+#[test]
+fn test_rebase_source_exclude() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &["a"]);
+    create_commit(&test_env, &repo_path, "c", &["b"]);
+    create_commit(&test_env, &repo_path, "d", &["c"]);
+    create_commit(&test_env, &repo_path, "x", &[]);
+    // Test the setup
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    @  x
+    │ ◉  d
+    │ ◉  c
+    │ ◉  b
+    │ ◉  a
+    ├─╯
+    ◉
+    "###);
+    let setup_opid = test_env.current_operation_id(&repo_path);
+
+    // The excluded commit is left behind, and its child is moved onto its
+    // rebased parent
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s=b", "--source-exclude=c", "-d=x"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 commits onto destination
+    Rebased 1 descendant commits
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    ◉  d
+    ◉  b
+    @  x
+    │ ◉  c
+    │ ◉  a
+    ├─╯
+    ◉
+    "###);
+
+    // If the source itself is excluded, its children are moved onto the
+    // destination
+    test_env.jj_cmd_ok(&repo_path, &["op", "restore", &setup_opid]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s=b", "--source-exclude=b", "-d=x"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 commits onto destination
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    ◉  d
+    ◉  c
+    @  x
+    │ ◉  b
+    │ ◉  a
+    ├─╯
+    ◉
+    "###);
+
+    // Requires -s
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["rebase", "--source-exclude=c", "-d=x"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the following required arguments were not provided:
+      --source <SOURCE>
+
+    Usage: jj rebase --source <SOURCE> --source-exclude <SOURCE_EXCLUDE> <--destination <DESTINATION>|--insert-after <INSERT_AFTER>|--insert-before <INSERT_BEFORE>>
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_rebase_simplify_after() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &["a"]);
+    create_commit(&test_env, &repo_path, "c", &[]);
+    create_commit(&test_env, &repo_path, "d", &["c", "a"]);
+    create_commit(&test_env, &repo_path, "e", &["a", "b"]);
+    // Test the setup
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    @    e
+    ├─╮
+    │ ◉  b
+    ├─╯
+    │ ◉  d
+    ╭─┤
+    ◉ │  a
+    │ ◉  c
+    ├─╯
+    ◉
+    "###);
+
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s=c", "-d=a", "-d=b", "--simplify-after"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 commits
+    "###);
+    // The redundant parents of the rebased commits were removed, but "e" wasn't
+    // rebased and is left as is
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    ◉  d
+    ◉  c
+    │ @  e
+    ╭─┤
+    ◉ │  b
+    ├─╯
+    ◉  a
+    ◉
+    "###);
+
+    let stderr =
+        test_env.jj_cmd_cli_error(&repo_path, &["rebase", "-r=c", "-d=b", "--simplify-after"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--revisions <REVISIONS>' cannot be used with '--simplify-after'
+
+    Usage: jj rebase --revisions <REVISIONS> <--destination <DESTINATION>|--insert-after <INSERT_AFTER>|--insert-before <INSERT_BEFORE>>
+
+    For more information, try '--help'.
+    "###);
+}
+
 #[test]
 fn test_rebase_skip_if_on_destination() {
     let test_env = TestEnvironment::default();
@@ -981,3 +981,69 @@ fn test_multiple_conflicts() {
     Error: No conflicts found at this revision
     "###);
 }
+
+#[test]
+fn test_resolve_order() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(
+        &test_env,
+        &repo_path,
+        "base",
+        &[],
+        &[("file1", "base\nline\nline\n"), ("file2", "base\n")],
+    );
+    create_commit(
+        &test_env,
+        &repo_path,
+        "a",
+        &["base"],
+        &[("file1", "a\nline\nline\n"), ("file2", "a\n")],
+    );
+    create_commit(
+        &test_env,
+        &repo_path,
+        "b",
+        &["base"],
+        &[("file1", "b\nline\nline\n"), ("file2", "b\n")],
+    );
+    create_commit(&test_env, &repo_path, "conflict", &["a", "b"], &[]);
+
+    // Sorted by path by default
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["resolve", "--list"]), @r###"
+    file1    2-sided conflict
+    file2    2-sided conflict
+    "###);
+
+    // Smallest conflict first
+    insta::assert_snapshot!(
+        test_env.jj_cmd_success(&repo_path, &["resolve", "--list", "--order=conflict-size"]),
+        @r###"
+    file2    2-sided conflict
+    file1    2-sided conflict
+    "###);
+
+    // The first conflict in the specified order is resolved
+    let editor_script = test_env.set_up_fake_editor();
+    std::fs::write(&editor_script, "expect\n\0write\nresolution\n").unwrap();
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["resolve", "--order=conflict-size"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Resolving conflicts in: file2
+    New conflicts appeared in these commits:
+      vruxwmqv 4c48d8e8 conflict | (conflict) conflict
+    To resolve the conflicts, start by updating to it:
+      jj new vruxwmqvtpmx
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    Working copy now at: vruxwmqv 4c48d8e8 conflict | (conflict) conflict
+    Parent commit      : zsuskuln 4798b74d a | a
+    Parent commit      : royxmykx 523c9709 b | b
+    Added 0 files, modified 1 files, removed 0 files
+    There are unresolved conflicts at these paths:
+    file1    2-sided conflict
+    "###);
+}
@@ -30,3 +30,90 @@ fn test_deprecated_flags() {
     );
     assert!(stdout.contains("COMPREPLY"));
 }
+
+#[test]
+fn test_dynamic_completion_script() {
+    let test_env = TestEnvironment::default();
+    let stdout = test_env.jj_cmd_success(
+        test_env.env_root(),
+        &["util", "completion", "bash", "--dynamic"],
+    );
+    assert!(stdout.contains("complete -F _jj_dynamic"));
+    let stdout = test_env.jj_cmd_success(
+        test_env.env_root(),
+        &["util", "completion", "fish", "--dynamic"],
+    );
+    assert!(stdout.contains("__jj_dynamic_candidates"));
+
+    let stderr = test_env.jj_cmd_failure(
+        test_env.env_root(),
+        &["util", "completion", "zsh", "--dynamic"],
+    );
+    assert_snapshot!(stderr, @r###"
+    Error: Dynamic completion isn't supported for zsh
+    "###);
+}
+
+#[test]
+fn test_complete_revisions() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-mfirst"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "-r@-", "feature"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-mthird"]);
+
+    let complete = |words: &[&str]| {
+        let mut args = vec!["util", "complete", "--index"];
+        let index = (words.len() - 1).to_string();
+        args.push(&index);
+        args.push("--");
+        args.extend(words);
+        test_env.jj_cmd_success(&repo_path, &args)
+    };
+
+    // Values of flags and positional arguments taking revisions
+    assert_snapshot!(complete(&["jj", "rebase", "-d", ""]), @r###"
+    feature
+    zsuskulnrvyr
+    rlvkpnrzqnoo
+    qpvuntsmwlqt
+    zzzzzzzzzzzz
+    "###);
+    assert_snapshot!(complete(&["jj", "rebase", "-r", "@", "--destination", "f"]), @r###"
+    feature
+    zsuskulnrvyr
+    rlvkpnrzqnoo
+    qpvuntsmwlqt
+    zzzzzzzzzzzz
+    "###);
+    assert_snapshot!(complete(&["jj", "--no-pager", "new", "@", ""]), @r###"
+    feature
+    zsuskulnrvyr
+    rlvkpnrzqnoo
+    qpvuntsmwlqt
+    zzzzzzzzzzzz
+    "###);
+    assert_snapshot!(complete(&["jj", "describe", "-m", "foo", ""]), @r###"
+    feature
+    zsuskulnrvyr
+    rlvkpnrzqnoo
+    qpvuntsmwlqt
+    zzzzzzzzzzzz
+    "###);
+
+    // Other arguments
+    assert_snapshot!(complete(&["jj", "describe", "-m", ""]), @"");
+    assert_snapshot!(complete(&["jj", "rebase", "-"]), @"");
+    assert_snapshot!(complete(&["jj", "file", "list", ""]), @"");
+    assert_snapshot!(complete(&["jj", ""]), @"");
+
+    // Outside of a repository, nothing is printed
+    let stdout = test_env.jj_cmd_success(
+        test_env.env_root(),
+        &[
+            "util", "complete", "--index", "3", "--", "jj", "rebase", "-d", "",
+        ],
+    );
+    assert_snapshot!(stdout, @"");
+}
@@ -48,6 +48,38 @@ fn test_show_with_template() {
     "###);
 }
 
+#[test]
+fn test_show_stat_and_name_only() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    std::fs::write(repo_path.join("file2"), "foo\nbar\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "a new commit"]);
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["show", "-T", "description", "--stat"]);
+    insta::assert_snapshot!(stdout, @r###"
+    a new commit
+    file1 | 1 +
+    file2 | 2 ++
+    2 files changed, 3 insertions(+), 0 deletions(-)
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["show", "-T", "description", "--name-only"]);
+    insta::assert_snapshot!(stdout, @r###"
+    a new commit
+    file1
+    file2
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["show", "--stat", "--name-only"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--stat' cannot be used with '--name-only'
+
+    Usage: jj show --stat [REVISION]
+
+    For more information, try '--help'.
+    "###);
+}
+
 #[test]
 fn test_show_with_no_template() {
     let test_env = TestEnvironment::default();
@@ -524,6 +524,81 @@ fn test_split_siblings_with_merge_child() {
     "###);
 }
 
+#[test]
+fn test_split_at_line_ranges() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "a\nb\nc\nd\ne\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("file1"), "a\nB\nc\ne\n").unwrap();
+    test_env.set_up_fake_editor();
+
+    // Ranges which don't overlap changed lines are rejected
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["split", "--at", "file1:3-3"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: No changed lines in file1 at 3-3
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["split", "--at", "file1"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Invalid line range: file1
+    Hint: Line ranges should be specified as <path>:<start>-<end>, e.g. src/main.rs:3-5
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["split", "--at", "file2:1"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: file2 is not a regular file in the revision
+    "###);
+
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["split", "--at", "file1:2"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    First part: rlvkpnrz 64346269 (no description set)
+    Second part: royxmykx 00c91ee8 (no description set)
+    Working copy now at: royxmykx 00c91ee8 (no description set)
+    Parent commit      : rlvkpnrz 64346269 (no description set)
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "show", "-r", "@-", "file1"]);
+    insta::assert_snapshot!(stdout, @r###"
+    a
+    B
+    c
+    d
+    e
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file1 b/file1
+    index 6fe8acc878...a1dc605300 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,5 +1,4 @@
+     a
+     B
+     c
+    -d
+     e
+    "###);
+
+    // A removal is selected by the line following it
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["split", "--at", "file1:4"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    First part: rlvkpnrz 9aeb4097 (no description set)
+    Second part: znkkpsqq c565934a (no description set)
+    Working copy now at: znkkpsqq c565934a (no description set)
+    Parent commit      : rlvkpnrz 9aeb4097 (no description set)
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "show", "-r", "@-", "file1"]);
+    insta::assert_snapshot!(stdout, @r###"
+    a
+    b
+    c
+    e
+    "###);
+}
+
 // Make sure `jj split` would refuse to split an empty commit.
 #[test]
 fn test_split_empty() {
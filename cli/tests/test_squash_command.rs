@@ -394,6 +394,32 @@ fn test_squash_from_to() {
     insta::assert_snapshot!(stdout, @r###"
     e
     "###);
+
+    // Can squash into an ancestor other than the parent, which rebases the
+    // commits in between
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["squash", "--from", "c", "--into", "a"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 4 descendant commits
+    Working copy now at: kmkuslsw 43f15d20 f | (no description set)
+    Parent commit      : znkkpsqq 4adae72e e | (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    @  43f15d209948 f
+    ◉  4adae72ea571 e
+    ◉  0944c7d46a82 d
+    │ ◉  cca9c624bc6e b c
+    ├─╯
+    ◉  56f2e0a2ec19 a
+    ◉  000000000000
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "show", "file1", "-r", "b"]);
+    insta::assert_snapshot!(stdout, @r###"
+    c
+    "###);
 }
 
 #[test]
@@ -104,6 +104,44 @@ fn test_status_filtered() {
     "###);
 }
 
+#[test]
+fn test_status_immutable_working_copy() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    // The working-copy commit became immutable after it was checked out
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "status",
+            "--config-toml",
+            r#"revset-aliases."immutable_heads()" = "@""#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    The working copy is clean
+    Working copy : qpvuntsm 230dd059 (empty) (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: The working-copy commit is immutable
+    Hint: Create a new commit on top of it with `jj new`, or configure the set of immutable commits via `revset-aliases.immutable_heads()`.
+    "###);
+
+    // No warning if immutability checks are disabled
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "status",
+            "--ignore-immutable",
+            "--config-toml",
+            r#"revset-aliases."immutable_heads()" = "@""#,
+        ],
+    );
+    insta::assert_snapshot!(stderr, @"");
+}
+
 // See <https://github.com/martinvonz/jj/issues/3108>
 #[test]
 fn test_status_display_rebase_instructions() {
@@ -224,3 +262,50 @@ fn test_status_simplify_conflict_sides() {
     Then run `jj squash` to move the resolution into the conflicted commit.
     "###);
 }
+
+#[test]
+fn test_status_snapshot_conflicted_files() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(
+        &test_env,
+        &repo_path,
+        "base",
+        &[],
+        &[("file1", "base\n"), ("file2", "base\n")],
+    );
+    create_commit(
+        &test_env,
+        &repo_path,
+        "a",
+        &["base"],
+        &[("file1", "a\n"), ("file2", "a\n")],
+    );
+    create_commit(
+        &test_env,
+        &repo_path,
+        "b",
+        &["base"],
+        &[("file1", "b\n"), ("file2", "b\n")],
+    );
+    create_commit(&test_env, &repo_path, "conflict", &["a", "b"], &[]);
+
+    // Edit the conflict in file1 but keep the markers, and resolve file2
+    std::fs::write(
+        repo_path.join("file1"),
+        "<<<<<<<\n%%%%%%%\n-base\n+a edited\n+++++++\nb\n>>>>>>>\n",
+    )
+    .unwrap();
+    std::fs::write(repo_path.join("file2"), "resolved\n").unwrap();
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["status"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Snapshotted modified files that still contain conflicts:
+      file1
+    "###);
+
+    // The conflicted file isn't reported again if it's unchanged
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["status"]);
+    insta::assert_snapshot!(stderr, @"");
+}
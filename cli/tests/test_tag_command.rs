@@ -117,3 +117,69 @@ fn test_tag_list() {
     added_targets: commit2
     "###);
 }
+
+#[test]
+fn test_tag_list_contains() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let git_repo = {
+        let mut git_repo_path = repo_path.clone();
+        git_repo_path.extend([".jj", "repo", "store", "git"]);
+        git2::Repository::open(git_repo_path).unwrap()
+    };
+
+    let copy_ref = |src_name: &str, dest_name: &str| {
+        let src = git_repo.find_reference(src_name).unwrap();
+        let oid = src.target().unwrap();
+        git_repo.reference(dest_name, oid, true, "").unwrap();
+    };
+
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-mcommit1"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "branch1"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-mcommit2"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "branch2"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-mcommit3"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "branch3"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "export"]);
+
+    copy_ref("refs/heads/branch1", "refs/tags/v1");
+    copy_ref("refs/heads/branch2", "refs/tags/v2");
+    copy_ref("refs/heads/branch3", "refs/tags/unrelated");
+    test_env.jj_cmd_ok(&repo_path, &["git", "import"]);
+
+    let template = r#"name ++ "\n""#;
+    let list = |args: &[&str]| {
+        test_env.jj_cmd_success(
+            &repo_path,
+            &[&["tag", "list", "-T", template], args].concat(),
+        )
+    };
+
+    // Tags on descendants of the given revisions
+    insta::assert_snapshot!(list(&["--contains", "branch1"]), @r###"
+    v1
+    v2
+    "###);
+    insta::assert_snapshot!(list(&["--contains", "branch2"]), @r###"
+    v2
+    "###);
+    insta::assert_snapshot!(list(&["--contains", "branch2", "--contains", "branch3"]), @r###"
+    unrelated
+    v2
+    "###);
+
+    // Tags on ancestors of the given revisions
+    insta::assert_snapshot!(list(&["--merged", "branch2"]), @r###"
+    v1
+    v2
+    "###);
+    insta::assert_snapshot!(list(&["--no-merged", "branch2"]), @r###"
+    unrelated
+    "###);
+
+    // Filters are combined with names
+    insta::assert_snapshot!(list(&["--merged", "branch2", "glob:v*", "--no-merged", "branch1"]), @r###"
+    v2
+    "###);
+}
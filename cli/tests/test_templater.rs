@@ -129,6 +129,42 @@ fn test_templater_upper_lower() {
       render(r#""Hello".upper() ++ "Hello".lower()"#), @"HELLOhello");
 }
 
+#[test]
+fn test_templater_env_function() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_env_var("JJ_TEST_TEMPLATE_VAR", "value");
+
+    // Disabled by default
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &[
+            "log",
+            "--no-graph",
+            "-r@-",
+            r#"-Tenv("JJ_TEST_TEMPLATE_VAR")"#,
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Failed to parse template: Access to environment variables is disabled by ui.allow-template-env
+    Caused by:  --> 1:1
+      |
+    1 | env("JJ_TEST_TEMPLATE_VAR")
+      | ^-^
+      |
+      = Access to environment variables is disabled by ui.allow-template-env
+    "###);
+
+    test_env.add_config("ui.allow-template-env = true");
+    insta::assert_snapshot!(
+        get_template_output(&test_env, &repo_path, "@-", r#"env("JJ_TEST_TEMPLATE_VAR")"#),
+        @"value");
+    insta::assert_snapshot!(
+        get_template_output(&test_env, &repo_path, "@-", r#""[" ++ env("JJ_TEST_UNSET_VAR") ++ "]""#),
+        @"[]");
+}
+
 #[test]
 fn test_templater_alias() {
     let test_env = TestEnvironment::default();
@@ -312,6 +348,51 @@ fn test_templater_alias() {
     "###);
 }
 
+#[test]
+fn test_templater_alias_default_arguments() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let render = |template| get_template_output(&test_env, &repo_path, "@-", template);
+    let render_err = |template| test_env.jj_cmd_failure(&repo_path, &["log", "-T", template]);
+
+    test_env.add_config(
+        r###"
+    [template-aliases]
+    'join(x, y, sep = "/")' = 'x ++ sep ++ y'
+    "###,
+    );
+
+    insta::assert_snapshot!(render(r#"join("a", "b")"#), @r###"
+    a/b
+    "###);
+    insta::assert_snapshot!(render(r#"join("a", "b", "-")"#), @r###"
+    a-b
+    "###);
+    insta::assert_snapshot!(render(r#"join("a", sep=":", y="b")"#), @r###"
+    a:b
+    "###);
+
+    insta::assert_snapshot!(render_err(r#"join("a", "b", separator=":")"#), @r###"
+    Error: Failed to parse template: Function "join": Unexpected keyword argument "separator"
+    Caused by:  --> 1:16
+      |
+    1 | join("a", "b", separator=":")
+      |                ^-----------^
+      |
+      = Function "join": Unexpected keyword argument "separator"
+    "###);
+    insta::assert_snapshot!(render_err(r#"join("a", sep=":")"#), @r###"
+    Error: Failed to parse template: Function "join": Missing argument for parameter "y"
+    Caused by:  --> 1:6
+      |
+    1 | join("a", sep=":")
+      |      ^----------^
+      |
+      = Function "join": Missing argument for parameter "y"
+    "###);
+}
+
 #[test]
 fn test_templater_alias_override() {
     let test_env = TestEnvironment::default();
@@ -46,6 +46,43 @@ fn test_undo_rewrite_with_child() {
     "###);
 }
 
+#[test]
+fn test_undo_dry_run() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "initial"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "modified"]);
+    let op_id = test_env.current_operation_id(&repo_path);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["undo", "--dry-run"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Working-copy commit of workspace default:
+      - qpvuntsm hidden 71e87fbe (empty) modified
+      + qpvuntsm 68e11012 main | (empty) initial
+    Heads:
+      - qpvuntsm hidden 71e87fbe (empty) modified
+      + qpvuntsm 68e11012 main | (empty) initial
+    Branches:
+      - main: qpvuntsm hidden 71e87fbe (empty) modified
+      + main: qpvuntsm 68e11012 main | (empty) initial
+    Dry-run requested, not undoing.
+    "###);
+    // No new operation should be created
+    assert_eq!(test_env.current_operation_id(&repo_path), op_id);
+
+    // Undoing the branch creation conflicts with the later branch update
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["undo", "--dry-run", "@-"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Branches:
+      - main: qpvuntsm 71e87fbe main?? | (empty) modified
+      + main: (conflicted)
+    Dry-run requested, not undoing.
+    "###);
+}
+
 #[test]
 fn test_git_push_undo() {
     let test_env = TestEnvironment::default();
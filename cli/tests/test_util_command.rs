@@ -60,6 +60,13 @@ fn test_gc_args() {
     insta::assert_snapshot!(stderr, @r###"
     Error: --expire only accepts 'now'
     "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["util", "gc", "--keep-operations=2x"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value '2x' for '--keep-operations <DURATION>': Invalid duration "2x". Expected e.g. "30d" or "12h"
+
+    For more information, try '--help'.
+    "###);
 }
 
 #[test]
@@ -86,7 +93,19 @@ fn test_gc_operation_log() {
 
     // Remove some operations.
     test_env.jj_cmd_ok(&repo_path, &["operation", "abandon", "..@-"]);
-    test_env.jj_cmd_ok(&repo_path, &["util", "gc", "--expire=now"]);
+
+    // Recent operations are kept even if they are unreachable. Test operations
+    // are timestamped in 2001.
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["util", "gc", "--expire=now", "--keep-operations=10000w"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["debug", "operation", &op_to_remove]);
+
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["util", "gc", "--expire=now", "--keep-operations=1w"],
+    );
 
     // Now this doesn't work.
     let stderr = test_env.jj_cmd_failure(&repo_path, &["debug", "operation", &op_to_remove]);
@@ -95,6 +114,38 @@ fn test_gc_operation_log() {
     "###);
 }
 
+#[test]
+fn test_gc_keep_operations_keeps_commits() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let git_repo = git2::Repository::open(repo_path.join(".jj/repo/store/git")).unwrap();
+    let has_keep_ref = |id: &str| {
+        git_repo
+            .find_reference(&format!("refs/jj/keep/{id}"))
+            .is_ok()
+    };
+
+    // Create a commit, and make it reachable only from abandoned operations.
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "abandoned"]);
+    let commit_id =
+        test_env.jj_cmd_success(&repo_path, &["log", "--no-graph", "-r@", "-Tcommit_id"]);
+    test_env.jj_cmd_ok(&repo_path, &["abandon"]);
+    test_env.jj_cmd_ok(&repo_path, &["operation", "abandon", "..@-"]);
+    assert!(has_keep_ref(&commit_id));
+
+    // The commit is still referenced by the kept operations. Test operations
+    // are timestamped in 2001.
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["util", "gc", "--expire=now", "--keep-operations=10000w"],
+    );
+    assert!(has_keep_ref(&commit_id));
+
+    test_env.jj_cmd_ok(&repo_path, &["util", "gc", "--expire=now"]);
+    assert!(!has_keep_ref(&commit_id));
+}
+
 #[test]
 fn test_shell_completions() {
     #[track_caller]
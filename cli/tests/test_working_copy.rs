@@ -23,12 +23,35 @@ fn test_snapshot_large_file() {
     // test a small file using raw-integer-literal syntax, which is interpreted
     // in bytes
     test_env.add_config(r#"snapshot.max-new-file-size = 10"#);
+    std::fs::write(repo_path.join("small"), "small").unwrap();
     std::fs::write(repo_path.join("large"), "a lot of text").unwrap();
-    let stderr = test_env.jj_cmd_failure(&repo_path, &["file", "list"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["file", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    small
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Refused to snapshot some files because they are too large:
+      large: it is 3 bytes too large; the maximum size allowed is 10 bytes (10.0B)
+    Hint: This is to prevent large files from being added on accident. You can fix this by:
+      - Adding the file to `.gitignore`
+      - Run `jj config set --repo snapshot.max-new-file-size 13`
+        This will increase the maximum file size allowed for new files, in this repository only.
+      - Run `jj --config-toml 'snapshot.max-new-file-size=13' st`
+        This will increase the maximum file size allowed for new files, for this command only.
+    "###);
+
+    // the skipped file is reported again by later commands
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["st"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Working copy changes:
+    A small
+    Working copy : qpvuntsm 971c1702 (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    "###);
     insta::assert_snapshot!(stderr, @r###"
-    Error: Failed to snapshot the working copy
-    The file '$TEST_ENV/repo/large' is too large to be snapshotted: it is 3 bytes too large; the maximum size allowed is 10 bytes (10.0B).
-    Hint: This is to prevent large files from being added on accident. You can fix this error by:
+    Warning: Refused to snapshot some files because they are too large:
+      large: it is 3 bytes too large; the maximum size allowed is 10 bytes (10.0B)
+    Hint: This is to prevent large files from being added on accident. You can fix this by:
       - Adding the file to `.gitignore`
       - Run `jj config set --repo snapshot.max-new-file-size 13`
         This will increase the maximum file size allowed for new files, in this repository only.
@@ -40,15 +63,31 @@ fn test_snapshot_large_file() {
     test_env.add_config(r#"snapshot.max-new-file-size = "10KB""#);
     let big_string = vec![0; 1024 * 11];
     std::fs::write(repo_path.join("large"), big_string).unwrap();
-    let stderr = test_env.jj_cmd_failure(&repo_path, &["file", "list"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["debug", "snapshot"]);
+    insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
-    Error: Failed to snapshot the working copy
-    The file '$TEST_ENV/repo/large' is too large to be snapshotted: it is 1024 bytes too large; the maximum size allowed is 10240 bytes (10.0KiB).
-    Hint: This is to prevent large files from being added on accident. You can fix this error by:
+    Warning: Refused to snapshot some files because they are too large:
+      large: it is 1024 bytes too large; the maximum size allowed is 10240 bytes (10.0KiB)
+    Hint: This is to prevent large files from being added on accident. You can fix this by:
       - Adding the file to `.gitignore`
       - Run `jj config set --repo snapshot.max-new-file-size 11264`
         This will increase the maximum file size allowed for new files, in this repository only.
       - Run `jj --config-toml 'snapshot.max-new-file-size=11264' st`
         This will increase the maximum file size allowed for new files, for this command only.
     "###);
+
+    // the file is snapshotted once the limit is raised
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "file",
+            "list",
+            "--config-toml=snapshot.max-new-file-size=11264",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    large
+    small
+    "###);
+    insta::assert_snapshot!(stderr, @"");
 }
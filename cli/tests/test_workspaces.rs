@@ -69,6 +69,53 @@ fn test_workspaces_add_second_workspace() {
     "###);
 }
 
+/// Test the working_copies() revset with multiple workspaces
+#[test]
+fn test_workspaces_working_copies_revset() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "main"]);
+    let main_path = test_env.env_root().join("main");
+
+    std::fs::write(main_path.join("file"), "contents").unwrap();
+    test_env.jj_cmd_ok(&main_path, &["commit", "-m", "initial"]);
+    test_env.jj_cmd_ok(
+        &main_path,
+        &["workspace", "add", "--name", "second", "../secondary"],
+    );
+
+    let template = r#"separate(" ", commit_id.short(), working_copies) ++ "\n""#;
+    let stdout = test_env.jj_cmd_success(
+        &main_path,
+        &[
+            "log",
+            "--no-graph",
+            "-T",
+            template,
+            "-r",
+            "working_copies()",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    0a77a39d7d6f second@
+    8183d0fcaa4c default@
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &main_path,
+        &[
+            "log",
+            "--no-graph",
+            "-T",
+            template,
+            "-r",
+            "~working_copies()",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    751b12b7b981
+    000000000000
+    "###);
+}
+
 /// Test how sparse patterns are inherited
 #[test]
 fn test_workspaces_sparse_patterns() {
@@ -612,7 +659,9 @@ fn test_workspaces_forget() {
     test_env.jj_cmd_ok(&main_path, &["workspace", "add", "../secondary"]);
     let (stdout, stderr) = test_env.jj_cmd_ok(&main_path, &["workspace", "forget"]);
     insta::assert_snapshot!(stdout, @"");
-    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Files in the forgotten workspace are left on disk, but are no longer tracked.
+    "###);
 
     // When listing workspaces, only the secondary workspace shows up
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
@@ -659,7 +708,9 @@ fn test_workspaces_forget() {
     let (stdout, stderr) =
         test_env.jj_cmd_ok(&main_path, &["workspace", "forget", "secondary", "third"]);
     insta::assert_snapshot!(stdout, @"");
-    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Files in the forgotten workspaces are left on disk, but are no longer tracked.
+    "###);
     // No workspaces left
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
     insta::assert_snapshot!(stdout, @"");
@@ -53,6 +53,14 @@ static CONFLICT_MARKER_REGEX: once_cell::sync::Lazy<Regex> = once_cell::sync::La
     .unwrap()
 });
 
+/// Returns true if the `line` (including the trailing newline) is one of the
+/// conflict markers written by `materialize_merge_result()`.
+pub fn is_conflict_marker_line(line: &[u8]) -> bool {
+    CONFLICT_MARKER_REGEX
+        .find(line)
+        .is_some_and(|m| m.start() == 0 && m.end() == line.len())
+}
+
 fn write_diff_hunks(hunks: &[DiffHunk], file: &mut dyn Write) -> std::io::Result<()> {
     for hunk in hunks {
         match hunk {
@@ -145,6 +153,10 @@ impl MaterializedTreeValue {
     pub fn is_present(&self) -> bool {
         !self.is_absent()
     }
+
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, MaterializedTreeValue::Conflict { .. })
+    }
 }
 
 /// Reads the data associated with a `MergedTreeValue` so it can be written to
@@ -0,0 +1,352 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Detects copied and renamed files between two trees by comparing file
+//! contents. Backends don't record copies, so this is the only source of copy
+//! information for now.
+
+#![warn(missing_docs)]
+
+use std::collections::{HashMap, HashSet};
+
+use crate::backend::{BackendResult, FileId, TreeValue};
+use crate::diff::{self, Diff, DiffHunk};
+use crate::matchers::Matcher;
+use crate::merge::MergedTreeValue;
+use crate::merged_tree::MergedTree;
+use crate::repo_path::{RepoPath, RepoPathBuf};
+
+/// Default minimum similarity (in percent) for a file to be considered a copy.
+pub const DEFAULT_SIMILARITY_THRESHOLD: u32 = 50;
+
+/// Default maximum number of copy sources or targets to compare by content.
+pub const DEFAULT_CANDIDATE_LIMIT: usize = 1000;
+
+/// Parameters of copy detection.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CopyDetectionOptions {
+    /// Minimum similarity (in percent) for a pair of files to be reported as a
+    /// copy or rename.
+    pub threshold: u32,
+    /// Whether files which are unmodified between the trees should be
+    /// considered as copy sources.
+    pub find_copies_harder: bool,
+    /// Maximum number of sources or targets to compare by content. If there
+    /// are more, only identical files are detected.
+    pub candidate_limit: usize,
+}
+
+impl Default for CopyDetectionOptions {
+    fn default() -> Self {
+        CopyDetectionOptions {
+            threshold: DEFAULT_SIMILARITY_THRESHOLD,
+            find_copies_harder: false,
+            candidate_limit: DEFAULT_CANDIDATE_LIMIT,
+        }
+    }
+}
+
+/// Whether the source of a copy still exists in the new tree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CopyOperation {
+    /// The source was removed.
+    Rename,
+    /// The source still exists, or it was also renamed to another target.
+    Copy,
+}
+
+/// Pair of source and target files compared by content.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CopyCandidate {
+    /// Path of the file in the old tree.
+    pub source: RepoPathBuf,
+    /// Path of the added file in the new tree.
+    pub target: RepoPathBuf,
+    /// Whether the source was removed in the new tree.
+    pub source_removed: bool,
+    /// Similarity of the file contents in percent.
+    pub similarity: u32,
+}
+
+/// Copy or rename selected from the candidates.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DetectedCopy {
+    /// Path of the file in the old tree.
+    pub source: RepoPathBuf,
+    /// Path of the added file in the new tree.
+    pub target: RepoPathBuf,
+    /// Whether this is a rename or a copy.
+    pub operation: CopyOperation,
+    /// Similarity of the file contents in percent.
+    pub similarity: u32,
+}
+
+/// Copies and renames detected between two trees, sorted by target path.
+#[derive(Clone, Debug, Default)]
+pub struct DetectedCopies {
+    copies: Vec<DetectedCopy>,
+}
+
+impl DetectedCopies {
+    /// Returns true if no copies were detected.
+    pub fn is_empty(&self) -> bool {
+        self.copies.is_empty()
+    }
+
+    /// Iterates over the detected copies.
+    pub fn iter(&self) -> impl Iterator<Item = &DetectedCopy> {
+        self.copies.iter()
+    }
+
+    /// Looks up the copy whose target is `path`.
+    pub fn for_target(&self, path: &RepoPath) -> Option<&DetectedCopy> {
+        self.copies
+            .binary_search_by(|copy| copy.target.as_ref().cmp(path))
+            .ok()
+            .map(|index| &self.copies[index])
+    }
+
+    /// Returns true if `path` was renamed to one of the targets.
+    pub fn is_renamed_source(&self, path: &RepoPath) -> bool {
+        self.copies
+            .iter()
+            .any(|copy| copy.operation == CopyOperation::Rename && copy.source.as_ref() == path)
+    }
+}
+
+/// Detects copies and renames from `from_tree` to `to_tree`.
+pub fn detect_copies(
+    from_tree: &MergedTree,
+    to_tree: &MergedTree,
+    matcher: &dyn Matcher,
+    options: &CopyDetectionOptions,
+) -> BackendResult<DetectedCopies> {
+    let candidates = score_copy_candidates(from_tree, to_tree, matcher, options)?;
+    Ok(select_copies(&candidates, options.threshold))
+}
+
+/// Compares files added in `to_tree` with the possible sources in
+/// `from_tree`.
+///
+/// Pairs of files are skipped if their sizes are too different for the
+/// similarity to reach `options.threshold`. The returned candidates are sorted
+/// by target path and then by descending similarity.
+pub fn score_copy_candidates(
+    from_tree: &MergedTree,
+    to_tree: &MergedTree,
+    matcher: &dyn Matcher,
+    options: &CopyDetectionOptions,
+) -> BackendResult<Vec<CopyCandidate>> {
+    let store = from_tree.store();
+    let mut sources: Vec<(RepoPathBuf, FileId, bool)> = vec![];
+    let mut targets: Vec<(RepoPathBuf, FileId)> = vec![];
+    for (path, diff) in from_tree.diff(to_tree, matcher) {
+        let (before, after) = diff?;
+        match (resolved_file_id(&before), resolved_file_id(&after)) {
+            (Some(before_id), _) if after.is_absent() => {
+                sources.push((path, before_id.clone(), true));
+            }
+            (Some(before_id), _) => sources.push((path, before_id.clone(), false)),
+            (None, Some(after_id)) if before.is_absent() => {
+                targets.push((path, after_id.clone()));
+            }
+            _ => {}
+        }
+    }
+    if targets.is_empty() {
+        return Ok(vec![]);
+    }
+    if options.find_copies_harder {
+        let changed: HashSet<RepoPathBuf> =
+            sources.iter().map(|(path, _, _)| path.clone()).collect();
+        for (path, value) in from_tree.entries_matching(matcher) {
+            if changed.contains(&path) {
+                continue;
+            }
+            if let Some(id) = resolved_file_id(&value?) {
+                sources.push((path, id.clone(), false));
+            }
+        }
+    }
+
+    let mut candidates = vec![];
+    let mut sources_by_id: HashMap<&FileId, Vec<usize>> = HashMap::new();
+    for (index, (_, id, _)) in sources.iter().enumerate() {
+        sources_by_id.entry(id).or_default().push(index);
+    }
+    let compare_contents =
+        sources.len() <= options.candidate_limit && targets.len() <= options.candidate_limit;
+    let mut source_contents: HashMap<usize, Vec<u8>> = HashMap::new();
+    for (target_path, target_id) in &targets {
+        let identical_sources = sources_by_id.get(target_id);
+        for &index in identical_sources.into_iter().flatten() {
+            let (source_path, _, source_removed) = &sources[index];
+            candidates.push(CopyCandidate {
+                source: source_path.clone(),
+                target: target_path.clone(),
+                source_removed: *source_removed,
+                similarity: 100,
+            });
+        }
+        if identical_sources.is_some() || !compare_contents {
+            continue;
+        }
+        let target_content = store.read_file_content(target_path, target_id)?;
+        if target_content.is_empty() {
+            continue;
+        }
+        for (index, (source_path, source_id, source_removed)) in sources.iter().enumerate() {
+            let source_content = match source_contents.get(&index) {
+                Some(content) => content,
+                None => {
+                    let content = store.read_file_content(source_path, source_id)?;
+                    source_contents.entry(index).or_insert(content)
+                }
+            };
+            if source_content.is_empty()
+                || max_similarity(source_content.len(), target_content.len()) < options.threshold
+            {
+                continue;
+            }
+            candidates.push(CopyCandidate {
+                source: source_path.clone(),
+                target: target_path.clone(),
+                source_removed: *source_removed,
+                similarity: similarity(source_content, &target_content),
+            });
+        }
+    }
+    candidates.sort_by(|a, b| {
+        (&a.target, b.similarity, &a.source).cmp(&(&b.target, a.similarity, &b.source))
+    });
+    Ok(candidates)
+}
+
+/// Picks the most similar source for each target among the candidates whose
+/// similarity is at least `threshold`.
+///
+/// A removed source is reported as renamed to its most similar target, and as
+/// copied to any other targets.
+pub fn select_copies(candidates: &[CopyCandidate], threshold: u32) -> DetectedCopies {
+    let mut ordered = candidates
+        .iter()
+        .filter(|candidate| candidate.similarity >= threshold)
+        .collect::<Vec<_>>();
+    // Stable sort keeps the path order among equally similar pairs
+    ordered.sort_by_key(|candidate| std::cmp::Reverse(candidate.similarity));
+    let mut assigned_targets = HashSet::new();
+    let mut renamed_sources = HashSet::new();
+    let mut copies = vec![];
+    for candidate in ordered {
+        if !assigned_targets.insert(&candidate.target) {
+            continue;
+        }
+        let operation = if candidate.source_removed && renamed_sources.insert(&candidate.source) {
+            CopyOperation::Rename
+        } else {
+            CopyOperation::Copy
+        };
+        copies.push(DetectedCopy {
+            source: candidate.source.clone(),
+            target: candidate.target.clone(),
+            operation,
+            similarity: candidate.similarity,
+        });
+    }
+    copies.sort_by(|a, b| a.target.cmp(&b.target));
+    DetectedCopies { copies }
+}
+
+/// Computes the similarity of two file contents in percent, based on the size
+/// of the lines they have in common.
+pub fn similarity(left: &[u8], right: &[u8]) -> u32 {
+    let total_len = left.len() + right.len();
+    if total_len == 0 {
+        return 100;
+    }
+    let diff = Diff::for_tokenizer(&[left, right], diff::find_line_ranges);
+    let matching_len: usize = diff
+        .hunks()
+        .map(|hunk| match hunk {
+            DiffHunk::Matching(content) => content.len(),
+            DiffHunk::Different(_) => 0,
+        })
+        .sum();
+    (matching_len * 2 * 100 / total_len) as u32
+}
+
+/// Upper bound of the similarity of two files of the given sizes.
+fn max_similarity(left_len: usize, right_len: usize) -> u32 {
+    (left_len.min(right_len) * 2 * 100 / (left_len + right_len)) as u32
+}
+
+fn resolved_file_id(value: &MergedTreeValue) -> Option<&FileId> {
+    match value.as_resolved() {
+        Some(Some(TreeValue::File { id, .. })) => Some(id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(source: &str, target: &str, removed: bool, similarity: u32) -> CopyCandidate {
+        CopyCandidate {
+            source: RepoPathBuf::from_internal_string(source),
+            target: RepoPathBuf::from_internal_string(target),
+            source_removed: removed,
+            similarity,
+        }
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity(b"", b""), 100);
+        assert_eq!(similarity(b"a\nb\n", b"a\nb\n"), 100);
+        assert_eq!(similarity(b"a\nb\n", b"a\nc\n"), 50);
+        assert_eq!(similarity(b"a\n", b"b\n"), 0);
+        assert_eq!(max_similarity(10, 30), 50);
+    }
+
+    #[test]
+    fn test_select_copies() {
+        let copies = select_copies(
+            &[
+                candidate("a", "b", true, 60),
+                candidate("a", "c", true, 90),
+                candidate("d", "c", true, 95),
+                candidate("d", "e", true, 40),
+            ],
+            50,
+        );
+        let copies = copies
+            .iter()
+            .map(|copy| {
+                (
+                    copy.source.as_internal_file_string(),
+                    copy.target.as_internal_file_string(),
+                    copy.operation,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            copies,
+            [
+                ("a", "b", CopyOperation::Rename),
+                ("d", "c", CopyOperation::Rename),
+            ]
+        );
+    }
+}
@@ -397,6 +397,34 @@ impl CompositeIndex {
         candidate_positions
     }
 
+    /// Returns the ancestors which are exactly `generation` steps back from
+    /// the `pos` commit along any path of parent edges. Since merges can reach
+    /// the same commit by paths of different lengths, a commit may be included
+    /// at more than one generation.
+    pub fn ancestors_at_generation(
+        &self,
+        pos: IndexPosition,
+        generation: u32,
+    ) -> BTreeSet<IndexPosition> {
+        let mut positions = BTreeSet::from([pos]);
+        for remaining in (0..generation).rev() {
+            let mut parent_positions = BTreeSet::new();
+            for pos in positions {
+                let entry = self.entry_by_pos(pos);
+                // Parents closer to the root than the remaining steps can't
+                // reach the target generation.
+                parent_positions.extend(
+                    entry
+                        .parents()
+                        .filter(|parent| parent.generation_number() >= remaining)
+                        .map(|parent| parent.position()),
+                );
+            }
+            positions = parent_positions;
+        }
+        positions
+    }
+
     pub(super) fn evaluate_revset(
         &self,
         expression: &ResolvedExpression,
@@ -445,6 +473,11 @@ impl Index for &CompositeIndex {
         self.commit_id_to_pos(commit_id).is_some()
     }
 
+    fn generation_number(&self, commit_id: &CommitId) -> Option<u32> {
+        let entry = self.entry_by_id(commit_id)?;
+        Some(entry.generation_number())
+    }
+
     fn is_ancestor(&self, ancestor_id: &CommitId, descendant_id: &CommitId) -> bool {
         let ancestor_pos = self.commit_id_to_pos(ancestor_id).unwrap();
         let descendant_pos = self.commit_id_to_pos(descendant_id).unwrap();
@@ -486,6 +486,10 @@ impl Index for DefaultMutableIndex {
         self.as_composite().has_id(commit_id)
     }
 
+    fn generation_number(&self, commit_id: &CommitId) -> Option<u32> {
+        self.as_composite().generation_number(commit_id)
+    }
+
     fn is_ancestor(&self, ancestor_id: &CommitId, descendant_id: &CommitId) -> bool {
         self.as_composite().is_ancestor(ancestor_id, descendant_id)
     }
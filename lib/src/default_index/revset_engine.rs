@@ -886,6 +886,50 @@ impl<'index> EvaluationContext<'index> {
                 });
                 Ok(Box::new(EagerRevset { positions }))
             }
+            ResolvedExpression::Mergers { roots, heads } => {
+                let root_positions = self
+                    .evaluate(roots)?
+                    .positions()
+                    .attach(index)
+                    .collect_vec();
+                let head_positions = self
+                    .evaluate(heads)?
+                    .positions()
+                    .attach(index)
+                    .collect_vec();
+                let root_set: HashSet<_> = root_positions.iter().copied().collect();
+                let descendants = RevWalkBuilder::new(index)
+                    .wanted_heads(head_positions)
+                    .descendants(root_positions)
+                    .collect_vec();
+                let descendant_set: HashSet<_> = descendants.iter().copied().collect();
+                // A merge brings in the commits reachable from the other parents
+                // but not from the first parent.
+                let positions = descendants
+                    .into_iter()
+                    .rev()
+                    .filter(|&pos| {
+                        let parent_positions = index.entry_by_pos(pos).parent_positions();
+                        let Some((&first, rest)) = parent_positions.split_first() else {
+                            return false;
+                        };
+                        let merged_heads = rest
+                            .iter()
+                            .copied()
+                            .filter(|parent| descendant_set.contains(parent))
+                            .collect_vec();
+                        if merged_heads.is_empty() {
+                            return false;
+                        }
+                        RevWalkBuilder::new(index)
+                            .wanted_heads(merged_heads)
+                            .unwanted_roots([first])
+                            .ancestors()
+                            .any(|pos| root_set.contains(&pos))
+                    })
+                    .collect();
+                Ok(Box::new(EagerRevset { positions }))
+            }
             ResolvedExpression::Latest { candidates, count } => {
                 let candidate_set = self.evaluate(candidates)?;
                 Ok(Box::new(
@@ -434,6 +434,14 @@ impl<'input> Diff<'input> {
         Diff::for_tokenizer(inputs, |_| vec![])
     }
 
+    /// Compares `inputs` word by word, without splitting them into lines
+    /// first. Changes in the non-word bytes between words are refined too.
+    pub fn by_word(inputs: &[&'input [u8]]) -> Self {
+        let mut diff = Diff::for_tokenizer(inputs, find_word_ranges);
+        diff.refine_changed_regions(find_nonword_ranges);
+        diff
+    }
+
     // TODO: At least when merging, it's wasteful to refine the diff if e.g. if 2
     // out of 3 inputs match in the differing regions. Perhaps the refine()
     // method should be on the hunk instead (probably returning a new Diff)?
@@ -930,6 +938,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_diff_by_word() {
+        // Words are matched across lines
+        let diff = Diff::by_word(&[b"a b\nc d", b"a X c\nd"]);
+        assert_eq!(
+            diff.hunks().collect_vec(),
+            vec![
+                DiffHunk::Matching(b"a "),
+                DiffHunk::Different(vec![b"b\n", b"X "]),
+                DiffHunk::Matching(b"c"),
+                DiffHunk::Different(vec![b" ", b"\n"]),
+                DiffHunk::Matching(b"d"),
+            ]
+        );
+
+        // Non-word bytes are diffed too
+        let diff = Diff::by_word(&[b"foo(bar)", b"foo[bar]"]);
+        assert_eq!(
+            diff.hunks().collect_vec(),
+            vec![
+                DiffHunk::Matching(b"foo"),
+                DiffHunk::Different(vec![b"(", b"["]),
+                DiffHunk::Matching(b"bar"),
+                DiffHunk::Different(vec![b")", b"]"]),
+            ]
+        );
+    }
+
     #[test]
     fn test_diff_nothing_in_common() {
         assert_eq!(
@@ -363,8 +363,8 @@ impl<R: RuleType> StringLiteralParser<R> {
 #[derive(Clone, Debug, Default)]
 pub struct AliasesMap<P> {
     symbol_aliases: HashMap<String, String>,
-    // name: [(params, defn)] (sorted by arity)
-    function_aliases: HashMap<String, Vec<(Vec<String>, String)>>,
+    // name: [(params, defaults, defn)] (sorted by arity)
+    function_aliases: HashMap<String, Vec<FunctionAlias>>,
     // Parser type P helps prevent misuse of AliasesMap of different language.
     parser: P,
 }
@@ -391,16 +391,39 @@ impl<P> AliasesMap<P> {
                 self.symbol_aliases.insert(name, defn.into());
             }
             AliasDeclaration::Function(name, params) => {
-                let overloads = self.function_aliases.entry(name).or_default();
-                match overloads.binary_search_by_key(&params.len(), |(params, _)| params.len()) {
-                    Ok(i) => overloads[i] = (params, defn.into()),
-                    Err(i) => overloads.insert(i, (params, defn.into())),
-                }
+                self.insert_function(name, params, vec![], defn.into());
+            }
+            AliasDeclaration::FunctionWithDefaults {
+                name,
+                params,
+                defaults,
+            } => {
+                self.insert_function(name, params, defaults, defn.into());
             }
         }
         Ok(())
     }
 
+    fn insert_function(
+        &mut self,
+        name: String,
+        params: Vec<String>,
+        defaults: Vec<String>,
+        defn: String,
+    ) {
+        assert!(defaults.len() <= params.len());
+        let alias = FunctionAlias {
+            params,
+            defaults,
+            defn,
+        };
+        let overloads = self.function_aliases.entry(name).or_default();
+        match overloads.binary_search_by_key(&alias.params.len(), |a| a.params.len()) {
+            Ok(i) => overloads[i] = alias,
+            Err(i) => overloads.insert(i, alias),
+        }
+    }
+
     /// Iterates symbol names in arbitrary order.
     pub fn symbol_names(&self) -> impl Iterator<Item = &str> {
         self.symbol_aliases.keys().map(|n| n.as_ref())
@@ -422,7 +445,8 @@ impl<P> AliasesMap<P> {
     /// parameter names, and definition text.
     pub fn get_function(&self, name: &str, arity: usize) -> Option<(AliasId<'_>, &[String], &str)> {
         let overloads = self.get_function_overloads(name)?;
-        overloads.find_by_arity(arity)
+        let (id, alias) = overloads.find_by_arity(arity)?;
+        Some((id, &alias.params, &alias.defn))
     }
 
     /// Looks up function aliases by name.
@@ -432,35 +456,98 @@ impl<P> AliasesMap<P> {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-struct AliasFunctionOverloads<'a> {
-    name: &'a String,
-    overloads: &'a Vec<(Vec<String>, String)>,
+#[derive(Clone, Debug)]
+struct FunctionAlias {
+    params: Vec<String>,
+    // Default values of the trailing parameters.
+    defaults: Vec<String>,
+    defn: String,
 }
 
-impl<'a> AliasFunctionOverloads<'a> {
-    fn arities(self) -> impl DoubleEndedIterator<Item = usize> + ExactSizeIterator + 'a {
-        self.overloads.iter().map(|(params, _)| params.len())
+impl FunctionAlias {
+    fn min_arity(&self) -> usize {
+        self.params.len() - self.defaults.len()
+    }
+
+    /// Returns default value of the `i`-th parameter if any.
+    fn default_value(&self, i: usize) -> Option<&str> {
+        let j = i.checked_sub(self.min_arity())?;
+        self.defaults.get(j).map(|s| s.as_ref())
     }
 
-    fn min_arity(self) -> usize {
-        self.arities().next().unwrap()
+    /// Assigns positional and keyword arguments to the parameters. Parameters
+    /// not specified by the caller are left as `None`.
+    fn bind_arguments<'i, T>(
+        &self,
+        function: FunctionCallNode<'i, T>,
+    ) -> Result<Vec<Option<ExpressionNode<'i, T>>>, InvalidArguments<'i>> {
+        let invalid_arguments = |message: String, span| InvalidArguments {
+            name: function.name,
+            message,
+            span,
+        };
+        let mut extracted = Vec::with_capacity(self.params.len());
+        extracted.extend(function.args.into_iter().map(Some));
+        extracted.resize_with(self.params.len(), || None);
+
+        for arg in function.keyword_args {
+            let name = arg.name;
+            let span = arg.name_span.start_pos().span(&arg.value.span.end_pos());
+            let pos = self.params.iter().position(|n| n == name).ok_or_else(|| {
+                invalid_arguments(format!(r#"Unexpected keyword argument "{name}""#), span)
+            })?;
+            if extracted[pos].is_some() {
+                return Err(invalid_arguments(
+                    format!(r#"Got multiple values for keyword "{name}""#),
+                    span,
+                ));
+            }
+            extracted[pos] = Some(arg.value);
+        }
+
+        if let Some(pos) = (0..self.min_arity()).find(|&i| extracted[i].is_none()) {
+            let name = &self.params[pos];
+            return Err(invalid_arguments(
+                format!(r#"Missing argument for parameter "{name}""#),
+                function.args_span,
+            ));
+        }
+        Ok(extracted)
     }
+}
 
-    fn max_arity(self) -> usize {
-        self.arities().next_back().unwrap()
+#[derive(Clone, Copy, Debug)]
+struct AliasFunctionOverloads<'a> {
+    name: &'a String,
+    overloads: &'a Vec<FunctionAlias>,
+}
+
+impl<'a> AliasFunctionOverloads<'a> {
+    /// Iterates acceptable numbers of arguments in ascending order.
+    fn arities(self) -> impl Iterator<Item = usize> + 'a {
+        self.overloads
+            .iter()
+            .flat_map(|alias| alias.min_arity()..=alias.params.len())
+            .sorted()
+            .dedup()
     }
 
-    fn find_by_arity(self, arity: usize) -> Option<(AliasId<'a>, &'a [String], &'a str)> {
-        let index = self
+    fn find_by_arity(self, arity: usize) -> Option<(AliasId<'a>, &'a FunctionAlias)> {
+        // Exact match takes precedence over the one filled with default values.
+        let alias = match self
             .overloads
-            .binary_search_by_key(&arity, |(params, _)| params.len())
-            .ok()?;
-        let (params, defn) = &self.overloads[index];
+            .binary_search_by_key(&arity, |alias| alias.params.len())
+        {
+            Ok(index) => &self.overloads[index],
+            Err(_) => self
+                .overloads
+                .iter()
+                .find(|alias| (alias.min_arity()..=alias.params.len()).contains(&arity))?,
+        };
         // Exact parameter names aren't needed to identify a function, but they
         // provide a better error indication. (e.g. "foo(x, y)" is easier to
         // follow than "foo/2".)
-        Some((AliasId::Function(self.name, params), params, defn))
+        Some((AliasId::Function(self.name, &alias.params), alias))
     }
 }
 
@@ -494,6 +581,16 @@ pub enum AliasDeclaration {
     Symbol(String),
     /// Function name and parameters.
     Function(String, Vec<String>),
+    /// Function name, parameters, and default values of the trailing
+    /// parameters.
+    FunctionWithDefaults {
+        /// Function name.
+        name: String,
+        /// Parameter names.
+        params: Vec<String>,
+        /// Default values of the trailing parameters.
+        defaults: Vec<String>,
+    },
 }
 
 // AliasDeclarationParser and AliasDefinitionParser can be merged into a single
@@ -520,6 +617,11 @@ pub trait AliasDefinitionParser {
         &self,
         source: &'i str,
     ) -> Result<ExpressionNode<'i, Self::Output<'i>>, Self::Error>;
+
+    /// Whether function aliases can be called with keyword arguments.
+    fn allows_keyword_arguments(&self) -> bool {
+        false
+    }
 }
 
 /// Expression item that supports alias substitution.
@@ -586,6 +688,31 @@ where
         self.states.pop();
         result
     }
+
+    fn expand_default_value(
+        &mut self,
+        id: AliasId<'i>,
+        defn: &'i str,
+        span: pest::Span<'i>,
+    ) -> Result<ExpressionNode<'i, T>, E> {
+        if self.states.iter().any(|s| s.id == id) {
+            return Err(E::recursive_expansion(id, span));
+        }
+        // Don't spill out the current scope. The alias itself is on the stack
+        // to detect recursion through the default value.
+        self.states.push(AliasExpandingState {
+            id,
+            locals: HashMap::new(),
+        });
+        let result = self
+            .aliases_map
+            .parser
+            .parse_definition(defn)
+            .and_then(|node| self.fold_expression(node))
+            .map_err(|e| e.within_alias_expansion(id, span));
+        self.states.pop();
+        result
+    }
 }
 
 impl<'i, T, P, E> ExpressionFolder<'i, T> for AliasExpander<'i, T, P>
@@ -616,25 +743,41 @@ where
         // For better error indication, builtin functions are shadowed by name,
         // not by (name, arity).
         if let Some(overloads) = self.aliases_map.get_function_overloads(function.name) {
-            // TODO: add support for keyword arguments
-            function
-                .ensure_no_keyword_arguments()
-                .map_err(E::invalid_arguments)?;
-            let Some((id, params, defn)) = overloads.find_by_arity(function.arity()) else {
-                let min = overloads.min_arity();
-                let max = overloads.max_arity();
-                let err = if max - min + 1 == overloads.arities().len() {
+            if !self.aliases_map.parser.allows_keyword_arguments() {
+                function
+                    .ensure_no_keyword_arguments()
+                    .map_err(E::invalid_arguments)?;
+            }
+            let arity = function.args.len() + function.keyword_args.len();
+            let Some((id, alias)) = overloads.find_by_arity(arity) else {
+                let arities = overloads.arities().collect_vec();
+                let min = *arities.first().unwrap();
+                let max = *arities.last().unwrap();
+                let err = if max - min + 1 == arities.len() {
                     function.invalid_arguments_count(min, Some(max))
                 } else {
-                    function.invalid_arguments_count_with_arities(overloads.arities())
+                    function.invalid_arguments_count_with_arities(arities)
                 };
                 return Err(E::invalid_arguments(err));
             };
+            let mut extracted = alias
+                .bind_arguments(*function)
+                .map_err(E::invalid_arguments)?;
             // Resolve arguments in the current scope, and pass them in to the alias
-            // expansion scope.
-            let args = fold_expression_nodes(self, function.args)?;
-            let locals = params.iter().map(|s| s.as_str()).zip(args).collect();
-            self.expand_defn(id, defn, locals, span)
+            // expansion scope. Default values are resolved in a fresh scope, so
+            // they can't refer to the other parameters.
+            let mut locals = HashMap::new();
+            for (i, name) in alias.params.iter().enumerate() {
+                let arg = match extracted[i].take() {
+                    Some(arg) => self.fold_expression(arg)?,
+                    None => {
+                        let defn = alias.default_value(i).unwrap();
+                        self.expand_default_value(id, defn, span)?
+                    }
+                };
+                locals.insert(name.as_str(), arg);
+            }
+            self.expand_defn(id, &alias.defn, locals, span)
         } else {
             let function = Box::new(fold_function_call_args(self, *function)?);
             Ok(T::function_call(function))
@@ -51,7 +51,7 @@ pub enum FilePatternParseError {
 }
 
 /// Basic pattern to match `RepoPath`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum FilePattern {
     /// Matches file (or exact) path.
     FilePath(RepoPathBuf),
@@ -182,7 +182,7 @@ fn split_glob_path(input: &str) -> (&str, &str) {
 }
 
 /// AST-level representation of the fileset expression.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum FilesetExpression {
     /// Matches nothing.
     None,
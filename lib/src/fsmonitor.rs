@@ -20,12 +20,46 @@
 
 #![warn(missing_docs)]
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use config::{Config, ConfigError};
+use itertools::Itertools as _;
 
 use crate::settings::ConfigResultExt;
 
+/// Opaque clock value which is saved in the working-copy state between
+/// filesystem monitor queries.
+pub type FsmonitorClock = crate::protos::working_copy::WatchmanClock;
+
+/// Error from a filesystem monitor query.
+pub type FsmonitorError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Interface to a filesystem monitor, which can report the files that may
+/// have changed in the working copy since the previous query.
+pub trait Fsmonitor: Debug + Send + Sync {
+    /// Queries for files changed since the `previous_clock`.
+    ///
+    /// Returns the clock to be saved for the next query, and the list of
+    /// changed paths relative to the `working_copy_path`. If the list is
+    /// `None`, then the caller must crawl the entire working copy.
+    fn query_changed_files(
+        &self,
+        working_copy_path: &Path,
+        previous_clock: Option<FsmonitorClock>,
+    ) -> Result<(Option<FsmonitorClock>, Option<Vec<PathBuf>>), FsmonitorError>;
+}
+
+/// Function to create a custom filesystem monitor from the `config`.
+pub type FsmonitorFactory =
+    Box<dyn Fn(&Config) -> Result<Arc<dyn Fsmonitor>, ConfigError> + Send + Sync>;
+
+/// Custom filesystem monitors keyed by the name to be selected by
+/// `core.fsmonitor`.
+pub type FsmonitorFactories = HashMap<String, FsmonitorFactory>;
+
 /// Config for Watchman filesystem monitor (<https://facebook.github.io/watchman/>).
 #[derive(Default, Eq, PartialEq, Clone, Debug)]
 pub struct WatchmanConfig {
@@ -34,11 +68,15 @@ pub struct WatchmanConfig {
 }
 
 /// The recognized kinds of filesystem monitors.
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum FsmonitorSettings {
     /// The Watchman filesystem monitor (<https://facebook.github.io/watchman/>).
     Watchman(WatchmanConfig),
 
+    /// Filesystem monitor provided by the caller, or created by the
+    /// [`FsmonitorFactory`] selected by `core.fsmonitor`.
+    Custom(Arc<dyn Fsmonitor>),
+
     /// Only used in tests.
     Test {
         /// The set of changed files to pretend that the filesystem monitor is
@@ -54,9 +92,33 @@ pub enum FsmonitorSettings {
     None,
 }
 
+// Custom monitors can't be compared by value, so they are compared by identity.
+impl PartialEq for FsmonitorSettings {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Watchman(a), Self::Watchman(b)) => a == b,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            (Self::Test { changed_files: a }, Self::Test { changed_files: b }) => a == b,
+            (Self::None, Self::None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for FsmonitorSettings {}
+
 impl FsmonitorSettings {
     /// Creates an `FsmonitorSettings` from a `config`.
     pub fn from_config(config: &Config) -> Result<FsmonitorSettings, ConfigError> {
+        Self::from_config_with_factories(config, &FsmonitorFactories::new())
+    }
+
+    /// Creates an `FsmonitorSettings` from a `config`. The custom monitors in
+    /// `factories` can be selected by name in addition to the built-in ones.
+    pub fn from_config_with_factories(
+        config: &Config,
+        factories: &FsmonitorFactories,
+    ) -> Result<FsmonitorSettings, ConfigError> {
         match config.get_string("core.fsmonitor") {
             Ok(s) => match s.as_str() {
                 "watchman" => Ok(Self::Watchman(WatchmanConfig {
@@ -69,9 +131,17 @@ impl FsmonitorSettings {
                     "cannot use test fsmonitor in real repository".to_string(),
                 )),
                 "none" => Ok(Self::None),
-                other => Err(ConfigError::Message(format!(
-                    "unknown fsmonitor kind: {other}",
-                ))),
+                other => match factories.get(other) {
+                    Some(factory) => Ok(Self::Custom(factory(config)?)),
+                    None => Err(ConfigError::Message(format!(
+                        "unknown fsmonitor kind: {other} (registered kinds: {})",
+                        ["none", "watchman"]
+                            .into_iter()
+                            .chain(factories.keys().map(String::as_str))
+                            .sorted()
+                            .join(", ")
+                    ))),
+                },
             },
             Err(ConfigError::NotFound(_)) => Ok(Self::None),
             Err(err) => Err(err),
@@ -328,4 +398,100 @@ pub mod watchman {
             expr::Expr::Not(Box::new(expr::Expr::Any(excludes)))
         }
     }
+
+    /// [`super::Fsmonitor`] which connects to Watchman on each query.
+    #[derive(Clone, Debug)]
+    pub struct WatchmanFsmonitor {
+        config: super::WatchmanConfig,
+    }
+
+    impl WatchmanFsmonitor {
+        /// Creates a monitor which uses the given `config`.
+        pub fn new(config: super::WatchmanConfig) -> Self {
+            WatchmanFsmonitor { config }
+        }
+    }
+
+    impl super::Fsmonitor for WatchmanFsmonitor {
+        #[tokio::main(flavor = "current_thread")]
+        async fn query_changed_files(
+            &self,
+            working_copy_path: &Path,
+            previous_clock: Option<super::FsmonitorClock>,
+        ) -> Result<(Option<super::FsmonitorClock>, Option<Vec<PathBuf>>), super::FsmonitorError>
+        {
+            let fsmonitor = Fsmonitor::init(working_copy_path, &self.config).await?;
+            let (clock, changed_files) = fsmonitor
+                .query_changed_files(previous_clock.map(Clock::from))
+                .await?;
+            Ok((Some(clock.into()), changed_files))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct NullFsmonitor;
+
+    impl Fsmonitor for NullFsmonitor {
+        fn query_changed_files(
+            &self,
+            _working_copy_path: &Path,
+            _previous_clock: Option<FsmonitorClock>,
+        ) -> Result<(Option<FsmonitorClock>, Option<Vec<PathBuf>>), FsmonitorError> {
+            Ok((None, None))
+        }
+    }
+
+    fn config_with_fsmonitor(name: &str) -> Config {
+        Config::builder()
+            .set_override("core.fsmonitor", name)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_from_config_with_factories() {
+        let fsmonitor: Arc<dyn Fsmonitor> = Arc::new(NullFsmonitor);
+        let mut factories = FsmonitorFactories::new();
+        factories.insert("null".to_owned(), {
+            let fsmonitor = fsmonitor.clone();
+            Box::new(move |_config| Ok(fsmonitor.clone()))
+        });
+
+        let settings = FsmonitorSettings::from_config_with_factories(
+            &config_with_fsmonitor("null"),
+            &factories,
+        )
+        .unwrap();
+        assert_eq!(settings, FsmonitorSettings::Custom(fsmonitor));
+
+        // Built-in monitors take precedence
+        let settings = FsmonitorSettings::from_config_with_factories(
+            &config_with_fsmonitor("none"),
+            &factories,
+        )
+        .unwrap();
+        assert_eq!(settings, FsmonitorSettings::None);
+
+        // Unknown names are reported with the registered ones
+        let err = FsmonitorSettings::from_config_with_factories(
+            &config_with_fsmonitor("bad"),
+            &factories,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown fsmonitor kind: bad (registered kinds: none, null, watchman)"
+        );
+        let err = FsmonitorSettings::from_config(&config_with_fsmonitor("null")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown fsmonitor kind: null (registered kinds: none, watchman)"
+        );
+    }
 }
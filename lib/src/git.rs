@@ -209,6 +209,9 @@ pub struct GitImportStats {
     /// Remote `(ref_name, (old_remote_ref, new_target))`s to be merged in to
     /// the local refs.
     pub changed_remote_refs: BTreeMap<RefName, (RemoteRef, RefTarget)>,
+    /// Refs which weren't imported because their commits couldn't be read.
+    /// These refs are left unchanged, so the next import will retry them.
+    pub skipped_refs: Vec<RefName>,
 }
 
 #[derive(Debug)]
@@ -239,14 +242,36 @@ pub fn import_some_refs(
     mut_repo: &mut MutableRepo,
     git_settings: &GitSettings,
     git_ref_filter: impl Fn(&RefName) -> bool,
+) -> Result<GitImportStats, GitImportError> {
+    import_some_refs_impl(mut_repo, git_settings, git_ref_filter, false)
+}
+
+/// Reflect changes made in the underlying Git repo in the Jujutsu repo, but
+/// skip refs pointing to commits which can't be read.
+///
+/// This is useful if the Git repo is partially corrupted, or some objects
+/// aren't available locally. The skipped refs are reported in
+/// `GitImportStats::skipped_refs`.
+pub fn import_refs_skipping_missing(
+    mut_repo: &mut MutableRepo,
+    git_settings: &GitSettings,
+) -> Result<GitImportStats, GitImportError> {
+    import_some_refs_impl(mut_repo, git_settings, |_| true, true)
+}
+
+fn import_some_refs_impl(
+    mut_repo: &mut MutableRepo,
+    git_settings: &GitSettings,
+    git_ref_filter: impl Fn(&RefName) -> bool,
+    skip_missing: bool,
 ) -> Result<GitImportStats, GitImportError> {
     let store = mut_repo.store();
     let git_backend = get_git_backend(store).ok_or(GitImportError::UnexpectedBackend)?;
     let git_repo = git_backend.git_repo();
 
     let RefsToImport {
-        changed_git_refs,
-        changed_remote_refs,
+        mut changed_git_refs,
+        mut changed_remote_refs,
     } = diff_refs_to_import(mut_repo.view(), &git_repo, git_ref_filter)?;
 
     // Bulk-import all reachable Git commits to the backend to reduce overhead
@@ -264,6 +289,7 @@ pub fn import_some_refs(
 
     // Import new remote heads
     let mut head_commits = Vec::new();
+    let mut skipped_refs = Vec::new();
     let get_commit = |id| {
         // If bulk-import failed, try again to find bad head or ref.
         if !heads_imported && !index.has_id(id) {
@@ -272,14 +298,25 @@ pub fn import_some_refs(
         store.get_commit(id)
     };
     for (ref_name, (_, new_target)) in &changed_remote_refs {
-        for id in new_target.added_ids() {
-            let commit = get_commit(id).map_err(|err| GitImportError::MissingRefAncestor {
-                ref_name: ref_name.to_string(),
-                err,
-            })?;
-            head_commits.push(commit);
+        let commits: Result<Vec<_>, _> = new_target.added_ids().map(get_commit).collect();
+        match commits {
+            Ok(commits) => head_commits.extend(commits),
+            Err(_) if skip_missing => skipped_refs.push(ref_name.clone()),
+            Err(err) => {
+                return Err(GitImportError::MissingRefAncestor {
+                    ref_name: ref_name.to_string(),
+                    err,
+                });
+            }
         }
     }
+    if !skipped_refs.is_empty() {
+        // Leave the skipped refs unchanged so they'll be imported next time.
+        changed_git_refs.retain(|(full_name, _)| {
+            parse_git_ref(full_name).map_or(true, |ref_name| !skipped_refs.contains(&ref_name))
+        });
+        changed_remote_refs.retain(|ref_name, _| !skipped_refs.contains(ref_name));
+    }
     // It's unlikely the imported commits were missing, but I/O-related error
     // can still occur.
     mut_repo
@@ -333,6 +370,7 @@ pub fn import_some_refs(
     let stats = GitImportStats {
         abandoned_commits,
         changed_remote_refs,
+        skipped_refs,
     };
     Ok(stats)
 }
@@ -648,6 +686,24 @@ pub fn export_refs(mut_repo: &mut MutableRepo) -> Result<Vec<FailedRefExport>, G
 pub fn export_some_refs(
     mut_repo: &mut MutableRepo,
     git_ref_filter: impl Fn(&RefName) -> bool,
+) -> Result<Vec<FailedRefExport>, GitExportError> {
+    export_refs_with(mut_repo, git_ref_filter, false)
+}
+
+/// Exports the branches matching `git_ref_filter` like `export_some_refs()`,
+/// and deletes Git refs of the other local branches which were previously
+/// exported. The local branches in the Jujutsu repo are kept intact.
+pub fn export_some_refs_and_prune_others(
+    mut_repo: &mut MutableRepo,
+    git_ref_filter: impl Fn(&RefName) -> bool,
+) -> Result<Vec<FailedRefExport>, GitExportError> {
+    export_refs_with(mut_repo, git_ref_filter, true)
+}
+
+fn export_refs_with(
+    mut_repo: &mut MutableRepo,
+    git_ref_filter: impl Fn(&RefName) -> bool,
+    prune_unmatched_local_branches: bool,
 ) -> Result<Vec<FailedRefExport>, GitExportError> {
     let git_repo = get_git_repo(mut_repo.store()).ok_or(GitExportError::UnexpectedBackend)?;
 
@@ -659,6 +715,7 @@ pub fn export_some_refs(
         mut_repo.view(),
         mut_repo.store().root_commit_id(),
         &git_ref_filter,
+        prune_unmatched_local_branches,
     );
 
     // TODO: Also check other worktrees' HEAD.
@@ -699,6 +756,16 @@ pub fn export_some_refs(
         } else {
             let new_target = RefTarget::absent();
             mut_repo.set_git_ref_target(&git_ref_name, new_target);
+            if let RefName::LocalBranch(branch) = &parsed_ref_name {
+                if !git_ref_filter(&parsed_ref_name) {
+                    // Pruned branch is no longer known to the Git repo.
+                    mut_repo.set_remote_branch(
+                        branch,
+                        REMOTE_NAME_FOR_LOCAL_GIT_REPO,
+                        RemoteRef::absent(),
+                    );
+                }
+            }
         }
     }
     for (parsed_ref_name, (old_oid, new_oid)) in branches_to_update {
@@ -760,6 +827,7 @@ fn diff_refs_to_export(
     view: &View,
     root_commit_id: &CommitId,
     git_ref_filter: impl Fn(&RefName) -> bool,
+    prune_unmatched_local_branches: bool,
 ) -> RefsToExport {
     // Local targets will be copied to the "git" remote if successfully exported. So
     // the local branches are considered to be the new "git" remote branches.
@@ -795,7 +863,12 @@ fn diff_refs_to_export(
                 RefName::LocalBranch(..) | RefName::RemoteBranch { .. }
             )
         })
-        .filter(|(ref_name, _)| git_ref_filter(ref_name));
+        .filter(|(ref_name, _)| {
+            // Previously-exported local branches which are now excluded will be
+            // deleted from the Git repo if pruning is requested.
+            git_ref_filter(ref_name)
+                || (prune_unmatched_local_branches && matches!(ref_name, RefName::LocalBranch(..)))
+        });
     for (ref_name, target) in known_git_refs {
         all_branch_targets
             .entry(ref_name)
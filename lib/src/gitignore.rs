@@ -14,7 +14,7 @@
 
 #![allow(missing_docs)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::{fs, io, iter};
 
@@ -36,6 +36,18 @@ pub enum GitIgnoreError {
     Underlying(#[from] ignore::Error),
 }
 
+/// The last matching rule which decides whether a path is ignored.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GitIgnoreRule {
+    /// File the rule was read from, or `None` if the rule wasn't read from a
+    /// file.
+    pub source: Option<PathBuf>,
+    /// The pattern as written in the source, including any leading `!`.
+    pub pattern: String,
+    /// Whether the path is ignored by this rule, i.e. the rule isn't negated.
+    pub is_ignored: bool,
+}
+
 /// Models the effective contents of multiple .gitignore files.
 #[derive(Debug)]
 pub struct GitIgnoreFile {
@@ -59,6 +71,15 @@ impl GitIgnoreFile {
         self: &Arc<GitIgnoreFile>,
         prefix: &str,
         input: &[u8],
+    ) -> Result<Arc<GitIgnoreFile>, GitIgnoreError> {
+        self.chain_with_source(prefix, input, None)
+    }
+
+    fn chain_with_source(
+        self: &Arc<GitIgnoreFile>,
+        prefix: &str,
+        input: &[u8],
+        source: Option<&Path>,
     ) -> Result<Arc<GitIgnoreFile>, GitIgnoreError> {
         let mut builder = gitignore::GitignoreBuilder::new(prefix);
         for (i, input_line) in input.split(|b| *b == b'\n').enumerate() {
@@ -69,9 +90,8 @@ impl GitIgnoreFile {
                     line: String::from_utf8_lossy(input_line).to_string(),
                     source: err,
                 })?;
-            // FIXME: do we need to provide the `from` argument? Is it for providing
-            // diagnostics or correctness?
-            builder.add_line(None, line)?;
+            // The `from` argument is only used for diagnostics.
+            builder.add_line(source.map(Path::to_path_buf), line)?;
         }
         let matcher = builder.build()?;
         let parent = if self.matcher.is_empty() {
@@ -96,25 +116,43 @@ impl GitIgnoreFile {
                 path: file.clone(),
                 source: err,
             })?;
-            self.chain(prefix, &buf)
+            self.chain_with_source(prefix, &buf, Some(&file))
         } else {
             Ok(self.clone())
         }
     }
 
+    fn matched_glob(&self, path: &str, is_dir: bool) -> Option<&gitignore::Glob> {
+        iter::successors(Some(self), |file| file.parent.as_deref()).find_map(|file| {
+            // TODO: the documentation warns that
+            // `matched_path_or_any_parents` is slower than `matched`;
+            // ideally, we would switch to that.
+            match file.matcher.matched_path_or_any_parents(path, is_dir) {
+                ignore::Match::None => None,
+                ignore::Match::Ignore(glob) | ignore::Match::Whitelist(glob) => Some(glob),
+            }
+        })
+    }
+
     fn matches_helper(&self, path: &str, is_dir: bool) -> bool {
-        iter::successors(Some(self), |file| file.parent.as_deref())
-            .find_map(|file| {
-                // TODO: the documentation warns that
-                // `matched_path_or_any_parents` is slower than `matched`;
-                // ideally, we would switch to that.
-                match file.matcher.matched_path_or_any_parents(path, is_dir) {
-                    ignore::Match::None => None,
-                    ignore::Match::Ignore(_) => Some(true),
-                    ignore::Match::Whitelist(_) => Some(false),
-                }
-            })
-            .unwrap_or_default()
+        self.matched_glob(path, is_dir)
+            .is_some_and(|glob| !glob.is_whitelist())
+    }
+
+    /// Returns the rule which decides whether the specified path is ignored,
+    /// or `None` if no rule matches. Like [`Self::matches()`], a path ending
+    /// with slash is considered a directory.
+    pub fn matched_rule(&self, path: &str) -> Option<GitIgnoreRule> {
+        let (path, is_dir) = match path.strip_suffix('/') {
+            Some(path) => (path, true),
+            None => (path, false),
+        };
+        let glob = self.matched_glob(path, is_dir)?;
+        Some(GitIgnoreRule {
+            source: glob.from().map(Path::to_path_buf),
+            pattern: glob.original().to_owned(),
+            is_ignored: !glob.is_whitelist(),
+        })
     }
 
     /// Returns whether specified path (not just file!) should be ignored. This
@@ -144,6 +182,25 @@ mod tests {
         file.matches(path)
     }
 
+    #[test]
+    fn test_gitignore_matched_rule() {
+        let file = GitIgnoreFile::empty()
+            .chain("", b"*.log\n")
+            .unwrap()
+            .chain("dir/", b"!keep.log\nbuild/\n")
+            .unwrap();
+        let rule = |path| {
+            file.matched_rule(path)
+                .map(|rule| (rule.pattern, rule.is_ignored))
+        };
+        assert_eq!(rule("foo.log"), Some(("*.log".to_owned(), true)));
+        assert_eq!(rule("dir/foo.log"), Some(("*.log".to_owned(), true)));
+        assert_eq!(rule("dir/keep.log"), Some(("!keep.log".to_owned(), false)));
+        assert_eq!(rule("dir/build/"), Some(("build/".to_owned(), true)));
+        assert_eq!(rule("dir/build/foo"), Some(("build/".to_owned(), true)));
+        assert_eq!(rule("foo"), None);
+    }
+
     #[test]
     fn test_gitignore_empty_file() {
         let file = GitIgnoreFile::empty();
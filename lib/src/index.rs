@@ -89,6 +89,14 @@ pub trait Index: Send + Sync {
     /// Returns true if `commit_id` is present in the index.
     fn has_id(&self, commit_id: &CommitId) -> bool;
 
+    /// Returns the generation number of the `commit_id` commit, which is the
+    /// length of the longest path to the root commit. Returns `None` if the
+    /// commit isn't present in the index, or if the index doesn't keep track
+    /// of generation numbers.
+    fn generation_number(&self, _commit_id: &CommitId) -> Option<u32> {
+        None
+    }
+
     /// Returns true if `ancestor_id` commit is an ancestor of the
     /// `descendant_id` commit, or if `ancestor_id` equals `descendant_id`.
     fn is_ancestor(&self, ancestor_id: &CommitId, descendant_id: &CommitId) -> bool;
@@ -47,9 +47,9 @@ use crate::backend::{
 use crate::commit::Commit;
 use crate::conflicts::{self, materialize_tree_value, MaterializedTreeValue};
 use crate::file_util::{check_symlink_support, try_symlink};
-use crate::fsmonitor::FsmonitorSettings;
 #[cfg(feature = "watchman")]
 use crate::fsmonitor::{watchman, WatchmanConfig};
+use crate::fsmonitor::{Fsmonitor, FsmonitorSettings};
 use crate::gitignore::GitIgnoreFile;
 use crate::lock::FileLock;
 use crate::matchers::{
@@ -64,8 +64,9 @@ use crate::settings::HumanByteSize;
 use crate::store::Store;
 use crate::tree::Tree;
 use crate::working_copy::{
-    CheckoutError, CheckoutStats, LockedWorkingCopy, ResetError, SnapshotError, SnapshotOptions,
-    SnapshotProgress, WorkingCopy, WorkingCopyFactory, WorkingCopyStateError,
+    CheckoutError, CheckoutStats, LockedWorkingCopy, ResetError, SkippedLargeFile, SnapshotError,
+    SnapshotOptions, SnapshotProgress, SnapshotStats, WorkingCopy, WorkingCopyFactory,
+    WorkingCopyStateError,
 };
 
 #[cfg(unix)]
@@ -443,6 +444,27 @@ fn mtime_from_metadata(metadata: &Metadata) -> MillisSinceEpoch {
     )
 }
 
+/// Returns the metadata of the file the symlink at `disk_path` points to. The
+/// given `metadata` is returned as-is if it isn't of a symlink, or if the
+/// symlink points to a directory.
+fn follow_symlink_metadata(
+    disk_path: &Path,
+    metadata: Metadata,
+) -> Result<Metadata, SnapshotError> {
+    if !metadata.is_symlink() {
+        return Ok(metadata);
+    }
+    // The OS detects symlink loops, so this doesn't recurse infinitely.
+    match disk_path.metadata() {
+        Ok(target_metadata) if target_metadata.is_dir() => Ok(metadata),
+        Ok(target_metadata) => Ok(target_metadata),
+        Err(err) => Err(SnapshotError::Other {
+            message: format!("Failed to follow symlink {}", disk_path.display()),
+            err: err.into(),
+        }),
+    }
+}
+
 fn file_state(metadata: &Metadata) -> Option<FileState> {
     let metadata_file_type = metadata.file_type();
     let file_type = if metadata_file_type.is_dir() {
@@ -472,11 +494,22 @@ fn file_state(metadata: &Metadata) -> Option<FileState> {
     })
 }
 
+#[derive(Default)]
 struct FsmonitorMatcher {
     matcher: Option<Box<dyn Matcher>>,
     watchman_clock: Option<crate::protos::working_copy::WatchmanClock>,
 }
 
+fn make_changed_files_matcher(changed_files: Vec<PathBuf>) -> Box<dyn Matcher> {
+    let repo_paths = trace_span!("processing fsmonitor paths").in_scope(|| {
+        changed_files
+            .into_iter()
+            .filter_map(|path| RepoPathBuf::from_relative_path(path).ok())
+            .collect_vec()
+    });
+    Box::new(FilesMatcher::new(repo_paths))
+}
+
 struct DirectoryToVisit<'a> {
     dir: RepoPathBuf,
     disk_dir: PathBuf,
@@ -757,18 +790,23 @@ impl TreeState {
 
     /// Look for changes to the working copy. If there are any changes, create
     /// a new tree from it and return it, and also update the dirstate on disk.
+    /// Returns whether the state is dirty, and the stats of the snapshot.
     #[instrument(skip_all)]
-    pub fn snapshot(&mut self, options: SnapshotOptions) -> Result<bool, SnapshotError> {
+    pub fn snapshot(
+        &mut self,
+        options: SnapshotOptions,
+    ) -> Result<(bool, SnapshotStats), SnapshotError> {
         let SnapshotOptions {
             base_ignores,
             fsmonitor_settings,
             progress,
             max_new_file_size,
+            follow_symlinks,
         } = options;
 
         let sparse_matcher = self.sparse_matcher();
 
-        let fsmonitor_clock_needs_save = fsmonitor_settings != FsmonitorSettings::None;
+        let fsmonitor_clock_needs_save = !matches!(fsmonitor_settings, FsmonitorSettings::None);
         let mut is_dirty = fsmonitor_clock_needs_save;
         let FsmonitorMatcher {
             matcher: fsmonitor_matcher,
@@ -783,12 +821,13 @@ impl TreeState {
         if matcher.visit(RepoPath::root()).is_nothing() {
             // No need to iterate file states to build empty deleted_files.
             self.watchman_clock = watchman_clock;
-            return Ok(is_dirty);
+            return Ok((is_dirty, SnapshotStats::default()));
         }
 
         let (tree_entries_tx, tree_entries_rx) = channel();
         let (file_states_tx, file_states_rx) = channel();
         let (present_files_tx, present_files_rx) = channel();
+        let (skipped_files_tx, skipped_files_rx) = channel();
 
         trace_span!("traverse filesystem").in_scope(|| -> Result<(), SnapshotError> {
             let current_tree = self.current_tree()?;
@@ -804,9 +843,11 @@ impl TreeState {
                 tree_entries_tx,
                 file_states_tx,
                 present_files_tx,
+                skipped_files_tx,
                 directory_to_visit,
                 progress,
                 max_new_file_size,
+                follow_symlinks,
             )
         })?;
 
@@ -824,12 +865,21 @@ impl TreeState {
                     .map(|(path, _state)| path.to_owned())
                     .collect()
             });
+        let mut conflicted_paths = vec![];
         trace_span!("process tree entries").in_scope(|| -> Result<(), SnapshotError> {
             while let Ok((path, tree_values)) = tree_entries_rx.recv() {
+                if !tree_values.is_resolved() {
+                    conflicted_paths.push(path.clone());
+                }
                 tree_builder.set_or_remove(path, tree_values);
             }
             Ok(())
         })?;
+        conflicted_paths.sort_unstable();
+        let skipped_large_files = skipped_files_rx
+            .iter()
+            .sorted_unstable_by(|file1: &SkippedLargeFile, file2| file1.path.cmp(&file2.path))
+            .collect_vec();
         trace_span!("process present files").in_scope(|| {
             while let Ok(path) = present_files_rx.recv() {
                 deleted_files.remove(&path);
@@ -866,7 +916,13 @@ impl TreeState {
             assert_eq!(state_paths, tree_paths);
         }
         self.watchman_clock = watchman_clock;
-        Ok(is_dirty)
+        Ok((
+            is_dirty,
+            SnapshotStats {
+                conflicted_paths,
+                skipped_large_files,
+            },
+        ))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -877,9 +933,11 @@ impl TreeState {
         tree_entries_tx: Sender<(RepoPathBuf, MergedTreeValue)>,
         file_states_tx: Sender<(RepoPathBuf, FileState)>,
         present_files_tx: Sender<RepoPathBuf>,
+        skipped_files_tx: Sender<SkippedLargeFile>,
         directory_to_visit: DirectoryToVisit,
         progress: Option<&SnapshotProgress>,
         max_new_file_size: u64,
+        follow_symlinks: bool,
     ) -> Result<(), SnapshotError> {
         let DirectoryToVisit {
             dir,
@@ -904,8 +962,9 @@ impl TreeState {
                 tree_entries_tx.clone(),
                 file_states_tx.clone(),
                 present_files_tx.clone(),
+                skipped_files_tx.clone(),
             ),
-            |(tree_entries_tx, file_states_tx, present_files_tx),
+            |(tree_entries_tx, file_states_tx, present_files_tx, skipped_files_tx),
              entry|
              -> Result<(), SnapshotError> {
                 let file_type = entry.file_type().unwrap();
@@ -952,6 +1011,11 @@ impl TreeState {
                                     });
                                 }
                             };
+                            let metadata = if follow_symlinks {
+                                follow_symlink_metadata(&disk_path, metadata)?
+                            } else {
+                                metadata
+                            };
                             if let Some(new_file_state) = file_state(&metadata) {
                                 present_files_tx.send(tracked_path.to_owned()).ok();
                                 let update = self.get_updated_tree_value(
@@ -986,9 +1050,11 @@ impl TreeState {
                             tree_entries_tx.clone(),
                             file_states_tx.clone(),
                             present_files_tx.clone(),
+                            skipped_files_tx.clone(),
                             directory_to_visit,
                             progress,
                             max_new_file_size,
+                            follow_symlinks,
                         )?;
                     }
                 } else if matcher.matches(&path) {
@@ -1006,13 +1072,23 @@ impl TreeState {
                             message: format!("Failed to stat file {}", entry.path().display()),
                             err: err.into(),
                         })?;
+                        let metadata = if follow_symlinks {
+                            follow_symlink_metadata(&entry.path(), metadata)?
+                        } else {
+                            metadata
+                        };
                         if maybe_current_file_state.is_none() && metadata.len() > max_new_file_size
                         {
-                            return Err(SnapshotError::NewFileTooLarge {
-                                path: entry.path().clone(),
-                                size: HumanByteSize(metadata.len()),
-                                max_size: HumanByteSize(max_new_file_size),
-                            });
+                            // Leave it untracked, but let the caller know
+                            // about it.
+                            skipped_files_tx
+                                .send(SkippedLargeFile {
+                                    path,
+                                    size: HumanByteSize(metadata.len()),
+                                    max_size: HumanByteSize(max_new_file_size),
+                                })
+                                .ok();
+                            return Ok(());
                         }
                         if let Some(new_file_state) = file_state(&metadata) {
                             present_files_tx.send(path.clone()).ok();
@@ -1043,17 +1119,18 @@ impl TreeState {
         &self,
         fsmonitor_settings: FsmonitorSettings,
     ) -> Result<FsmonitorMatcher, SnapshotError> {
-        let (watchman_clock, changed_files) = match fsmonitor_settings {
-            FsmonitorSettings::None => (None, None),
-            FsmonitorSettings::Test { changed_files } => (None, Some(changed_files)),
+        let fsmonitor: Arc<dyn Fsmonitor> = match fsmonitor_settings {
+            FsmonitorSettings::None => return Ok(FsmonitorMatcher::default()),
+            FsmonitorSettings::Test { changed_files } => {
+                return Ok(FsmonitorMatcher {
+                    matcher: Some(make_changed_files_matcher(changed_files)),
+                    watchman_clock: None,
+                });
+            }
             #[cfg(feature = "watchman")]
-            FsmonitorSettings::Watchman(config) => match self.query_watchman(&config) {
-                Ok((watchman_clock, changed_files)) => (Some(watchman_clock.into()), changed_files),
-                Err(err) => {
-                    tracing::warn!(?err, "Failed to query filesystem monitor");
-                    (None, None)
-                }
-            },
+            FsmonitorSettings::Watchman(config) => {
+                Arc::new(watchman::WatchmanFsmonitor::new(config))
+            }
             #[cfg(not(feature = "watchman"))]
             FsmonitorSettings::Watchman(_) => {
                 return Err(SnapshotError::Other {
@@ -1063,22 +1140,19 @@ impl TreeState {
                         .into(),
                 });
             }
+            FsmonitorSettings::Custom(fsmonitor) => fsmonitor,
         };
-        let matcher: Option<Box<dyn Matcher>> = match changed_files {
-            None => None,
-            Some(changed_files) => {
-                let repo_paths = trace_span!("processing fsmonitor paths").in_scope(|| {
-                    changed_files
-                        .into_iter()
-                        .filter_map(|path| RepoPathBuf::from_relative_path(path).ok())
-                        .collect_vec()
-                });
-
-                Some(Box::new(FilesMatcher::new(repo_paths)))
+        let (watchman_clock, changed_files) = match fsmonitor
+            .query_changed_files(&self.working_copy_path, self.watchman_clock.clone())
+        {
+            Ok(result) => result,
+            Err(err) => {
+                tracing::warn!(?err, "Failed to query filesystem monitor");
+                (None, None)
             }
         };
         Ok(FsmonitorMatcher {
-            matcher,
+            matcher: changed_files.map(make_changed_files_matcher),
             watchman_clock,
         })
     }
@@ -1713,6 +1787,16 @@ impl LocalWorkingCopy {
             })
     }
 
+    /// Returns the most recent clock value returned by Watchman, if any.
+    #[cfg(feature = "watchman")]
+    pub fn watchman_clock(&self) -> Result<Option<watchman::Clock>, WorkingCopyStateError> {
+        Ok(self
+            .tree_state()?
+            .watchman_clock
+            .clone()
+            .map(watchman::Clock::from))
+    }
+
     #[cfg(feature = "watchman")]
     pub fn is_watchman_trigger_registered(
         &self,
@@ -1789,7 +1873,10 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
         &self.old_tree_id
     }
 
-    fn snapshot(&mut self, options: SnapshotOptions) -> Result<MergedTreeId, SnapshotError> {
+    fn snapshot(
+        &mut self,
+        options: SnapshotOptions,
+    ) -> Result<(MergedTreeId, SnapshotStats), SnapshotError> {
         let tree_state = self
             .wc
             .tree_state_mut()
@@ -1797,8 +1884,9 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
                 message: "Failed to read the working copy state".to_string(),
                 err: err.into(),
             })?;
-        self.tree_state_dirty |= tree_state.snapshot(options)?;
-        Ok(tree_state.current_tree_id().clone())
+        let (is_dirty, stats) = tree_state.snapshot(options)?;
+        self.tree_state_dirty |= is_dirty;
+        Ok((tree_state.current_tree_id().clone(), stats))
     }
 
     fn check_out(&mut self, commit: &Commit) -> Result<CheckoutStats, CheckoutError> {
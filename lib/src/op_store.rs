@@ -446,6 +446,27 @@ pub trait OpStore: Send + Sync + Debug {
     /// concurrently by another process.
     // TODO: return stats?
     fn gc(&self, head_ids: &[OperationId], keep_newer: SystemTime) -> OpStoreResult<()>;
+
+    /// Prunes unreachable operations and views like [`OpStore::gc()`], but
+    /// also preserves operations that ended after `keep_ops_newer` along with
+    /// their ancestors and views, regardless of reachability from the
+    /// `head_ids`.
+    ///
+    /// Returns the heads of the preserved operations which aren't ancestors of
+    /// the `head_ids`. The caller should keep the commits referenced by them.
+    ///
+    /// The default implementation doesn't support this and returns an error.
+    fn gc_keeping_recent_ops(
+        &self,
+        head_ids: &[OperationId],
+        keep_newer: SystemTime,
+        keep_ops_newer: SystemTime,
+    ) -> OpStoreResult<Vec<OperationId>> {
+        let _ = (head_ids, keep_newer, keep_ops_newer);
+        Err(OpStoreError::Other(
+            "Preserving recent operations isn't supported by this operation store".into(),
+        ))
+    }
 }
 
 #[cfg(test)]
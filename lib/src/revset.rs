@@ -17,10 +17,11 @@
 use std::any::Any;
 use std::collections::{hash_map, HashMap};
 use std::convert::Infallible;
-use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::{fmt, mem};
 
 use itertools::Itertools;
 use once_cell::sync::Lazy;
@@ -139,6 +140,40 @@ pub enum RevsetFilterPredicate {
     Extension(Rc<dyn RevsetFilterExtension>),
 }
 
+// Extension predicates can't be compared by value, so they are compared by
+// identity.
+impl PartialEq for RevsetFilterPredicate {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::ParentCount(a), Self::ParentCount(b)) => a == b,
+            (Self::Description(a), Self::Description(b)) => a == b,
+            (Self::Author(a), Self::Author(b)) => a == b,
+            (Self::Committer(a), Self::Committer(b)) => a == b,
+            (Self::File(a), Self::File(b)) => a == b,
+            (Self::HasConflict, Self::HasConflict) => true,
+            (Self::Extension(a), Self::Extension(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for RevsetFilterPredicate {}
+
+impl Hash for RevsetFilterPredicate {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Self::ParentCount(range) => range.hash(state),
+            Self::Description(pattern) | Self::Author(pattern) | Self::Committer(pattern) => {
+                pattern.hash(state);
+            }
+            Self::File(expression) => expression.hash(state),
+            Self::HasConflict => {}
+            Self::Extension(extension) => Rc::as_ptr(extension).cast::<()>().hash(state),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum RevsetExpression {
     None,
@@ -172,6 +207,9 @@ pub enum RevsetExpression {
     },
     Heads(Rc<RevsetExpression>),
     Roots(Rc<RevsetExpression>),
+    // Merge commits which brought some of "roots" into their first-parent
+    // history
+    Mergers(Rc<RevsetExpression>),
     Latest {
         candidates: Rc<RevsetExpression>,
         count: usize,
@@ -286,6 +324,11 @@ impl RevsetExpression {
         Rc::new(RevsetExpression::Roots(self.clone()))
     }
 
+    /// Merge commits which brought `self` into their first-parent history.
+    pub fn mergers(self: &Rc<RevsetExpression>) -> Rc<RevsetExpression> {
+        Rc::new(RevsetExpression::Mergers(self.clone()))
+    }
+
     /// Parents of `self`.
     pub fn parents(self: &Rc<RevsetExpression>) -> Rc<RevsetExpression> {
         self.ancestors_at(1)
@@ -466,7 +509,7 @@ impl RevsetExpression {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum ResolvedPredicateExpression {
     /// Pure filter predicate.
     Filter(RevsetFilterPredicate),
@@ -486,7 +529,7 @@ pub enum ResolvedPredicateExpression {
 /// properties.
 ///
 /// Use `RevsetExpression` API to build a query programmatically.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum ResolvedExpression {
     Commits(Vec<CommitId>),
     Ancestors {
@@ -512,6 +555,12 @@ pub enum ResolvedExpression {
     },
     Heads(Box<ResolvedExpression>),
     Roots(Box<ResolvedExpression>),
+    /// Merge commits up to `heads` which have some of `roots` as ancestors of
+    /// the other parents, but not of the first parent.
+    Mergers {
+        roots: Box<ResolvedExpression>,
+        heads: Box<ResolvedExpression>,
+    },
     Latest {
         candidates: Box<ResolvedExpression>,
         count: usize,
@@ -665,6 +714,11 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         };
         Ok(candidates.latest(count))
     });
+    map.insert("mergers", |function, context| {
+        let [arg] = function.expect_exact_arguments()?;
+        let expression = lower_expression(arg, context)?;
+        Ok(expression.mergers())
+    });
     map.insert("merges", |function, _context| {
         function.expect_no_arguments()?;
         Ok(RevsetExpression::filter(
@@ -946,6 +1000,9 @@ fn try_transform_expression<E>(
             RevsetExpression::Roots(candidates) => {
                 transform_rec(candidates, pre, post)?.map(RevsetExpression::Roots)
             }
+            RevsetExpression::Mergers(roots) => {
+                transform_rec(roots, pre, post)?.map(RevsetExpression::Mergers)
+            }
             RevsetExpression::Latest { candidates, count } => transform_rec(candidates, pre, post)?
                 .map(|candidates| RevsetExpression::Latest {
                     candidates,
@@ -1746,6 +1803,10 @@ impl VisibilityResolutionContext<'_> {
             RevsetExpression::Roots(candidates) => {
                 ResolvedExpression::Roots(self.resolve(candidates).into())
             }
+            RevsetExpression::Mergers(roots) => ResolvedExpression::Mergers {
+                roots: self.resolve(roots).into(),
+                heads: self.resolve_visible_heads().into(),
+            },
             RevsetExpression::Latest { candidates, count } => ResolvedExpression::Latest {
                 candidates: self.resolve(candidates).into(),
                 count: *count,
@@ -1827,6 +1888,7 @@ impl VisibilityResolutionContext<'_> {
             | RevsetExpression::Reachable { .. }
             | RevsetExpression::Heads(_)
             | RevsetExpression::Roots(_)
+            | RevsetExpression::Mergers(_)
             | RevsetExpression::Latest { .. } => {
                 ResolvedPredicateExpression::Set(self.resolve(expression).into())
             }
@@ -262,6 +262,13 @@ impl UserSettings {
         }
     }
 
+    pub fn follow_symlinks(&self) -> Result<bool, config::ConfigError> {
+        self.config
+            .get_bool("snapshot.follow-symlinks")
+            .optional()
+            .map(|value| value.unwrap_or(false))
+    }
+
     // separate from sign_settings as those two are needed in pretty different
     // places
     pub fn signing_backend(&self) -> Option<String> {
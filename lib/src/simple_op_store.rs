@@ -92,6 +92,140 @@ impl SimpleOpStore {
     fn operation_path(&self, id: &OperationId) -> PathBuf {
         self.path.join("operations").join(id.hex())
     }
+
+    fn gc_impl(
+        &self,
+        head_ids: &[OperationId],
+        keep_newer: SystemTime,
+        keep_ops_newer: Option<SystemTime>,
+    ) -> OpStoreResult<Vec<OperationId>> {
+        let to_op_id = |entry: &fs::DirEntry| -> Option<OperationId> {
+            let name = entry.file_name().into_string().ok()?;
+            OperationId::try_from_hex(&name).ok()
+        };
+        let to_view_id = |entry: &fs::DirEntry| -> Option<ViewId> {
+            let name = entry.file_name().into_string().ok()?;
+            ViewId::try_from_hex(&name).ok()
+        };
+        let remove_file_if_not_new = |entry: &fs::DirEntry| -> Result<(), PathError> {
+            let path = entry.path();
+            // Check timestamp, but there's still TOCTOU problem if an existing
+            // file is renewed.
+            let metadata = entry.metadata().context(&path)?;
+            let mtime = metadata.modified().expect("unsupported platform?");
+            if mtime > keep_newer {
+                tracing::trace!(?path, "not removing");
+                Ok(())
+            } else {
+                tracing::trace!(?path, "removing");
+                fs::remove_file(&path).context(&path)
+            }
+        };
+
+        // Operations recorded after keep_ops_newer are kept as if they were
+        // heads, so their ancestors and views are preserved as well.
+        let orig_head_ids = head_ids;
+        let mut head_ids = head_ids.to_vec();
+        if let Some(keep_ops_newer) = keep_ops_newer {
+            let since_epoch = keep_ops_newer
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default();
+            let threshold = MillisSinceEpoch(since_epoch.as_millis().try_into().unwrap());
+            let collect_op_ids = || -> Result<Vec<OperationId>, PathError> {
+                let op_dir = self.path.join("operations");
+                let mut ids = vec![];
+                for entry in op_dir.read_dir().context(&op_dir)? {
+                    let entry = entry.context(&op_dir)?;
+                    ids.extend(to_op_id(&entry));
+                }
+                Ok(ids)
+            };
+            let op_ids = collect_op_ids().map_err(|err| OpStoreError::Other(err.into()))?;
+            for id in op_ids {
+                let data = self.read_operation(&id)?;
+                if data.metadata.end_time.timestamp >= threshold && !head_ids.contains(&id) {
+                    head_ids.push(id);
+                }
+            }
+        }
+
+        // Reachable objects are resolved without considering the keep_newer
+        // parameter. We could collect ancestors of the "new" operations here,
+        // but more files can be added anyway after that.
+        let read_op = |id: &OperationId| self.read_operation(id).map(|data| (id.clone(), data));
+        let reachable_ops: HashMap<OperationId, Operation> = dag_walk::dfs_ok(
+            head_ids.iter().map(read_op),
+            |(id, _)| id.clone(),
+            |(_, data)| data.parents.iter().map(read_op).collect_vec(),
+        )
+        .try_collect()?;
+        let reachable_views: HashSet<&ViewId> =
+            reachable_ops.values().map(|data| &data.view_id).collect();
+        tracing::info!(
+            reachable_op_count = reachable_ops.len(),
+            reachable_view_count = reachable_views.len(),
+            "collected reachable objects"
+        );
+
+        let prune_ops = || -> Result<(), PathError> {
+            let op_dir = self.path.join("operations");
+            for entry in op_dir.read_dir().context(&op_dir)? {
+                let entry = entry.context(&op_dir)?;
+                let Some(id) = to_op_id(&entry) else {
+                    tracing::trace!(?entry, "skipping invalid file name");
+                    continue;
+                };
+                if reachable_ops.contains_key(&id) {
+                    continue;
+                }
+                // If the operation was added after collecting reachable_views,
+                // its view mtime would also be renewed. So there's no need to
+                // update the reachable_views set to preserve the view.
+                remove_file_if_not_new(&entry)?;
+            }
+            Ok(())
+        };
+        prune_ops().map_err(|err| OpStoreError::Other(err.into()))?;
+
+        let prune_views = || -> Result<(), PathError> {
+            let view_dir = self.path.join("views");
+            for entry in view_dir.read_dir().context(&view_dir)? {
+                let entry = entry.context(&view_dir)?;
+                let Some(id) = to_view_id(&entry) else {
+                    tracing::trace!(?entry, "skipping invalid file name");
+                    continue;
+                };
+                if reachable_views.contains(&id) {
+                    continue;
+                }
+                remove_file_if_not_new(&entry)?;
+            }
+            Ok(())
+        };
+        prune_views().map_err(|err| OpStoreError::Other(err.into()))?;
+
+        if head_ids.len() == orig_head_ids.len() {
+            return Ok(vec![]);
+        }
+        // Recent operations that are ancestors of the other heads don't have
+        // to be reported.
+        let read_parents = |id: &OperationId| -> Vec<OpStoreResult<OperationId>> {
+            match self.read_operation(id) {
+                Ok(data) => data.parents.into_iter().map(Ok).collect(),
+                Err(err) => vec![Err(err)],
+            }
+        };
+        let new_head_ids = dag_walk::heads_ok(
+            head_ids.iter().cloned().map(Ok),
+            |id| id.clone(),
+            read_parents,
+        )?;
+        Ok(new_head_ids
+            .into_iter()
+            .filter(|id| !orig_head_ids.contains(id))
+            .sorted()
+            .collect())
+    }
 }
 
 impl OpStore for SimpleOpStore {
@@ -228,86 +362,18 @@ impl OpStore for SimpleOpStore {
 
     #[tracing::instrument(skip(self))]
     fn gc(&self, head_ids: &[OperationId], keep_newer: SystemTime) -> OpStoreResult<()> {
-        let to_op_id = |entry: &fs::DirEntry| -> Option<OperationId> {
-            let name = entry.file_name().into_string().ok()?;
-            OperationId::try_from_hex(&name).ok()
-        };
-        let to_view_id = |entry: &fs::DirEntry| -> Option<ViewId> {
-            let name = entry.file_name().into_string().ok()?;
-            ViewId::try_from_hex(&name).ok()
-        };
-        let remove_file_if_not_new = |entry: &fs::DirEntry| -> Result<(), PathError> {
-            let path = entry.path();
-            // Check timestamp, but there's still TOCTOU problem if an existing
-            // file is renewed.
-            let metadata = entry.metadata().context(&path)?;
-            let mtime = metadata.modified().expect("unsupported platform?");
-            if mtime > keep_newer {
-                tracing::trace!(?path, "not removing");
-                Ok(())
-            } else {
-                tracing::trace!(?path, "removing");
-                fs::remove_file(&path).context(&path)
-            }
-        };
-
-        // Reachable objects are resolved without considering the keep_newer
-        // parameter. We could collect ancestors of the "new" operations here,
-        // but more files can be added anyway after that.
-        let read_op = |id: &OperationId| self.read_operation(id).map(|data| (id.clone(), data));
-        let reachable_ops: HashMap<OperationId, Operation> = dag_walk::dfs_ok(
-            head_ids.iter().map(read_op),
-            |(id, _)| id.clone(),
-            |(_, data)| data.parents.iter().map(read_op).collect_vec(),
-        )
-        .try_collect()?;
-        let reachable_views: HashSet<&ViewId> =
-            reachable_ops.values().map(|data| &data.view_id).collect();
-        tracing::info!(
-            reachable_op_count = reachable_ops.len(),
-            reachable_view_count = reachable_views.len(),
-            "collected reachable objects"
-        );
-
-        let prune_ops = || -> Result<(), PathError> {
-            let op_dir = self.path.join("operations");
-            for entry in op_dir.read_dir().context(&op_dir)? {
-                let entry = entry.context(&op_dir)?;
-                let Some(id) = to_op_id(&entry) else {
-                    tracing::trace!(?entry, "skipping invalid file name");
-                    continue;
-                };
-                if reachable_ops.contains_key(&id) {
-                    continue;
-                }
-                // If the operation was added after collecting reachable_views,
-                // its view mtime would also be renewed. So there's no need to
-                // update the reachable_views set to preserve the view.
-                remove_file_if_not_new(&entry)?;
-            }
-            Ok(())
-        };
-        prune_ops().map_err(|err| OpStoreError::Other(err.into()))?;
-
-        let prune_views = || -> Result<(), PathError> {
-            let view_dir = self.path.join("views");
-            for entry in view_dir.read_dir().context(&view_dir)? {
-                let entry = entry.context(&view_dir)?;
-                let Some(id) = to_view_id(&entry) else {
-                    tracing::trace!(?entry, "skipping invalid file name");
-                    continue;
-                };
-                if reachable_views.contains(&id) {
-                    continue;
-                }
-                remove_file_if_not_new(&entry)?;
-            }
-            Ok(())
-        };
-        prune_views().map_err(|err| OpStoreError::Other(err.into()))?;
-
+        self.gc_impl(head_ids, keep_newer, None)?;
         Ok(())
     }
+
+    fn gc_keeping_recent_ops(
+        &self,
+        head_ids: &[OperationId],
+        keep_newer: SystemTime,
+        keep_ops_newer: SystemTime,
+    ) -> OpStoreResult<Vec<OperationId>> {
+        self.gc_impl(head_ids, keep_newer, Some(keep_ops_newer))
+    }
 }
 
 fn io_to_read_error(err: std::io::Error, id: &impl ObjectId) -> OpStoreError {
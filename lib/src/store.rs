@@ -15,17 +15,17 @@
 #![allow(missing_docs)]
 
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 use std::io::Read;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use std::time::SystemTime;
 
 use pollster::FutureExt;
 
 use crate::backend::{
-    self, Backend, BackendResult, ChangeId, CommitId, ConflictId, FileId, MergedTreeId, SigningFn,
-    SymlinkId, TreeId,
+    self, Backend, BackendError, BackendResult, ChangeId, CommitId, ConflictId, FileId,
+    MergedTreeId, SigningFn, SymlinkId, TreeId, TreeValue,
 };
 use crate::commit::Commit;
 use crate::index::Index;
@@ -44,6 +44,41 @@ pub struct Store {
     commit_cache: RwLock<HashMap<CommitId, Arc<backend::Commit>>>,
     tree_cache: RwLock<HashMap<(RepoPathBuf, TreeId), Arc<backend::Tree>>>,
     use_tree_conflict_format: bool,
+    // Disabled by default to avoid overhead.
+    write_file_tracker: OnceLock<Mutex<WriteFileTracker>>,
+}
+
+/// Statistics about `Store::write_file()` calls.
+///
+/// Only file ids seen by this `Store` instance are taken into account. A write
+/// counted as unseen may still be deduplicated by the backend.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WriteFileStats {
+    /// Number of writes whose file id was already seen by this store.
+    pub seen_count: usize,
+    /// Number of writes whose file id wasn't seen by this store.
+    pub unseen_count: usize,
+}
+
+/// Tracks file ids seen by the store to count repeated writes.
+///
+/// File contents are content-addressed, so a write is repeated if the
+/// resulting file id has been written before or is referenced by a tree loaded
+/// through the store.
+#[derive(Debug, Default)]
+struct WriteFileTracker {
+    stats: WriteFileStats,
+    known_ids: HashSet<FileId>,
+}
+
+impl WriteFileTracker {
+    fn add_tree(&mut self, tree: &backend::Tree) {
+        let file_ids = tree.entries().filter_map(|entry| match entry.value() {
+            TreeValue::File { id, .. } => Some(id.clone()),
+            _ => None,
+        });
+        self.known_ids.extend(file_ids);
+    }
 }
 
 impl Debug for Store {
@@ -66,9 +101,27 @@ impl Store {
             commit_cache: Default::default(),
             tree_cache: Default::default(),
             use_tree_conflict_format,
+            write_file_tracker: OnceLock::new(),
         })
     }
 
+    /// Starts counting writes of file contents already seen by this store.
+    pub fn enable_write_file_stats(&self) {
+        self.write_file_tracker.get_or_init(|| {
+            let mut tracker = WriteFileTracker::default();
+            for tree in self.tree_cache.read().unwrap().values() {
+                tracker.add_tree(tree);
+            }
+            Mutex::new(tracker)
+        });
+    }
+
+    /// Returns statistics about file writes if enabled.
+    pub fn write_file_stats(&self) -> Option<WriteFileStats> {
+        let tracker = self.write_file_tracker.get()?;
+        Some(tracker.lock().unwrap().stats.clone())
+    }
+
     pub fn backend_impl(&self) -> &dyn Any {
         self.backend.as_any()
     }
@@ -180,6 +233,9 @@ impl Store {
             }
         }
         let data = self.backend.read_tree(dir, id).await?;
+        if let Some(tracker) = self.write_file_tracker.get() {
+            tracker.lock().unwrap().add_tree(&data);
+        }
         let data = Arc::new(data);
         let mut write_locked_cache = self.tree_cache.write().unwrap();
         write_locked_cache.insert(key, data.clone());
@@ -205,6 +261,9 @@ impl Store {
         tree: backend::Tree,
     ) -> BackendResult<Tree> {
         let tree_id = self.backend.write_tree(path, &tree)?;
+        if let Some(tracker) = self.write_file_tracker.get() {
+            tracker.lock().unwrap().add_tree(&tree);
+        }
         let data = Arc::new(tree);
         {
             let mut write_locked_cache = self.tree_cache.write().unwrap();
@@ -218,6 +277,19 @@ impl Store {
         self.read_file_async(path, id).block_on()
     }
 
+    /// Reads the whole content of the file.
+    pub fn read_file_content(&self, path: &RepoPath, id: &FileId) -> BackendResult<Vec<u8>> {
+        let mut content = vec![];
+        self.read_file(path, id)?
+            .read_to_end(&mut content)
+            .map_err(|err| BackendError::ReadFile {
+                path: path.to_owned(),
+                id: id.clone(),
+                source: err.into(),
+            })?;
+        Ok(content)
+    }
+
     pub async fn read_file_async(
         &self,
         path: &RepoPath,
@@ -227,7 +299,16 @@ impl Store {
     }
 
     pub fn write_file(&self, path: &RepoPath, contents: &mut dyn Read) -> BackendResult<FileId> {
-        self.backend.write_file(path, contents)
+        let id = self.backend.write_file(path, contents)?;
+        if let Some(tracker) = self.write_file_tracker.get() {
+            let mut tracker = tracker.lock().unwrap();
+            if tracker.known_ids.insert(id.clone()) {
+                tracker.stats.unseen_count += 1;
+            } else {
+                tracker.stats.seen_count += 1;
+            }
+        }
+        Ok(id)
     }
 
     pub fn read_symlink(&self, path: &RepoPath, id: &SymlinkId) -> BackendResult<String> {
@@ -34,7 +34,7 @@ pub enum StringPatternParseError {
 
 /// Pattern to be tested against string property like commit description or
 /// branch name.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum StringPattern {
     /// Matches strings exactly equal to `string`.
     Exact(String),
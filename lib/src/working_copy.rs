@@ -98,8 +98,11 @@ pub trait LockedWorkingCopy {
     /// The tree at the time the lock was taken
     fn old_tree_id(&self) -> &MergedTreeId;
 
-    /// Snapshot the working copy and return the tree id.
-    fn snapshot(&mut self, options: SnapshotOptions) -> Result<MergedTreeId, SnapshotError>;
+    /// Snapshot the working copy and return the tree id and stats.
+    fn snapshot(
+        &mut self,
+        options: SnapshotOptions,
+    ) -> Result<(MergedTreeId, SnapshotStats), SnapshotError>;
 
     /// Check out the specified commit in the working copy.
     fn check_out(&mut self, commit: &Commit) -> Result<CheckoutStats, CheckoutError>;
@@ -154,6 +157,8 @@ pub enum SnapshotError {
     BackendError(#[from] BackendError),
     /// A file was larger than the specified maximum file size for new
     /// (previously untracked) files.
+    #[deprecated = "oversized new files are reported in `SnapshotStats::skipped_large_files` \
+                    instead"]
     #[error("New file {path} of size ~{size} exceeds snapshot.max-new-file-size ({max_size})")]
     NewFileTooLarge {
         /// The path of the large file.
@@ -195,9 +200,13 @@ pub struct SnapshotOptions<'a> {
     /// The size of the largest file that should be allowed to become tracked
     /// (already tracked files are always snapshotted). If there are larger
     /// files in the working copy, then `LockedWorkingCopy::snapshot()` may
-    /// (depending on implementation)
-    /// return `SnapshotError::NewFileTooLarge`.
+    /// (depending on implementation) leave them untracked and report them in
+    /// `SnapshotStats::skipped_large_files`.
     pub max_new_file_size: u64,
+    /// Whether to snapshot the contents of the files symlinks point to instead
+    /// of the symlinks themselves. Broken and looping symlinks are errors.
+    /// Symlinks to directories are still recorded as symlinks.
+    pub follow_symlinks: bool,
 }
 
 impl SnapshotOptions<'_> {
@@ -208,6 +217,7 @@ impl SnapshotOptions<'_> {
             fsmonitor_settings: FsmonitorSettings::None,
             progress: None,
             max_new_file_size: u64::MAX,
+            follow_symlinks: false,
         }
     }
 }
@@ -215,6 +225,28 @@ impl SnapshotOptions<'_> {
 /// A callback for getting progress updates.
 pub type SnapshotProgress<'a> = dyn Fn(&RepoPath) + 'a + Sync;
 
+/// Stats about a snapshot operation on a working copy.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SnapshotStats {
+    /// Paths of files that were modified in the working copy and still parse
+    /// as conflicts. Sorted by path.
+    pub conflicted_paths: Vec<RepoPathBuf>,
+    /// New files that were left untracked because they were larger than
+    /// `SnapshotOptions::max_new_file_size`. Sorted by path.
+    pub skipped_large_files: Vec<SkippedLargeFile>,
+}
+
+/// A new file that was too large to be snapshotted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SkippedLargeFile {
+    /// The path of the large file.
+    pub path: RepoPathBuf,
+    /// The size of the large file.
+    pub size: HumanByteSize,
+    /// The maximum allowed size.
+    pub max_size: HumanByteSize,
+}
+
 /// Stats about a checkout operation on a working copy. All "files" mentioned
 /// below may also be symlinks or materialized conflicts.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -147,11 +147,31 @@ impl Workspace {
     pub fn init_local(
         user_settings: &UserSettings,
         workspace_root: &Path,
+    ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
+        Self::init_local_with_working_copy_factory(
+            user_settings,
+            workspace_root,
+            &*default_working_copy_factory(),
+        )
+    }
+
+    /// Same as [`Self::init_local()`], but uses the given working-copy
+    /// implementation.
+    pub fn init_local_with_working_copy_factory(
+        user_settings: &UserSettings,
+        workspace_root: &Path,
+        working_copy_factory: &dyn WorkingCopyFactory,
     ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
         let backend_initializer: &BackendInitializer =
             &|_settings, store_path| Ok(Box::new(LocalBackend::init(store_path)));
         let signer = Signer::from_settings(user_settings)?;
-        Self::init_with_backend(user_settings, workspace_root, backend_initializer, signer)
+        Self::init_with_backend_and_working_copy_factory(
+            user_settings,
+            workspace_root,
+            backend_initializer,
+            signer,
+            working_copy_factory,
+        )
     }
 
     /// Initializes a workspace with a new Git backend and bare Git repo in
@@ -160,6 +180,21 @@ impl Workspace {
     pub fn init_internal_git(
         user_settings: &UserSettings,
         workspace_root: &Path,
+    ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
+        Self::init_internal_git_with_working_copy_factory(
+            user_settings,
+            workspace_root,
+            &*default_working_copy_factory(),
+        )
+    }
+
+    /// Same as [`Self::init_internal_git()`], but uses the given working-copy
+    /// implementation.
+    #[cfg(feature = "git")]
+    pub fn init_internal_git_with_working_copy_factory(
+        user_settings: &UserSettings,
+        workspace_root: &Path,
+        working_copy_factory: &dyn WorkingCopyFactory,
     ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
         let backend_initializer: &BackendInitializer = &|settings, store_path| {
             Ok(Box::new(crate::git_backend::GitBackend::init_internal(
@@ -167,7 +202,13 @@ impl Workspace {
             )?))
         };
         let signer = Signer::from_settings(user_settings)?;
-        Self::init_with_backend(user_settings, workspace_root, backend_initializer, signer)
+        Self::init_with_backend_and_working_copy_factory(
+            user_settings,
+            workspace_root,
+            backend_initializer,
+            signer,
+            working_copy_factory,
+        )
     }
 
     /// Initializes a workspace with a new Git backend and Git repo that shares
@@ -176,6 +217,21 @@ impl Workspace {
     pub fn init_colocated_git(
         user_settings: &UserSettings,
         workspace_root: &Path,
+    ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
+        Self::init_colocated_git_with_working_copy_factory(
+            user_settings,
+            workspace_root,
+            &*default_working_copy_factory(),
+        )
+    }
+
+    /// Same as [`Self::init_colocated_git()`], but uses the given working-copy
+    /// implementation.
+    #[cfg(feature = "git")]
+    pub fn init_colocated_git_with_working_copy_factory(
+        user_settings: &UserSettings,
+        workspace_root: &Path,
+        working_copy_factory: &dyn WorkingCopyFactory,
     ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
         let backend_initializer = |settings: &UserSettings,
                                    store_path: &Path|
@@ -197,7 +253,13 @@ impl Workspace {
             Ok(Box::new(backend))
         };
         let signer = Signer::from_settings(user_settings)?;
-        Self::init_with_backend(user_settings, workspace_root, &backend_initializer, signer)
+        Self::init_with_backend_and_working_copy_factory(
+            user_settings,
+            workspace_root,
+            &backend_initializer,
+            signer,
+            working_copy_factory,
+        )
     }
 
     /// Initializes a workspace with an existing Git repo at the specified path.
@@ -209,6 +271,23 @@ impl Workspace {
         user_settings: &UserSettings,
         workspace_root: &Path,
         git_repo_path: &Path,
+    ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
+        Self::init_external_git_with_working_copy_factory(
+            user_settings,
+            workspace_root,
+            git_repo_path,
+            &*default_working_copy_factory(),
+        )
+    }
+
+    /// Same as [`Self::init_external_git()`], but uses the given working-copy
+    /// implementation.
+    #[cfg(feature = "git")]
+    pub fn init_external_git_with_working_copy_factory(
+        user_settings: &UserSettings,
+        workspace_root: &Path,
+        git_repo_path: &Path,
+        working_copy_factory: &dyn WorkingCopyFactory,
     ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
         let backend_initializer = |settings: &UserSettings,
                                    store_path: &Path|
@@ -237,7 +316,13 @@ impl Workspace {
             Ok(Box::new(backend))
         };
         let signer = Signer::from_settings(user_settings)?;
-        Self::init_with_backend(user_settings, workspace_root, &backend_initializer, signer)
+        Self::init_with_backend_and_working_copy_factory(
+            user_settings,
+            workspace_root,
+            &backend_initializer,
+            signer,
+            working_copy_factory,
+        )
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -293,6 +378,22 @@ impl Workspace {
         workspace_root: &Path,
         backend_initializer: &BackendInitializer,
         signer: Signer,
+    ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
+        Self::init_with_backend_and_working_copy_factory(
+            user_settings,
+            workspace_root,
+            backend_initializer,
+            signer,
+            &*default_working_copy_factory(),
+        )
+    }
+
+    fn init_with_backend_and_working_copy_factory(
+        user_settings: &UserSettings,
+        workspace_root: &Path,
+        backend_initializer: &BackendInitializer,
+        signer: Signer,
+        working_copy_factory: &dyn WorkingCopyFactory,
     ) -> Result<(Self, Arc<ReadonlyRepo>), WorkspaceInitError> {
         Self::init_with_factories(
             user_settings,
@@ -303,7 +404,7 @@ impl Workspace {
             ReadonlyRepo::default_op_heads_store_initializer(),
             ReadonlyRepo::default_index_store_initializer(),
             ReadonlyRepo::default_submodule_store_initializer(),
-            &*default_working_copy_factory(),
+            working_copy_factory,
             WorkspaceId::default(),
         )
     }
@@ -15,7 +15,8 @@
 use indoc::indoc;
 use jj_lib::backend::FileId;
 use jj_lib::conflicts::{
-    extract_as_single_hunk, materialize_merge_result, parse_conflict, update_from_content,
+    extract_as_single_hunk, is_conflict_marker_line, materialize_merge_result, parse_conflict,
+    update_from_content,
 };
 use jj_lib::merge::Merge;
 use jj_lib::repo::Repo;
@@ -507,6 +508,28 @@ line 5
     )
 }
 
+#[test]
+fn test_is_conflict_marker_line() {
+    assert!(is_conflict_marker_line(b"<<<<<<<\n"));
+    assert!(is_conflict_marker_line(b"<<<<<<< Conflict 1 of 1\n"));
+    assert!(is_conflict_marker_line(
+        b"%%%%%%% Changes from base to side #1\n"
+    ));
+    assert!(is_conflict_marker_line(b"------- Contents of base\n"));
+    assert!(is_conflict_marker_line(b"+++++++ Contents of side #2\n"));
+    assert!(is_conflict_marker_line(b">>>>>>> Conflict 1 of 1 ends\n"));
+    // Not at the start of the line
+    assert!(!is_conflict_marker_line(b" <<<<<<<\n"));
+    assert!(!is_conflict_marker_line(b"a<<<<<<<\n"));
+    // Wrong length or missing separator
+    assert!(!is_conflict_marker_line(b"<<<<<<\n"));
+    assert!(!is_conflict_marker_line(b"<<<<<<<x\n"));
+    // Not a complete line
+    assert!(!is_conflict_marker_line(b"<<<<<<<"));
+    // Not a marker written by jj
+    assert!(!is_conflict_marker_line(b"=======\n"));
+}
+
 #[test]
 fn test_parse_conflict_simple() {
     insta::assert_debug_snapshot!(
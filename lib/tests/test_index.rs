@@ -114,6 +114,9 @@ fn test_index_commits_standard_cases() {
     assert_eq!(generation_number(index, commit_f.id()), 5);
     assert_eq!(generation_number(index, commit_g.id()), 6);
     assert_eq!(generation_number(index, commit_h.id()), 5);
+    assert_eq!(repo.index().generation_number(commit_g.id()), Some(6));
+    let missing_id = CommitId::from_hex("ffffff");
+    assert_eq!(repo.index().generation_number(&missing_id), None);
 
     assert!(index.is_ancestor(root_commit_id, commit_a.id()));
     assert!(!index.is_ancestor(commit_a.id(), root_commit_id));
@@ -130,6 +133,64 @@ fn test_index_commits_standard_cases() {
     assert!(index.is_ancestor(commit_a.id(), commit_h.id()));
 }
 
+#[test]
+fn test_index_ancestors_at_generation() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // o G
+    // o F
+    // |\
+    // | o E
+    // | o D
+    // | o C
+    // o | B
+    // |/
+    // o A
+    // o root
+
+    let root_commit_id = repo.store().root_commit_id().clone();
+    let mut tx = repo.start_transaction(&settings);
+    let mut graph_builder = CommitGraphBuilder::new(&settings, tx.mut_repo());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_d = graph_builder.commit_with_parents(&[&commit_c]);
+    let commit_e = graph_builder.commit_with_parents(&[&commit_d]);
+    let commit_f = graph_builder.commit_with_parents(&[&commit_b, &commit_e]);
+    let commit_g = graph_builder.commit_with_parents(&[&commit_f]);
+    let repo = tx.commit("test");
+
+    let index = as_readonly_composite(&repo);
+    let ancestors_at = |generation: u32| -> HashSet<CommitId> {
+        let pos = index.commit_id_to_pos(commit_g.id()).unwrap();
+        index
+            .ancestors_at_generation(pos, generation)
+            .into_iter()
+            .map(|pos| index.entry_by_pos(pos).commit_id())
+            .collect()
+    };
+
+    assert_eq!(ancestors_at(0), hashset! {commit_g.id().clone()});
+    assert_eq!(ancestors_at(1), hashset! {commit_f.id().clone()});
+    assert_eq!(
+        ancestors_at(2),
+        hashset! {commit_b.id().clone(), commit_e.id().clone()}
+    );
+    assert_eq!(
+        ancestors_at(3),
+        hashset! {commit_a.id().clone(), commit_d.id().clone()}
+    );
+    assert_eq!(
+        ancestors_at(4),
+        hashset! {root_commit_id.clone(), commit_c.id().clone()}
+    );
+    assert_eq!(ancestors_at(5), hashset! {commit_a.id().clone()});
+    assert_eq!(ancestors_at(6), hashset! {root_commit_id.clone()});
+    assert_eq!(ancestors_at(7), hashset! {});
+}
+
 #[test]
 fn test_index_commits_criss_cross() {
     let settings = testutils::user_settings();
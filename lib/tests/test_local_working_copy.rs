@@ -16,23 +16,25 @@
 use std::os::unix::fs::PermissionsExt;
 #[cfg(unix)]
 use std::os::unix::net::UnixListener;
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
+use assert_matches::assert_matches;
 use indoc::indoc;
 use itertools::Itertools;
 use jj_lib::backend::{MergedTreeId, TreeId, TreeValue};
 use jj_lib::file_util::{check_symlink_support, try_symlink};
-use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::fsmonitor::{Fsmonitor, FsmonitorClock, FsmonitorError, FsmonitorSettings};
 use jj_lib::local_working_copy::LocalWorkingCopy;
 use jj_lib::merge::{Merge, MergedTreeValue};
 use jj_lib::merged_tree::{MergedTree, MergedTreeBuilder};
 use jj_lib::op_store::{OperationId, WorkspaceId};
+use jj_lib::protos::working_copy::watchman_clock;
 use jj_lib::repo::{ReadonlyRepo, Repo};
 use jj_lib::repo_path::{RepoPath, RepoPathBuf, RepoPathComponent};
 use jj_lib::secret_backend::SecretBackend;
-use jj_lib::settings::UserSettings;
-use jj_lib::working_copy::{CheckoutStats, SnapshotError, SnapshotOptions};
+use jj_lib::settings::{HumanByteSize, UserSettings};
+use jj_lib::working_copy::{CheckoutStats, SkippedLargeFile, SnapshotOptions};
 use jj_lib::workspace::{default_working_copy_factories, LockedWorkspace, Workspace};
 use test_case::test_case;
 use testutils::{
@@ -715,6 +717,71 @@ fn test_materialize_snapshot_conflicted_files() {
     );
 }
 
+#[test]
+fn test_snapshot_stats_conflicted_paths() {
+    let settings = testutils::user_settings();
+    let mut test_workspace = TestWorkspace::init(&settings);
+    let repo = &test_workspace.repo.clone();
+    let ws = &mut test_workspace.workspace;
+    let workspace_root = ws.workspace_root().clone();
+
+    let file1_path = RepoPath::from_internal_string("file1");
+    let file2_path = RepoPath::from_internal_string("file2");
+    let file3_path = RepoPath::from_internal_string("file3");
+    let side1_tree = create_tree(
+        repo,
+        &[
+            (file1_path, "a\n"),
+            (file2_path, "1\n"),
+            (file3_path, "x\n"),
+        ],
+    );
+    let base_tree = create_tree(
+        repo,
+        &[
+            (file1_path, "b\n"),
+            (file2_path, "2\n"),
+            (file3_path, "y\n"),
+        ],
+    );
+    let side2_tree = create_tree(
+        repo,
+        &[
+            (file1_path, "c\n"),
+            (file2_path, "3\n"),
+            (file3_path, "z\n"),
+        ],
+    );
+    let merged_tree = side1_tree.merge(&base_tree, &side2_tree).unwrap();
+    let commit = commit_with_tree(repo.store(), merged_tree.id());
+    ws.check_out(repo.op_id().clone(), None, &commit).unwrap();
+
+    // file1 is edited but still has conflict markers, file2 is resolved, and
+    // file3 is left untouched.
+    testutils::write_working_copy_file(
+        &workspace_root,
+        file1_path,
+        indoc! {"
+            <<<<<<< Conflict 1 of 1
+            %%%%%%% Changes from base to side #1
+            -b
+            +a_edited
+            +++++++ Contents of side #2
+            c
+            >>>>>>> Conflict 1 of 1 ends
+        "},
+    );
+    testutils::write_working_copy_file(&workspace_root, file2_path, "resolved\n");
+
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    let (_tree_id, stats) = locked_ws
+        .locked_wc()
+        .snapshot(SnapshotOptions::empty_for_test())
+        .unwrap();
+    locked_ws.finish(repo.op_id().clone()).unwrap();
+    assert_eq!(stats.conflicted_paths, vec![file1_path.to_owned()]);
+}
+
 #[test]
 fn test_snapshot_racy_timestamps() {
     // Tests that file modifications are detected even if they happen the same
@@ -732,7 +799,7 @@ fn test_snapshot_racy_timestamps() {
             .workspace
             .start_working_copy_mutation()
             .unwrap();
-        let new_tree_id = locked_ws
+        let (new_tree_id, _stats) = locked_ws
             .locked_wc()
             .snapshot(SnapshotOptions::empty_for_test())
             .unwrap();
@@ -766,7 +833,7 @@ fn test_snapshot_special_file() {
 
     // Snapshot the working copy with the socket file
     let mut locked_ws = ws.start_working_copy_mutation().unwrap();
-    let tree_id = locked_ws
+    let (tree_id, _stats) = locked_ws
         .locked_wc()
         .snapshot(SnapshotOptions::empty_for_test())
         .unwrap();
@@ -1189,6 +1256,7 @@ fn test_fsmonitor() {
                 ..SnapshotOptions::empty_for_test()
             })
             .unwrap()
+            .0
     };
 
     {
@@ -1247,6 +1315,87 @@ fn test_fsmonitor() {
     }
 }
 
+#[test]
+fn test_fsmonitor_custom() {
+    #[derive(Debug, Default)]
+    struct CustomFsmonitor {
+        changed_files: Mutex<Vec<PathBuf>>,
+        previous_clocks: Mutex<Vec<Option<FsmonitorClock>>>,
+    }
+
+    impl Fsmonitor for CustomFsmonitor {
+        fn query_changed_files(
+            &self,
+            _working_copy_path: &Path,
+            previous_clock: Option<FsmonitorClock>,
+        ) -> Result<(Option<FsmonitorClock>, Option<Vec<PathBuf>>), FsmonitorError> {
+            let mut previous_clocks = self.previous_clocks.lock().unwrap();
+            previous_clocks.push(previous_clock);
+            let clock = FsmonitorClock {
+                watchman_clock: Some(watchman_clock::WatchmanClock::StringClock(format!(
+                    "c{}",
+                    previous_clocks.len()
+                ))),
+            };
+            let changed_files = self.changed_files.lock().unwrap().clone();
+            Ok((Some(clock), Some(changed_files)))
+        }
+    }
+
+    let settings = testutils::user_settings();
+    let mut test_workspace = TestWorkspace::init(&settings);
+    let repo = &test_workspace.repo;
+    let workspace_root = test_workspace.workspace.workspace_root().clone();
+    let ws = &mut test_workspace.workspace;
+
+    let foo_path = RepoPath::from_internal_string("foo");
+    let bar_path = RepoPath::from_internal_string("bar");
+    testutils::write_working_copy_file(&workspace_root, foo_path, "foo\n");
+    testutils::write_working_copy_file(&workspace_root, bar_path, "bar\n");
+
+    let fsmonitor = Arc::new(CustomFsmonitor::default());
+    let snapshot = |locked_ws: &mut LockedWorkspace| {
+        locked_ws
+            .locked_wc()
+            .snapshot(SnapshotOptions {
+                fsmonitor_settings: FsmonitorSettings::Custom(fsmonitor.clone()),
+                ..SnapshotOptions::empty_for_test()
+            })
+            .unwrap()
+            .0
+    };
+
+    // Only the files reported by the monitor are snapshotted
+    *fsmonitor.changed_files.lock().unwrap() = vec![foo_path.to_fs_path(Path::new(""))];
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    let tree_id = snapshot(&mut locked_ws);
+    insta::assert_snapshot!(testutils::dump_tree(repo.store(), &tree_id), @r###"
+    tree d5e38c0a1b0ee5de47c5
+      file "foo" (e99c2057c15160add351): "foo\n"
+    "###);
+    locked_ws.finish(repo.op_id().clone()).unwrap();
+
+    // The clock returned by the previous query is saved and passed back
+    *fsmonitor.changed_files.lock().unwrap() = vec![bar_path.to_fs_path(Path::new(""))];
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    let tree_id = snapshot(&mut locked_ws);
+    insta::assert_snapshot!(testutils::dump_tree(repo.store(), &tree_id), @r###"
+    tree a667b9074b57da1bcc5e
+      file "bar" (94cc973e7e1aefb7eff6): "bar\n"
+      file "foo" (e99c2057c15160add351): "foo\n"
+    "###);
+    locked_ws.finish(repo.op_id().clone()).unwrap();
+    let previous_clocks = fsmonitor.previous_clocks.lock().unwrap();
+    assert_eq!(previous_clocks.len(), 2);
+    assert_eq!(previous_clocks[0], None);
+    assert_eq!(
+        previous_clocks[1]
+            .as_ref()
+            .and_then(|clock| clock.watchman_clock.clone()),
+        Some(watchman_clock::WatchmanClock::StringClock("c1".to_owned()))
+    );
+}
+
 #[test]
 fn test_snapshot_max_new_file_size() {
     let settings = UserSettings::from_config(
@@ -1270,13 +1419,93 @@ fn test_snapshot_max_new_file_size() {
     test_workspace
         .snapshot()
         .expect("existing files may grow beyond the size limit");
-    // A new file of 1KiB + 1 bytes should fail
+    // A new file of 1KiB + 1 bytes should be left untracked
     std::fs::write(large_path.to_fs_path(&workspace_root), vec![0; 1024 + 1]).unwrap();
-    let err = test_workspace
-        .snapshot()
-        .expect_err("new files beyond the size limit should fail");
-    assert!(
-        matches!(err, SnapshotError::NewFileTooLarge { .. }),
-        "the failure should be attributed to new file size"
+    let mut locked_ws = test_workspace
+        .workspace
+        .start_working_copy_mutation()
+        .unwrap();
+    let (tree_id, stats) = locked_ws
+        .locked_wc()
+        .snapshot(SnapshotOptions {
+            max_new_file_size: settings.max_new_file_size().unwrap(),
+            ..SnapshotOptions::empty_for_test()
+        })
+        .expect("new files beyond the size limit should not fail the snapshot");
+    locked_ws
+        .finish(test_workspace.repo.op_id().clone())
+        .unwrap();
+    assert_eq!(
+        stats.skipped_large_files,
+        vec![SkippedLargeFile {
+            path: large_path.to_owned(),
+            size: HumanByteSize(1024 + 1),
+            max_size: HumanByteSize(1024),
+        }]
     );
+    let tree = test_workspace.repo.store().get_root_tree(&tree_id).unwrap();
+    assert!(tree.path_value(small_path).unwrap().is_present());
+    assert!(tree.path_value(large_path).unwrap().is_absent());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_snapshot_follow_symlinks() {
+    let settings = testutils::user_settings();
+    let mut test_workspace = TestWorkspace::init(&settings);
+    let workspace_root = test_workspace.workspace.workspace_root().clone();
+    let store = test_workspace.repo.store().clone();
+    let op_id = test_workspace.repo.op_id().clone();
+    let ws = &mut test_workspace.workspace;
+
+    let file_path = RepoPath::from_internal_string("file");
+    let link_path = RepoPath::from_internal_string("link");
+    let dir_link_path = RepoPath::from_internal_string("dir_link");
+    std::fs::write(file_path.to_fs_path(&workspace_root), "contents").unwrap();
+    std::fs::create_dir(workspace_root.join("dir")).unwrap();
+    try_symlink("file", link_path.to_fs_path(&workspace_root)).unwrap();
+    try_symlink("dir", dir_link_path.to_fs_path(&workspace_root)).unwrap();
+
+    let snapshot = |ws: &mut Workspace| {
+        let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+        let result = locked_ws.locked_wc().snapshot(SnapshotOptions {
+            follow_symlinks: true,
+            ..SnapshotOptions::empty_for_test()
+        });
+        if result.is_ok() {
+            locked_ws.finish(op_id.clone()).unwrap();
+        }
+        result.map(|(tree_id, _stats)| tree_id)
+    };
+
+    // The symlink to the file is recorded as a copy of the file, but the
+    // symlink to the directory is recorded as is.
+    let tree = store.get_root_tree(&snapshot(ws).unwrap()).unwrap();
+    assert_eq!(
+        tree.path_value(link_path).unwrap(),
+        tree.path_value(file_path).unwrap()
+    );
+    assert_matches!(
+        tree.path_value(dir_link_path).unwrap().into_resolved(),
+        Ok(Some(TreeValue::Symlink(_)))
+    );
+
+    // Updating the target file updates the followed symlink
+    std::fs::write(file_path.to_fs_path(&workspace_root), "modified contents").unwrap();
+    let tree = store.get_root_tree(&snapshot(ws).unwrap()).unwrap();
+    assert_eq!(
+        tree.path_value(link_path).unwrap(),
+        tree.path_value(file_path).unwrap()
+    );
+
+    // Broken symlinks are errors
+    let broken_path = workspace_root.join("broken");
+    try_symlink("nonexistent", &broken_path).unwrap();
+    assert!(snapshot(ws).is_err());
+    std::fs::remove_file(&broken_path).unwrap();
+
+    // Looping symlinks are errors
+    try_symlink("loop2", workspace_root.join("loop1")).unwrap();
+    try_symlink("loop1", workspace_root.join("loop2")).unwrap();
+    assert!(snapshot(ws).is_err());
 }
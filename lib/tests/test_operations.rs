@@ -650,6 +650,14 @@ fn test_gc() {
     assert_eq!(list_dir(&op_dir), expected_op_entries);
     assert_eq!(list_dir(&view_dir), expected_view_entries);
 
+    // B|C|D are no longer reachable, but kept as recent operations
+    let kept_head_ids = op_store
+        .gc_keeping_recent_ops(slice::from_ref(repo_a.op_id()), now, SystemTime::UNIX_EPOCH)
+        .unwrap();
+    assert_eq!(kept_head_ids, [repo_d.op_id().clone()]);
+    assert_eq!(list_dir(&op_dir), expected_op_entries);
+    assert_eq!(list_dir(&view_dir), expected_view_entries);
+
     // B|C|D are no longer reachable
     op_store.gc(slice::from_ref(repo_a.op_id()), now).unwrap();
     expected_op_entries.retain(|name| {
@@ -1028,6 +1028,79 @@ fn test_evaluate_expression_roots() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_heads_roots_order() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction(&settings);
+    let mut_repo = tx.mut_repo();
+    let mut graph_builder = CommitGraphBuilder::new(&settings, mut_repo);
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.initial_commit();
+    let commit3 = graph_builder.initial_commit();
+    let commit4 = graph_builder.commit_with_parents(&[&commit1]);
+
+    // Unrelated commits are ordered newest first regardless of the operand order
+    for revset in [
+        format!(
+            "{}|{}|{}",
+            commit1.id().hex(),
+            commit2.id().hex(),
+            commit3.id().hex()
+        ),
+        format!(
+            "{}|{}|{}",
+            commit3.id().hex(),
+            commit1.id().hex(),
+            commit2.id().hex()
+        ),
+    ] {
+        assert_eq!(
+            resolve_commit_ids(mut_repo, &format!("heads({revset})")),
+            vec![
+                commit3.id().clone(),
+                commit2.id().clone(),
+                commit1.id().clone(),
+            ]
+        );
+        assert_eq!(
+            resolve_commit_ids(mut_repo, &format!("roots({revset})")),
+            vec![
+                commit3.id().clone(),
+                commit2.id().clone(),
+                commit1.id().clone(),
+            ]
+        );
+    }
+
+    // Unrelated commits at different depths are also ordered newest first
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("heads({}|{})", commit2.id().hex(), commit4.id().hex())
+        ),
+        vec![commit4.id().clone(), commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!(
+                "roots({}|{}|{})",
+                commit4.id().hex(),
+                commit2.id().hex(),
+                commit3.id().hex()
+            )
+        ),
+        vec![
+            commit4.id().clone(),
+            commit3.id().clone(),
+            commit2.id().clone()
+        ]
+    );
+}
+
 #[test]
 fn test_evaluate_expression_parents() {
     let settings = testutils::user_settings();
@@ -2295,6 +2368,55 @@ fn test_evaluate_expression_merges() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_mergers() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction(&settings);
+    let mut_repo = tx.mut_repo();
+    let mut graph_builder = CommitGraphBuilder::new(&settings, mut_repo);
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit3 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit4 = graph_builder.commit_with_parents(&[&commit3]);
+    let commit5 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit6 = graph_builder.commit_with_parents(&[&commit2, &commit4]);
+    let commit7 = graph_builder.commit_with_parents(&[&commit6, &commit5]);
+
+    // The merge which brought the commit into the first-parent history
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("mergers({})", commit3.id().hex())),
+        vec![commit6.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("mergers({})", commit5.id().hex())),
+        vec![commit7.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("mergers({}|{})", commit4.id().hex(), commit5.id().hex())
+        ),
+        vec![commit7.id().clone(), commit6.id().clone()]
+    );
+    // Commits in the first-parent history weren't brought in by merges
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("mergers({})", commit2.id().hex())),
+        vec![]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("mergers({})", commit1.id().hex())),
+        vec![]
+    );
+    // The merge itself isn't included
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("mergers({})", commit6.id().hex())),
+        vec![]
+    );
+}
+
 #[test]
 fn test_evaluate_expression_description() {
     let settings = testutils::user_settings();
@@ -238,7 +238,7 @@ impl TestWorkspace {
     /// new operation).
     pub fn snapshot(&mut self) -> Result<MergedTree, SnapshotError> {
         let mut locked_ws = self.workspace.start_working_copy_mutation().unwrap();
-        let tree_id = locked_ws.locked_wc().snapshot(SnapshotOptions {
+        let (tree_id, _stats) = locked_ws.locked_wc().snapshot(SnapshotOptions {
             max_new_file_size: self.settings.max_new_file_size().unwrap(),
             ..SnapshotOptions::empty_for_test()
         })?;